@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
@@ -7,6 +8,62 @@ pub struct PriceAlert {
     pub target: f64,
     pub above: bool,     // true = alert when price >= target
     pub triggered: bool,
+    /// Re-arm once the price is back on the starting side, rather than
+    /// firing once
+    #[serde(default)]
+    pub repeat: bool,
+    /// When it last fired
+    #[serde(default)]
+    pub fired_at: Option<DateTime<Utc>>,
+    /// ISO code of the currency `target` is in, the symbol's native one
+    #[serde(default)]
+    pub currency: Option<String>,
+}
+
+impl PriceAlert {
+    /// Fire if prices between `low` and `high` reach the target. A repeating
+    /// alert that has fired re-arms once they're back short of it.
+    pub fn check(&mut self, low: f64, high: f64) -> bool {
+        if self.triggered {
+            if self.repeat && (if self.above { high < self.target } else { low > self.target }) {
+                self.triggered = false;
+            }
+            return false;
+        }
+        let crossed = if self.above { high >= self.target } else { low <= self.target };
+        if crossed {
+            self.triggered = true;
+            self.fired_at = Some(Utc::now());
+        }
+        crossed
+    }
+
+    /// "≥ $200.00", in the alert's currency
+    pub fn condition(&self) -> String {
+        format!("{} {}{:.2}", if self.above { "≥" } else { "≤" }, self.prefix(), self.target)
+    }
+
+    pub fn prefix(&self) -> String {
+        crate::stock::currency_prefix(self.currency.as_deref())
+    }
+}
+
+/// Read "≥ 200", ">=200", "<180" or a bare "200" into a target and whether
+/// it's an upper one. A bare price is above or below the current one.
+pub fn parse_condition(input: &str, current_price: Option<f64>) -> Option<(f64, bool)> {
+    let input = input.trim();
+    let (above, rest) = if let Some(rest) = input.strip_prefix(['>', '≥']) {
+        (Some(true), rest)
+    } else if let Some(rest) = input.strip_prefix(['<', '≤']) {
+        (Some(false), rest)
+    } else {
+        (None, input)
+    };
+    let target: f64 = rest.trim_start_matches('=').trim().trim_start_matches('$').parse().ok()?;
+    if !target.is_finite() || target <= 0.0 {
+        return None;
+    }
+    Some((target, above.unwrap_or_else(|| current_price.is_none_or(|current| current < target))))
 }
 
 fn alerts_path() -> Option<PathBuf> {
@@ -36,3 +93,35 @@ pub fn save(alerts: &[PriceAlert]) {
         let _ = std::fs::write(path, json);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alert(target: f64, above: bool, currency: Option<&str>) -> PriceAlert {
+        PriceAlert {
+            symbol: "7203.T".to_string(),
+            target,
+            above,
+            triggered: false,
+            repeat: false,
+            fired_at: None,
+            currency: currency.map(String::from),
+        }
+    }
+
+    #[test]
+    fn condition_is_in_the_alert_currency() {
+        assert_eq!(alert(200.0, true, None).condition(), "≥ $200.00");
+        assert_eq!(alert(2_950.0, false, Some("JPY")).condition(), "≤ JP¥2950.00");
+        assert_eq!(alert(88.5, true, Some("CHF")).condition(), "≥ CHF 88.50");
+    }
+
+    #[test]
+    fn alerts_saved_without_a_currency_still_load() {
+        let json = r#"[{"symbol":"AAPL","target":200.0,"above":true,"triggered":false}]"#;
+        let alerts: Vec<PriceAlert> = serde_json::from_str(json).unwrap();
+        assert_eq!(alerts[0].currency, None);
+        assert_eq!(alerts[0].condition(), "≥ $200.00");
+    }
+}
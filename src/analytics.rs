@@ -0,0 +1,314 @@
+//! Summary statistics over a loaded price series or a live trade session, and
+//! the alignment behind the comparison chart.
+
+use chrono::{DateTime, Utc};
+
+/// Largest peak-to-trough fall, as a positive percentage of the peak.
+#[derive(Debug, Clone, Copy)]
+pub struct Drawdown {
+    pub percent: f64,
+    pub peak: DateTime<Utc>,
+    pub trough: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SeriesStats {
+    pub high: (f64, DateTime<Utc>),
+    pub low: (f64, DateTime<Utc>),
+    pub mean: f64,
+    /// Sample standard deviation of prices
+    pub std_dev: f64,
+    /// Standard deviation of log returns scaled to a year; `None` with fewer
+    /// than two returns between positive prices
+    pub annualized_volatility: Option<f64>,
+    /// `None` when the series never falls from a previous high
+    pub max_drawdown: Option<Drawdown>,
+    /// First to last price, in percent
+    pub total_return: f64,
+}
+
+/// Statistics for parallel `prices` and `timestamps`. Returns `None` for an
+/// empty series or one whose first price is not positive.
+pub fn series_stats(prices: &[f64], timestamps: &[DateTime<Utc>]) -> Option<SeriesStats> {
+    let len = prices.len().min(timestamps.len());
+    if len == 0 || prices[0] <= 0.0 {
+        return None;
+    }
+    let (prices, timestamps) = (&prices[..len], &timestamps[..len]);
+
+    let mut high = (prices[0], timestamps[0]);
+    let mut low = high;
+    for (&p, &t) in prices.iter().zip(timestamps) {
+        if p > high.0 {
+            high = (p, t);
+        }
+        if p < low.0 {
+            low = (p, t);
+        }
+    }
+
+    let n = len as f64;
+    let mean = prices.iter().sum::<f64>() / n;
+    let std_dev = if len > 1 {
+        (prices.iter().map(|p| (p - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt()
+    } else {
+        0.0
+    };
+
+    Some(SeriesStats {
+        high,
+        low,
+        mean,
+        std_dev,
+        annualized_volatility: annualized_volatility(prices, timestamps),
+        max_drawdown: max_drawdown(prices, timestamps),
+        total_return: (prices[len - 1] / prices[0] - 1.0) * 100.0,
+    })
+}
+
+/// Bars per year for a series sampled every `step_secs`. Intraday bars only
+/// count the 6.5-hour session and daily bars trading days; anything longer
+/// (weekly, monthly) uses calendar time.
+fn periods_per_year_for_step(step_secs: f64) -> f64 {
+    const TRADING_DAYS: f64 = 252.0;
+    const SESSION_SECS: f64 = 6.5 * 3600.0;
+    if step_secs < 20.0 * 3600.0 {
+        TRADING_DAYS * SESSION_SECS / step_secs
+    } else if step_secs < 3.0 * 86_400.0 {
+        TRADING_DAYS
+    } else {
+        365.25 * 86_400.0 / step_secs
+    }
+}
+
+/// Annualized standard deviation of log returns, in percent. Steps to or from
+/// a non-positive price have no log return and are skipped.
+fn annualized_volatility(prices: &[f64], timestamps: &[DateTime<Utc>]) -> Option<f64> {
+    let returns: Vec<f64> = prices.windows(2)
+        .filter(|w| w[0] > 0.0 && w[1] > 0.0)
+        .map(|w| (w[1] / w[0]).ln())
+        .collect();
+    if returns.len() < 2 {
+        return None;
+    }
+    let n = returns.len() as f64;
+    let mean = returns.iter().sum::<f64>() / n;
+    let variance = returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    Some(variance.sqrt() * periods_per_year(timestamps)?.sqrt() * 100.0)
+}
+
+/// Bars per year implied by the spacing of `timestamps`, for annualizing.
+/// The median gap ignores overnight and weekend jumps in the spacing.
+pub fn periods_per_year(timestamps: &[DateTime<Utc>]) -> Option<f64> {
+    let mut steps: Vec<f64> = timestamps.windows(2)
+        .map(|w| (w[1] - w[0]).num_seconds() as f64)
+        .filter(|&s| s > 0.0)
+        .collect();
+    if steps.is_empty() {
+        return None;
+    }
+    steps.sort_by(|a, b| a.total_cmp(b));
+    Some(periods_per_year_for_step(steps[steps.len() / 2]))
+}
+
+/// Highest price up to and including each point of `prices`.
+pub fn running_max(prices: &[f64]) -> Vec<f64> {
+    prices.iter()
+        .scan(f64::NEG_INFINITY, |peak, &p| {
+            *peak = peak.max(p);
+            Some(*peak)
+        })
+        .collect()
+}
+
+/// Deepest fall below the running maximum of parallel `prices` and
+/// `timestamps`; `None` for an empty series or one that never falls. A fall
+/// from a peak that isn't positive has no percentage and is skipped.
+pub fn max_drawdown(prices: &[f64], timestamps: &[DateTime<Utc>]) -> Option<Drawdown> {
+    if prices.is_empty() || timestamps.is_empty() {
+        return None;
+    }
+    let mut peak = (prices[0], timestamps[0]);
+    let mut worst: Option<Drawdown> = None;
+    for (&p, &t) in prices.iter().zip(timestamps) {
+        if p > peak.0 {
+            peak = (p, t);
+            continue;
+        }
+        if peak.0 <= 0.0 {
+            continue;
+        }
+        let percent = (peak.0 - p) / peak.0 * 100.0;
+        if percent > 0.0 && worst.is_none_or(|w| percent > w.percent) {
+            worst = Some(Drawdown { percent, peak: peak.1, trough: t });
+        }
+    }
+    worst
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SessionStats {
+    pub high: f64,
+    pub low: f64,
+    /// Volume-weighted average price; `None` when no trade carried volume
+    pub vwap: Option<f64>,
+    pub trades: usize,
+}
+
+/// High, low and VWAP over live trades given as `(price, volume)`.
+pub fn session_stats(trades: &[(f64, Option<u64>)]) -> Option<SessionStats> {
+    if trades.is_empty() {
+        return None;
+    }
+    let high = trades.iter().map(|t| t.0).fold(f64::NEG_INFINITY, f64::max);
+    let low = trades.iter().map(|t| t.0).fold(f64::INFINITY, f64::min);
+    let (notional, volume) = trades.iter()
+        .filter_map(|&(p, v)| v.filter(|&v| v > 0).map(|v| (p * v as f64, v as f64)))
+        .fold((0.0, 0.0), |(n, v), (pn, pv)| (n + pn, v + pv));
+    let vwap = (volume > 0.0).then(|| notional / volume);
+    Some(SessionStats { high, low, vwap, trades: trades.len() })
+}
+
+/// One timestamp both series traded at, with each rebased to 100 at the first one.
+#[derive(Debug, Clone, Copy)]
+pub struct RebasedPoint {
+    pub timestamp: DateTime<Utc>,
+    pub base: f64,
+    pub other: f64,
+}
+
+/// Join two series on the timestamps they share and rebase both to 100 at the
+/// first of them. With `by_date`, bars match on their UTC calendar date, for
+/// daily-or-longer bars whose open time depends on the exchange.
+pub fn rebase_common(
+    base: (&[DateTime<Utc>], &[f64]),
+    other: (&[DateTime<Utc>], &[f64]),
+    by_date: bool,
+) -> Vec<RebasedPoint> {
+    use std::collections::HashMap;
+
+    let key = |t: &DateTime<Utc>| if by_date { t.timestamp().div_euclid(86_400) } else { t.timestamp() };
+    let other_by_key: HashMap<i64, f64> = other.0.iter().zip(other.1).map(|(t, &p)| (key(t), p)).collect();
+
+    let joined: Vec<(DateTime<Utc>, f64, f64)> = base.0.iter()
+        .zip(base.1)
+        .filter_map(|(t, &p)| other_by_key.get(&key(t)).map(|&o| (*t, p, o)))
+        .filter(|&(_, p, o)| p > 0.0 && o > 0.0)
+        .collect();
+    let Some(&(_, base_start, other_start)) = joined.first() else { return Vec::new() };
+
+    joined.into_iter()
+        .map(|(timestamp, p, o)| RebasedPoint {
+            timestamp,
+            base: p / base_start * 100.0,
+            other: o / other_start * 100.0,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn days(count: usize) -> Vec<DateTime<Utc>> {
+        (0..count as i64).map(|d| DateTime::from_timestamp(1_700_006_400 + d * 86_400, 0).unwrap()).collect()
+    }
+
+    fn close(actual: f64, expected: f64) -> bool {
+        (actual - expected).abs() < 1e-9
+    }
+
+    #[test]
+    fn series_stats_over_a_fixed_series() {
+        let t = days(4);
+        let stats = series_stats(&[10.0, 12.0, 9.0, 11.0], &t).unwrap();
+        assert_eq!(stats.high, (12.0, t[1]));
+        assert_eq!(stats.low, (9.0, t[2]));
+        assert_eq!(stats.mean, 10.5);
+        assert!(close(stats.std_dev, (5.0f64 / 3.0).sqrt()));
+        assert!(close(stats.total_return, 10.0));
+        // Daily bars annualize over 252 trading days
+        let returns = [1.2f64.ln(), 0.75f64.ln(), (11.0f64 / 9.0).ln()];
+        let mean = returns.iter().sum::<f64>() / 3.0;
+        let variance = returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / 2.0;
+        assert!(close(stats.annualized_volatility.unwrap(), variance.sqrt() * 252f64.sqrt() * 100.0));
+        let drawdown = stats.max_drawdown.unwrap();
+        assert_eq!((drawdown.percent, drawdown.peak, drawdown.trough), (25.0, t[1], t[2]));
+    }
+
+    #[test]
+    fn series_stats_edge_cases() {
+        assert!(series_stats(&[], &[]).is_none());
+        assert!(series_stats(&[0.0, 1.0], &days(2)).is_none());
+
+        let single = series_stats(&[5.0], &days(1)).unwrap();
+        assert_eq!((single.std_dev, single.total_return), (0.0, 0.0));
+        assert!(single.annualized_volatility.is_none() && single.max_drawdown.is_none());
+
+        let flat = series_stats(&[5.0; 4], &days(4)).unwrap();
+        assert_eq!((flat.std_dev, flat.annualized_volatility, flat.total_return), (0.0, Some(0.0), 0.0));
+        assert!(flat.max_drawdown.is_none());
+    }
+
+    #[test]
+    fn volatility_skips_steps_through_non_positive_prices() {
+        // Only 10 -> 11 -> 12.1 has log returns, both 10%
+        let vol = annualized_volatility(&[10.0, 0.0, 10.0, 11.0, 12.1], &days(5)).unwrap();
+        assert!(vol.abs() < 1e-6);
+        assert!(annualized_volatility(&[10.0, -1.0, 10.0, 11.0], &days(4)).is_none());
+        assert!(annualized_volatility(&[10.0, 11.0, 12.0], &[]).is_none());
+    }
+
+    #[test]
+    fn periods_per_year_from_the_median_step() {
+        let minutes: Vec<_> = (0..5).map(|m| DateTime::from_timestamp(1_700_000_040 + m * 60, 0).unwrap()).collect();
+        assert!(close(periods_per_year(&minutes).unwrap(), 252.0 * 390.0));
+        // A weekend between Friday and Monday doesn't lengthen daily bars
+        let mut weekdays = days(5);
+        weekdays.push(weekdays[4] + chrono::Duration::days(3));
+        assert_eq!(periods_per_year(&weekdays), Some(252.0));
+        let weeks: Vec<_> = days(15).into_iter().step_by(7).collect();
+        assert!(close(periods_per_year(&weeks).unwrap(), 365.25 / 7.0));
+
+        assert!(periods_per_year(&[]).is_none());
+        assert!(periods_per_year(&days(1)).is_none());
+        assert!(periods_per_year(&[days(1)[0]; 3]).is_none());
+    }
+
+    #[test]
+    fn running_max_holds_the_high() {
+        assert_eq!(running_max(&[3.0, 1.0, 4.0, 1.0, 5.0]), [3.0, 3.0, 4.0, 4.0, 5.0]);
+        assert_eq!(running_max(&[2.0; 3]), [2.0; 3]);
+        assert!(running_max(&[]).is_empty());
+    }
+
+    #[test]
+    fn max_drawdown_keeps_the_deepest_fall() {
+        // 120 -> 90 is 25%; 130 -> 104 is only 20%
+        let t = days(5);
+        let drawdown = max_drawdown(&[100.0, 120.0, 90.0, 130.0, 104.0], &t).unwrap();
+        assert_eq!((drawdown.percent, drawdown.peak, drawdown.trough), (25.0, t[1], t[2]));
+    }
+
+    #[test]
+    fn max_drawdown_edge_cases() {
+        assert!(max_drawdown(&[], &[]).is_none());
+        assert!(max_drawdown(&[1.0, 2.0, 3.0], &days(3)).is_none());
+        assert!(max_drawdown(&[4.0; 3], &days(3)).is_none());
+        // Falls from a zero or negative peak are skipped rather than infinite
+        assert!(max_drawdown(&[0.0, -1.0], &days(2)).is_none());
+        assert!(max_drawdown(&[-5.0, -10.0], &days(2)).is_none());
+        let t = days(3);
+        let drawdown = max_drawdown(&[0.0, 2.0, 1.0], &t).unwrap();
+        assert_eq!((drawdown.percent, drawdown.peak, drawdown.trough), (50.0, t[1], t[2]));
+    }
+
+    #[test]
+    fn session_stats_weights_by_volume() {
+        let trades = [(10.0, Some(100)), (12.0, Some(300)), (11.0, None), (9.0, Some(0))];
+        let stats = session_stats(&trades).unwrap();
+        assert_eq!((stats.high, stats.low, stats.vwap, stats.trades), (12.0, 9.0, Some(11.5), 4));
+        assert!(session_stats(&[(10.0, None)]).unwrap().vwap.is_none());
+        assert!(session_stats(&[]).is_none());
+    }
+}
@@ -0,0 +1,628 @@
+//! Technical indicators over a price series. Each returns `(index, value)`
+//! points, starting at the first index where the indicator is defined, so the
+//! result can be handed straight to a chart `Dataset`.
+
+/// Simple moving average. A series shorter than `period` yields nothing.
+pub fn sma(prices: &[f64], period: usize) -> Vec<(f64, f64)> {
+    if period == 0 || prices.len() < period {
+        return Vec::new();
+    }
+    prices
+        .windows(period)
+        .enumerate()
+        .map(|(i, w)| ((i + period - 1) as f64, w.iter().sum::<f64>() / period as f64))
+        .collect()
+}
+
+/// Exponential moving average with smoothing `2 / (period + 1)`, seeded with
+/// the SMA of the first `period` prices.
+pub fn ema(prices: &[f64], period: usize) -> Vec<(f64, f64)> {
+    if period == 0 || prices.len() < period {
+        return Vec::new();
+    }
+    let k = 2.0 / (period as f64 + 1.0);
+    let mut value = prices[..period].iter().sum::<f64>() / period as f64;
+    let mut points = Vec::with_capacity(prices.len() - period + 1);
+    points.push(((period - 1) as f64, value));
+    for (i, &price) in prices.iter().enumerate().skip(period) {
+        value = price * k + value * (1.0 - k);
+        points.push((i as f64, value));
+    }
+    points
+}
+
+/// Relative Strength Index with Wilder's smoothing: the first average gain/loss
+/// is a plain mean over `period` changes, then each step keeps `(period - 1) / period`
+/// of the previous average. The first point is at index `period`. RSI is 100
+/// with no losses, or 50 with no moves at all.
+pub fn rsi(prices: &[f64], period: usize) -> Vec<(f64, f64)> {
+    if period == 0 || prices.len() <= period {
+        return Vec::new();
+    }
+    let n = period as f64;
+    let changes: Vec<f64> = prices.windows(2).map(|w| w[1] - w[0]).collect();
+    let mut avg_gain = changes[..period].iter().map(|c| c.max(0.0)).sum::<f64>() / n;
+    let mut avg_loss = changes[..period].iter().map(|c| (-c).max(0.0)).sum::<f64>() / n;
+
+    let value = |gain: f64, loss: f64| match (gain, loss) {
+        (0.0, 0.0) => 50.0,
+        (_, 0.0) => 100.0,
+        _ => 100.0 - 100.0 / (1.0 + gain / loss),
+    };
+    let mut points = Vec::with_capacity(changes.len() - period + 1);
+    points.push((period as f64, value(avg_gain, avg_loss)));
+    for (i, &change) in changes.iter().enumerate().skip(period) {
+        avg_gain = (avg_gain * (n - 1.0) + change.max(0.0)) / n;
+        avg_loss = (avg_loss * (n - 1.0) + (-change).max(0.0)) / n;
+        points.push(((i + 1) as f64, value(avg_gain, avg_loss)));
+    }
+    points
+}
+
+/// MACD line, signal line and histogram, each as `(index, value)` points.
+#[derive(Debug, Clone, Default)]
+pub struct Macd {
+    pub macd: Vec<(f64, f64)>,
+    pub signal: Vec<(f64, f64)>,
+    pub histogram: Vec<(f64, f64)>,
+}
+
+/// MACD = EMA(fast) − EMA(slow); signal = EMA(signal) of the MACD line;
+/// histogram = MACD − signal. Empty when the series is too short for the slow EMA.
+pub fn macd(prices: &[f64], fast: usize, slow: usize, signal: usize) -> Macd {
+    let fast_ema = ema(prices, fast);
+    let slow_ema = ema(prices, slow);
+    // Both are indexed by price position; line them up on the later start
+    let macd: Vec<(f64, f64)> = slow_ema.iter()
+        .filter_map(|&(x, slow_v)| {
+            let fast_v = (x as usize + 1).checked_sub(fast).and_then(|i| fast_ema.get(i))?.1;
+            Some((x, fast_v - slow_v))
+        })
+        .collect();
+
+    let macd_values: Vec<f64> = macd.iter().map(|&(_, v)| v).collect();
+    let offset = macd.first().map_or(0.0, |&(x, _)| x);
+    let signal: Vec<(f64, f64)> = ema(&macd_values, signal)
+        .into_iter()
+        .map(|(i, v)| (i + offset, v))
+        .collect();
+    let histogram = signal.iter()
+        .map(|&(x, s)| (x, macd[(x - offset) as usize].1 - s))
+        .collect();
+
+    Macd { macd, signal, histogram }
+}
+
+/// Slow stochastic %K and its %D signal line, each as `(index, value)` points.
+#[derive(Debug, Clone, Default)]
+pub struct Stochastic {
+    pub k: Vec<(f64, f64)>,
+    pub d: Vec<(f64, f64)>,
+}
+
+/// Stochastic oscillator over parallel high/low/close series. Raw %K is where
+/// the close sits in the `period` high–low range (50 when the range is flat);
+/// %K smooths it with an SMA of `smooth_k` and %D is an SMA of `smooth_d` over %K.
+pub fn stochastic(highs: &[f64], lows: &[f64], closes: &[f64], period: usize, smooth_k: usize, smooth_d: usize) -> Stochastic {
+    let len = highs.len().min(lows.len()).min(closes.len());
+    if period == 0 || len < period {
+        return Stochastic { k: Vec::new(), d: Vec::new() };
+    }
+    let raw: Vec<f64> = (period - 1..len)
+        .map(|i| {
+            let start = i + 1 - period;
+            let high = highs[start..=i].iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+            let low = lows[start..=i].iter().cloned().fold(f64::INFINITY, f64::min);
+            if high > low { (closes[i] - low) / (high - low) * 100.0 } else { 50.0 }
+        })
+        .collect();
+
+    // Shift the smoothed series back onto price positions
+    let shift = |points: Vec<(f64, f64)>, by: f64| -> Vec<(f64, f64)> {
+        points.into_iter().map(|(i, v)| (i + by, v)).collect()
+    };
+    let k = shift(sma(&raw, smooth_k), (period - 1) as f64);
+    let k_values: Vec<f64> = k.iter().map(|&(_, v)| v).collect();
+    let d = shift(sma(&k_values, smooth_d), k.first().map_or(0.0, |&(x, _)| x));
+    Stochastic { k, d }
+}
+
+/// Average True Range with Wilder's smoothing. The true range is the widest of
+/// high − low and the gaps from the previous close; the first ATR is the mean of
+/// the first `period` ranges, at index `period - 1`.
+pub fn atr(highs: &[f64], lows: &[f64], closes: &[f64], period: usize) -> Vec<(f64, f64)> {
+    let len = highs.len().min(lows.len()).min(closes.len());
+    if period == 0 || len < period {
+        return Vec::new();
+    }
+    let true_range = |i: usize| {
+        let range = highs[i] - lows[i];
+        if i == 0 {
+            return range;
+        }
+        let prev = closes[i - 1];
+        range.max((highs[i] - prev).abs()).max((lows[i] - prev).abs())
+    };
+    let n = period as f64;
+    let mut value = (0..period).map(true_range).sum::<f64>() / n;
+    let mut points = Vec::with_capacity(len - period + 1);
+    points.push(((period - 1) as f64, value));
+    for i in period..len {
+        value = (value * (n - 1.0) + true_range(i)) / n;
+        points.push((i as f64, value));
+    }
+    points
+}
+
+/// Which way a fast average crossed a slow one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CrossKind {
+    /// Fast moved above slow
+    Golden,
+    /// Fast moved below slow
+    Death,
+}
+
+/// A crossover at price position `index`, where the slow average was `value`.
+#[derive(Debug, Clone, Copy)]
+pub struct Cross {
+    pub index: usize,
+    pub kind: CrossKind,
+    pub value: f64,
+}
+
+/// Crossovers between two averages given as `(index, value)` points, such as
+/// `sma(prices, 20)` and `sma(prices, 50)`. Bars where the two are equal don't
+/// count as a cross by themselves; the cross lands where the order flips.
+pub fn crossovers(fast: &[(f64, f64)], slow: &[(f64, f64)]) -> Vec<Cross> {
+    let Some(&(fast_start, _)) = fast.first() else { return Vec::new() };
+    let mut crosses = Vec::new();
+    let mut last_sign = 0.0;
+    for &(x, slow_v) in slow {
+        if x < fast_start {
+            continue;
+        }
+        let Some(&(_, fast_v)) = fast.get((x - fast_start) as usize) else { continue };
+        let diff = fast_v - slow_v;
+        if diff == 0.0 {
+            continue;
+        }
+        let sign = diff.signum();
+        if last_sign != 0.0 && sign != last_sign {
+            let kind = if sign > 0.0 { CrossKind::Golden } else { CrossKind::Death };
+            crosses.push(Cross { index: x as usize, kind, value: slow_v });
+        }
+        last_sign = sign;
+    }
+    crosses
+}
+
+/// Least-squares line `value = intercept + slope * index` and the standard
+/// error of its residuals.
+#[derive(Debug, Clone, Copy)]
+pub struct Regression {
+    pub slope: f64,
+    pub intercept: f64,
+    pub std_error: f64,
+}
+
+impl Regression {
+    pub fn at(&self, index: f64) -> f64 {
+        self.intercept + self.slope * index
+    }
+}
+
+/// Ordinary least squares fit of `prices` against their index. Needs at least
+/// three points so the standard error (n − 2 degrees of freedom) is defined.
+pub fn linear_regression(prices: &[f64]) -> Option<Regression> {
+    if prices.len() < 3 {
+        return None;
+    }
+    let n = prices.len() as f64;
+    let mean_x = (n - 1.0) / 2.0;
+    let mean_y = prices.iter().sum::<f64>() / n;
+    let (mut sxy, mut sxx) = (0.0, 0.0);
+    for (i, &y) in prices.iter().enumerate() {
+        let dx = i as f64 - mean_x;
+        sxy += dx * (y - mean_y);
+        sxx += dx * dx;
+    }
+    let slope = sxy / sxx;
+    let intercept = mean_y - slope * mean_x;
+    let residuals = prices.iter()
+        .enumerate()
+        .map(|(i, &y)| (y - (intercept + slope * i as f64)).powi(2))
+        .sum::<f64>();
+    Some(Regression { slope, intercept, std_error: (residuals / (n - 2.0)).sqrt() })
+}
+
+/// On-balance volume: a running total that adds each bar's volume when the
+/// close rose from the previous one, subtracts it when it fell, and skips it
+/// when unchanged. Starts at zero on the first bar.
+pub fn obv(closes: &[f64], volumes: &[f64]) -> Vec<(f64, f64)> {
+    let len = closes.len().min(volumes.len());
+    if len == 0 {
+        return Vec::new();
+    }
+    let mut total = 0.0;
+    let mut points = Vec::with_capacity(len);
+    points.push((0.0, total));
+    for i in 1..len {
+        if closes[i] > closes[i - 1] {
+            total += volumes[i];
+        } else if closes[i] < closes[i - 1] {
+            total -= volumes[i];
+        }
+        points.push((i as f64, total));
+    }
+    points
+}
+
+/// Body no larger than this share of the high–low range makes a doji.
+pub const DOJI_BODY_RATIO: f64 = 0.1;
+/// A hammer's body stays within this share of its range...
+pub const HAMMER_BODY_RATIO: f64 = 0.35;
+/// ...its lower wick is at least this many bodies long...
+pub const HAMMER_WICK_TO_BODY: f64 = 2.0;
+/// ...and its upper wick is at most this share of the range.
+pub const HAMMER_UPPER_WICK_RATIO: f64 = 0.1;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CandlePattern {
+    Doji,
+    Hammer,
+    BullishEngulfing,
+    BearishEngulfing,
+}
+
+impl CandlePattern {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CandlePattern::Doji => "Doji",
+            CandlePattern::Hammer => "Hammer",
+            CandlePattern::BullishEngulfing => "Bullish engulfing",
+            CandlePattern::BearishEngulfing => "Bearish engulfing",
+        }
+    }
+}
+
+/// Classic one- and two-candle patterns over parallel OHLC series, at most one
+/// per candle: engulfing wins over hammer, which wins over doji. Candles with
+/// no range (high == low) never match.
+pub fn candle_patterns(opens: &[f64], highs: &[f64], lows: &[f64], closes: &[f64]) -> Vec<(usize, CandlePattern)> {
+    let len = opens.len().min(highs.len()).min(lows.len()).min(closes.len());
+    let mut found = Vec::new();
+    for i in 0..len {
+        let range = highs[i] - lows[i];
+        if range <= 0.0 {
+            continue;
+        }
+        let body = (closes[i] - opens[i]).abs();
+        let lower_wick = opens[i].min(closes[i]) - lows[i];
+        let upper_wick = highs[i] - opens[i].max(closes[i]);
+
+        let engulfing = (i > 0).then(|| {
+            let (prev_open, prev_close) = (opens[i - 1], closes[i - 1]);
+            let (lo, hi) = (prev_open.min(prev_close), prev_open.max(prev_close));
+            let covers = opens[i].min(closes[i]) <= lo && opens[i].max(closes[i]) >= hi && body > hi - lo;
+            if !covers {
+                None
+            } else if prev_close < prev_open && closes[i] > opens[i] {
+                Some(CandlePattern::BullishEngulfing)
+            } else if prev_close > prev_open && closes[i] < opens[i] {
+                Some(CandlePattern::BearishEngulfing)
+            } else {
+                None
+            }
+        }).flatten();
+
+        let pattern = if let Some(p) = engulfing {
+            p
+        } else if body > DOJI_BODY_RATIO * range
+            && body <= HAMMER_BODY_RATIO * range
+            && lower_wick >= HAMMER_WICK_TO_BODY * body
+            && upper_wick <= HAMMER_UPPER_WICK_RATIO * range
+        {
+            CandlePattern::Hammer
+        } else if body <= DOJI_BODY_RATIO * range {
+            CandlePattern::Doji
+        } else {
+            continue;
+        };
+        found.push((i, pattern));
+    }
+    found
+}
+
+/// Rolling realized volatility: the sample standard deviation of the last
+/// `period` log returns, times `sqrt(periods_per_year)`, in percent. The first
+/// point is at index `period`. Windows that touch a non-positive price have no
+/// log return and are skipped.
+pub fn realized_volatility(prices: &[f64], period: usize, periods_per_year: f64) -> Vec<(f64, f64)> {
+    if period < 2 || prices.len() <= period {
+        return Vec::new();
+    }
+    let returns: Vec<Option<f64>> = prices.windows(2)
+        .map(|w| (w[0] > 0.0 && w[1] > 0.0).then(|| (w[1] / w[0]).ln()))
+        .collect();
+    let n = period as f64;
+    returns.windows(period)
+        .enumerate()
+        .filter_map(|(i, window)| {
+            let window: Vec<f64> = window.iter().copied().collect::<Option<_>>()?;
+            let mean = window.iter().sum::<f64>() / n;
+            let variance = window.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (n - 1.0);
+            Some(((i + period) as f64, variance.sqrt() * periods_per_year.sqrt() * 100.0))
+        })
+        .collect()
+}
+
+/// Classic floor-trader pivot levels from one session's high, low and close.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PivotLevels {
+    pub pivot: f64,
+    pub r1: f64,
+    pub r2: f64,
+    pub s1: f64,
+    pub s2: f64,
+}
+
+impl PivotLevels {
+    /// Labeled levels from highest to lowest.
+    pub fn levels(&self) -> [(&'static str, f64); 5] {
+        [("R2", self.r2), ("R1", self.r1), ("P", self.pivot), ("S1", self.s1), ("S2", self.s2)]
+    }
+}
+
+/// P = (H + L + C) / 3, R1 = 2P − L, S1 = 2P − H, R2 = P + (H − L), S2 = P − (H − L).
+pub fn pivot_points(high: f64, low: f64, close: f64) -> PivotLevels {
+    let pivot = (high + low + close) / 3.0;
+    let range = high - low;
+    PivotLevels {
+        pivot,
+        r1: 2.0 * pivot - low,
+        r2: pivot + range,
+        s1: 2.0 * pivot - high,
+        s2: pivot - range,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_points(actual: &[(f64, f64)], expected: &[(f64, f64)]) {
+        assert_eq!(actual.len(), expected.len(), "{:?}", actual);
+        for (&(x, v), &(ex, ev)) in actual.iter().zip(expected) {
+            assert_eq!(x, ex, "{:?}", actual);
+            assert!((v - ev).abs() < 1e-9, "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    #[test]
+    fn sma_averages_each_window() {
+        assert_points(&sma(&[1.0, 2.0, 3.0, 4.0, 5.0], 3), &[(2.0, 2.0), (3.0, 3.0), (4.0, 4.0)]);
+        assert_points(&sma(&[1.0, 2.0, 6.0], 3), &[(2.0, 3.0)]);
+        assert_points(&sma(&[5.0; 6], 4), &[(3.0, 5.0), (4.0, 5.0), (5.0, 5.0)]);
+    }
+
+    #[test]
+    fn ema_is_seeded_with_the_sma() {
+        // k = 0.5: 4, then 8 * 0.5 + 4 * 0.5, then 12 * 0.5 + 6 * 0.5
+        assert_points(&ema(&[2.0, 4.0, 6.0, 8.0, 12.0], 3), &[(2.0, 4.0), (3.0, 6.0), (4.0, 9.0)]);
+        assert_points(&ema(&[5.0; 5], 2), &[(1.0, 5.0), (2.0, 5.0), (3.0, 5.0), (4.0, 5.0)]);
+    }
+
+    #[test]
+    fn averages_need_a_full_period() {
+        for average in [sma, ema] {
+            assert!(average(&[], 3).is_empty());
+            assert!(average(&[1.0, 2.0], 3).is_empty());
+            assert!(average(&[1.0, 2.0], 0).is_empty());
+        }
+    }
+
+    #[test]
+    fn rsi_uses_wilder_smoothing() {
+        // Changes +1 -1 +2 +1 -1: even, then gains 1.25 vs 0.25, 1.125 vs 0.125, even again
+        let prices = [44.0, 45.0, 44.0, 46.0, 47.0, 46.0];
+        assert_points(&rsi(&prices, 2), &[(2.0, 50.0), (3.0, 100.0 - 100.0 / 6.0), (4.0, 90.0), (5.0, 50.0)]);
+    }
+
+    #[test]
+    fn rsi_edge_cases() {
+        assert!(rsi(&[], 14).is_empty());
+        // A period of changes needs one more price than that
+        assert!(rsi(&[1.0, 2.0, 3.0], 3).is_empty());
+        assert!(rsi(&[1.0, 2.0, 3.0], 0).is_empty());
+        assert_points(&rsi(&[1.0, 2.0, 3.0, 4.0], 2), &[(2.0, 100.0), (3.0, 100.0)]);
+        assert_points(&rsi(&[4.0, 3.0, 2.0], 2), &[(2.0, 0.0)]);
+        assert_points(&rsi(&[7.0; 4], 2), &[(2.0, 50.0), (3.0, 50.0)]);
+    }
+
+    #[test]
+    fn macd_lines_up_on_the_slow_ema() {
+        // On a straight line EMA(2) trails by 0.5 and EMA(3) by 1, so MACD is 0.5 throughout
+        let prices = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+        let m = macd(&prices, 2, 3, 2);
+        assert_points(&m.macd, &[(2.0, 0.5), (3.0, 0.5), (4.0, 0.5), (5.0, 0.5)]);
+        assert_points(&m.signal, &[(3.0, 0.5), (4.0, 0.5), (5.0, 0.5)]);
+        assert_points(&m.histogram, &[(3.0, 0.0), (4.0, 0.0), (5.0, 0.0)]);
+    }
+
+    #[test]
+    fn macd_edge_cases() {
+        let empty = macd(&[], 12, 26, 9);
+        assert!(empty.macd.is_empty() && empty.signal.is_empty() && empty.histogram.is_empty());
+        let short = macd(&[1.0; 20], 12, 26, 9);
+        assert!(short.macd.is_empty() && short.signal.is_empty());
+
+        // Too short for the signal: a MACD line but nothing else
+        let m = macd(&[1.0, 2.0, 3.0], 2, 3, 2);
+        assert_eq!(m.macd.len(), 1);
+        assert!(m.signal.is_empty() && m.histogram.is_empty());
+
+        let flat = macd(&[10.0; 40], 12, 26, 9);
+        assert_eq!((flat.macd.len(), flat.signal.len()), (15, 7));
+        assert!(flat.macd.iter().chain(&flat.histogram).all(|&(_, v)| v == 0.0));
+
+        // Fast and slow swapped starts on the fast EMA instead of panicking
+        assert_points(&macd(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], 3, 2, 2).macd, &[(2.0, -0.5), (3.0, -0.5), (4.0, -0.5), (5.0, -0.5)]);
+    }
+
+    #[test]
+    fn stochastic_places_the_close_in_the_range() {
+        let highs = [10.0, 11.0, 12.0, 11.0, 13.0];
+        let lows = [8.0, 9.0, 10.0, 9.0, 10.0];
+        let closes = [9.0, 10.0, 11.0, 10.0, 12.0];
+        let s = stochastic(&highs, &lows, &closes, 3, 1, 2);
+        assert_points(&s.k, &[(2.0, 75.0), (3.0, 100.0 / 3.0), (4.0, 75.0)]);
+        let d = (75.0 + 100.0 / 3.0) / 2.0;
+        assert_points(&s.d, &[(3.0, d), (4.0, d)]);
+
+        // Smoothing %K moves it onto the last bar of each window
+        let slow = stochastic(&highs, &lows, &closes, 3, 2, 2);
+        assert_points(&slow.k, &[(3.0, d), (4.0, d)]);
+        assert_points(&slow.d, &[(4.0, d)]);
+    }
+
+    #[test]
+    fn stochastic_edge_cases() {
+        assert!(stochastic(&[], &[], &[], 14, 3, 3).k.is_empty());
+        let s = stochastic(&[2.0, 3.0], &[1.0, 2.0], &[1.5, 2.5], 3, 1, 1);
+        assert!(s.k.is_empty() && s.d.is_empty());
+        // A flat range reads as the middle
+        let flat = stochastic(&[5.0; 4], &[5.0; 4], &[5.0; 4], 2, 1, 1);
+        assert_points(&flat.k, &[(1.0, 50.0), (2.0, 50.0), (3.0, 50.0)]);
+        // Mismatched series use the shortest
+        assert_eq!(stochastic(&[2.0; 5], &[1.0; 5], &[1.5; 3], 2, 1, 1).k.len(), 2);
+    }
+
+    #[test]
+    fn atr_counts_gaps_from_the_previous_close() {
+        // Ranges 1, then 1.5 from the gap up off 9.5, then 2
+        let highs = [10.0, 11.0, 12.0];
+        let lows = [9.0, 10.0, 10.0];
+        let closes = [9.5, 10.5, 11.5];
+        assert_points(&atr(&highs, &lows, &closes, 2), &[(1.0, 1.25), (2.0, 1.625)]);
+        assert_points(&atr(&highs, &lows, &closes, 3), &[(2.0, 1.5)]);
+    }
+
+    #[test]
+    fn atr_edge_cases() {
+        assert!(atr(&[], &[], &[], 14).is_empty());
+        assert!(atr(&[2.0, 3.0], &[1.0, 2.0], &[1.5, 2.5], 3).is_empty());
+        assert!(atr(&[2.0, 3.0], &[1.0, 2.0], &[1.5, 2.5], 0).is_empty());
+        assert_points(&atr(&[5.0; 3], &[5.0; 3], &[5.0; 3], 2), &[(1.0, 0.0), (2.0, 0.0)]);
+    }
+
+    #[test]
+    fn crossovers_land_where_the_order_flips() {
+        let fast = [(0.0, 1.0), (1.0, 2.0), (2.0, 3.0), (3.0, 2.0), (4.0, 1.0)];
+        let slow = [(1.0, 2.5), (2.0, 2.5), (3.0, 2.5), (4.0, 2.5)];
+        let crosses: Vec<_> = crossovers(&fast, &slow).iter().map(|c| (c.index, c.kind, c.value)).collect();
+        assert_eq!(crosses, [(2, CrossKind::Golden, 2.5), (3, CrossKind::Death, 2.5)]);
+    }
+
+    #[test]
+    fn crossovers_edge_cases() {
+        assert!(crossovers(&[], &[(0.0, 1.0)]).is_empty());
+        assert!(crossovers(&[(0.0, 1.0)], &[]).is_empty());
+        // Touching without flipping isn't a cross; riding level and then flipping is
+        let slow = [(0.0, 2.0), (1.0, 2.0), (2.0, 2.0), (3.0, 2.0)];
+        assert!(crossovers(&[(0.0, 1.0), (1.0, 2.0), (2.0, 1.0), (3.0, 1.5)], &slow).is_empty());
+        let crosses = crossovers(&[(0.0, 1.0), (1.0, 2.0), (2.0, 2.0), (3.0, 3.0)], &slow);
+        assert_eq!(crosses.iter().map(|c| (c.index, c.kind)).collect::<Vec<_>>(), [(3, CrossKind::Golden)]);
+        // Identical flat averages never cross
+        assert!(crossovers(&slow, &slow).is_empty());
+        // The slow average starting first is only compared from the fast one's start
+        let fast = [(2.0, 3.0), (3.0, 1.0)];
+        let crosses = crossovers(&fast, &slow);
+        assert_eq!(crosses.iter().map(|c| (c.index, c.kind)).collect::<Vec<_>>(), [(3, CrossKind::Death)]);
+    }
+
+    #[test]
+    fn linear_regression_fits_least_squares() {
+        let exact = linear_regression(&[1.0, 3.0, 5.0, 7.0]).unwrap();
+        assert_eq!((exact.slope, exact.intercept, exact.std_error), (2.0, 1.0, 0.0));
+        assert_eq!(exact.at(10.0), 21.0);
+
+        // Residuals -0.3, 0.9, -0.9, 0.3 over two degrees of freedom
+        let noisy = linear_regression(&[1.0, 3.0, 2.0, 4.0]).unwrap();
+        assert!((noisy.slope - 0.8).abs() < 1e-9);
+        assert!((noisy.intercept - 1.3).abs() < 1e-9);
+        assert!((noisy.std_error - 0.9f64.sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn linear_regression_edge_cases() {
+        assert!(linear_regression(&[]).is_none());
+        assert!(linear_regression(&[1.0, 2.0]).is_none());
+        let flat = linear_regression(&[4.0; 5]).unwrap();
+        assert_eq!((flat.slope, flat.intercept, flat.std_error), (0.0, 4.0, 0.0));
+    }
+
+    #[test]
+    fn obv_adds_up_and_down_volume() {
+        let closes = [10.0, 11.0, 11.0, 10.0, 12.0];
+        let volumes = [100.0, 200.0, 300.0, 400.0, 500.0];
+        assert_points(&obv(&closes, &volumes), &[(0.0, 0.0), (1.0, 200.0), (2.0, 200.0), (3.0, -200.0), (4.0, 300.0)]);
+    }
+
+    #[test]
+    fn obv_edge_cases() {
+        assert!(obv(&[], &[]).is_empty());
+        assert!(obv(&[1.0, 2.0], &[]).is_empty());
+        assert_points(&obv(&[3.0; 3], &[50.0; 3]), &[(0.0, 0.0), (1.0, 0.0), (2.0, 0.0)]);
+        assert_points(&obv(&[1.0, 2.0, 3.0], &[10.0, 20.0]), &[(0.0, 0.0), (1.0, 20.0)]);
+    }
+
+    #[test]
+    fn candle_patterns_match_each_shape() {
+        // Doji, hammer, a down bar, bullish engulfing, an up bar, bearish engulfing, no range
+        let opens = [10.0, 10.0, 10.5, 9.9, 10.0, 10.5, 10.0];
+        let highs = [11.0, 10.55, 10.6, 10.8, 10.5, 10.6, 10.0];
+        let lows = [9.0, 8.5, 9.9, 9.8, 9.9, 9.7, 10.0];
+        let closes = [10.05, 10.5, 10.0, 10.7, 10.4, 9.8, 10.0];
+        assert_eq!(
+            candle_patterns(&opens, &highs, &lows, &closes),
+            [
+                (0, CandlePattern::Doji),
+                (1, CandlePattern::Hammer),
+                (3, CandlePattern::BullishEngulfing),
+                (5, CandlePattern::BearishEngulfing),
+            ],
+        );
+    }
+
+    #[test]
+    fn candle_patterns_edge_cases() {
+        assert!(candle_patterns(&[], &[], &[], &[]).is_empty());
+        // Flat bars have no range to judge
+        assert!(candle_patterns(&[5.0; 3], &[5.0; 3], &[5.0; 3], &[5.0; 3]).is_empty());
+        // An up bar swallowing an up bar isn't engulfing, but its small body is still a doji
+        let found = candle_patterns(&[10.0, 9.9], &[10.3, 12.0], &[9.9, 8.0], &[10.2, 10.25]);
+        assert_eq!(found, [(1, CandlePattern::Doji)]);
+    }
+
+    #[test]
+    fn realized_volatility_over_fixed_returns() {
+        // Returns +10% then -10%, log 0.0953 and -0.1054
+        let expected = ((1.1f64.ln() - 0.9f64.ln()).powi(2) / 2.0).sqrt() * 100.0;
+        assert_points(&realized_volatility(&[100.0, 110.0, 99.0], 2, 1.0), &[(2.0, expected)]);
+        assert_points(&realized_volatility(&[100.0, 110.0, 99.0], 2, 4.0), &[(2.0, expected * 2.0)]);
+    }
+
+    #[test]
+    fn realized_volatility_edge_cases() {
+        assert!(realized_volatility(&[], 20, 252.0).is_empty());
+        assert!(realized_volatility(&[1.0, 2.0, 3.0], 3, 252.0).is_empty());
+        assert!(realized_volatility(&[1.0, 2.0, 3.0], 1, 252.0).is_empty());
+        assert_points(&realized_volatility(&[8.0; 4], 2, 252.0), &[(2.0, 0.0), (3.0, 0.0)]);
+        // Windows touching the zero are skipped, not NaN
+        let points = realized_volatility(&[100.0, 0.0, 100.0, 110.0, 121.0], 2, 1.0);
+        assert_eq!(points.len(), 1);
+        assert_eq!(points[0].0, 4.0);
+        assert!(points[0].1.abs() < 1e-6);
+    }
+}
@@ -1,5 +1,5 @@
 use crossterm::{
-    event::{self, Event, KeyCode, KeyEventKind},
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -10,25 +10,39 @@ use std::sync::Arc;
 use tokio::sync::Mutex;
 
 mod alerts;
+mod analytics;
+mod config;
+mod indicators;
+mod provider;
+mod session;
 mod stock;
 mod ui;
 mod watchlist;
 mod websocket;
 
-use ui::{App, AppState, Candlestick, LandingPanel, MarketPanel, WebSocketStatus};
+use provider::DataProvider;
+use ui::{App, AppState, AppearanceOption, Indicator, IndicatorPane, CandleInterval, Candlestick, LandingPanel, MarketPanel, WebSocketStatus};
 use std::collections::HashMap;
 use crate::stock::{QuoteSnapshot, log_debug};
 use websocket::LivePrice;
 
 enum AppUpdate {
-    StockData { symbol: String, result: Result<stock::StockData, String> },
+    StockData { symbol: String, result: Result<Box<stock::StockData>, String> },
     MarketData {
         gainers: Vec<stock::MarketMover>,
         losers: Vec<stock::MarketMover>,
         active: Vec<stock::MarketMover>,
     },
     MarketError(String),
-    HistoricalCandles(Vec<Candlestick>),
+    HistoricalCandles { interval: CandleInterval, candles: Vec<Candlestick> },
+    CandlesError(String),
+    Pivots { symbol: String, result: Result<Option<indicators::PivotLevels>, String> },
+    CompareData { symbol: String, timeframe: stock::TimeFrame, result: Result<Box<stock::StockData>, String> },
+    Earnings { symbol: String, earnings: Option<stock::EarningsInfo> },
+    Profile { symbol: String, result: Result<Option<stock::CompanyProfile>, String> },
+    BidAsk { symbol: String, result: Result<Option<stock::BidAsk>, String> },
+    SearchResults { query: String, results: Vec<stock::SymbolMatch> },
+    Sparklines(Vec<(String, Result<ui::Sparkline, String>)>),
 }
 
 
@@ -41,6 +55,24 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     log_debug("=== charty started ===");
 
     let mut app = App::new();
+    // Live buffer caps from the command line win over config.json
+    if let Some(n) = count_arg("--trades") { app.trade_buffer = n; }
+    if let Some(n) = count_arg("--ticks") { app.tick_buffer = n; }
+    if let Some(n) = count_arg("--candles") { app.candle_buffer = n; }
+    if let Some(path) = path_arg("--csv") {
+        app.load_csv(path);
+    } else if let Some(path) = path_arg("--replay") {
+        // Recordings are JSON lines; anything else is an exported snapshot
+        if path.extension().is_some_and(|ext| ext == "jsonl") {
+            let speed = flag_value("--speed")
+                .and_then(|v| v.parse::<f64>().ok())
+                .filter(|&s| s > 0.0)
+                .unwrap_or(1.0);
+            app.replay_recording(&path, speed);
+        } else {
+            app.replay_live_session(&path);
+        }
+    }
 
     let (tx, mut rx) = mpsc::unbounded_channel::<LivePrice>();
     let (status_tx, mut status_rx) = mpsc::unbounded_channel::<WebSocketStatus>();
@@ -96,6 +128,30 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// Path given with `<flag> <file>` (or `<flag>=<file>`), if any.
+fn path_arg(flag: &str) -> Option<std::path::PathBuf> {
+    flag_value(flag).map(Into::into)
+}
+
+/// Positive count given with `<flag> <n>` (or `<flag>=<n>`), if any.
+fn count_arg(flag: &str) -> Option<usize> {
+    flag_value(flag).and_then(|v| v.parse().ok()).filter(|&n| n > 0)
+}
+
+fn flag_value(flag: &str) -> Option<String> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == flag {
+            return args.next();
+        }
+        if let Some(value) = arg.strip_prefix(flag).and_then(|rest| rest.strip_prefix('=')) {
+            return Some(value.to_string());
+        }
+    }
+    None
+}
+
+#[allow(clippy::too_many_arguments)]
 async fn run_app(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     app: &mut App,
@@ -110,8 +166,17 @@ async fn run_app(
     let mut ws_task_handle: Option<tokio::task::JoinHandle<()>> = None;
     let mut last_alert_check = std::time::Instant::now();
     const ALERT_CHECK_SECS: u64 = 30;
+    const SEARCH_DEBOUNCE_MS: u64 = 300;
     let mut needs_redraw = true;
 
+    // A recording given with --replay plays through the same channel as the live feed
+    if let Some((records, speed)) = app.take_replay_request() {
+        let (tx, status_tx, should_stop) = (tx.clone(), status_tx.clone(), app.ws_should_stop.clone());
+        ws_task_handle = Some(tokio::spawn(async move {
+            websocket::start_replay(records, speed, tx, status_tx, should_stop).await;
+        }));
+    }
+
     loop {
         if needs_redraw {
             terminal.draw(|f| ui::ui(f, app))?;
@@ -121,34 +186,62 @@ async fn run_app(
         // Apply results from background data fetches
         while let Ok(update) = update_rx.try_recv() {
             match update {
-                AppUpdate::StockData { symbol, result } => app.apply_stock_data(&symbol, result),
+                AppUpdate::StockData { symbol, result } => {
+                    app.apply_stock_data(&symbol, result.map(|d| *d));
+                    if let Some(sym) = app.take_earnings_request() {
+                        spawn_earnings_fetch(sym, update_tx.clone());
+                    }
+                    if let Some(sym) = app.take_compare_request() {
+                        spawn_compare_fetch(app.provider.clone(), sym, app.timeframe, app.interval_override, update_tx.clone());
+                    }
+                }
+                AppUpdate::CompareData { symbol, timeframe, result } => app.apply_compare_data(&symbol, timeframe, result.map(|d| *d)),
+                AppUpdate::Earnings { symbol, earnings } => app.apply_earnings(&symbol, earnings),
+                AppUpdate::Profile { symbol, result } => app.apply_profile(symbol, result),
+                AppUpdate::BidAsk { symbol, result } => app.apply_bid_ask(&symbol, result),
                 AppUpdate::MarketData { gainers, losers, active } => app.apply_market_data(gainers, losers, active),
                 AppUpdate::MarketError(e) => app.apply_market_error(e),
-                AppUpdate::HistoricalCandles(candles) => app.apply_historical_candles(candles),
+                AppUpdate::HistoricalCandles { interval, candles } => app.apply_historical_candles(interval, candles),
+                AppUpdate::CandlesError(e) => app.apply_candles_error(e),
+                AppUpdate::Pivots { symbol, result } => app.apply_pivot_levels(&symbol, result),
+                AppUpdate::SearchResults { query, results } => app.apply_search_results(&query, results),
+                AppUpdate::Sparklines(results) => app.apply_sparklines(results),
             }
             needs_redraw = true;
         }
 
+        // Sparklines load after the landing page has drawn, so startup isn't held up
+        if let Some(symbols) = app.take_sparkline_request() {
+            spawn_sparkline_fetch(symbols, update_tx.clone());
+        }
+
+        // Keep the live header's spread current
+        if let Some(symbol) = app.take_bid_ask_request() {
+            spawn_bid_ask_fetch(symbol, update_tx.clone());
+        }
+
+        // Fire a symbol search once the user pauses typing
+        if let Some(query) = app.take_due_search(std::time::Duration::from_millis(SEARCH_DEBOUNCE_MS)) {
+            spawn_symbol_search(query, update_tx.clone());
+        }
+
         // Check for WebSocket status updates
         while let Ok(status) = status_rx.try_recv() {
-            if let WebSocketStatus::Error { ref message, .. } = status {
-                app.add_error_to_log(message.clone());
+            match status {
+                WebSocketStatus::Warning(message) => app.add_error_to_log(message),
+                WebSocketStatus::Error { ref message, .. } => {
+                    app.add_error_to_log(message.clone());
+                    app.ws_status = status;
+                }
+                _ => app.ws_status = status,
             }
-            app.ws_status = status;
             needs_redraw = true;
         }
 
         // Check for background quote updates; run alert checks on arrival
         if let Ok(quotes) = quotes_rx.try_recv() {
-            let triggered = app.check_alerts(&quotes);
-            for (symbol, target) in triggered {
-                let msg = format!("{} crossed ${:.2}", symbol, target);
-                let _ = std::process::Command::new("notify-send")
-                    .arg("Charty Price Alert")
-                    .arg(&msg)
-                    .spawn();
-            }
-            app.landing_quotes.extend(quotes.into_iter());
+            app.check_alerts(&quotes);
+            app.landing_quotes.extend(quotes);
             // Sync market_state into stock_data from the fresh quote
             let updated_state = app.stock_data.as_ref()
                 .and_then(|d| app.landing_quotes.get(&d.symbol))
@@ -165,7 +258,7 @@ async fn run_app(
 
         // Periodically fetch prices for any pending alerts
         let pending_alert_syms: Vec<String> = app.alerts.iter()
-            .filter(|a| !a.triggered)
+            .filter(|a| !a.triggered || a.repeat)
             .map(|a| a.symbol.clone())
             .collect();
         if !pending_alert_syms.is_empty()
@@ -189,17 +282,27 @@ async fn run_app(
             });
         }
 
-        // Check for live price updates with throttling
-        let mut latest_price = None;
-        while let Ok(live_price) = rx.try_recv() {
-            latest_price = Some(live_price);
+        // Drain every pending trade batch into the candles and volume; the
+        // throttle only limits how often new prices force a redraw.
+        let traded = app.drain_live_prices(rx);
+        if traded && app.update_throttle.should_update() {
+            needs_redraw = true;
         }
 
-        if let Some(live_price) = latest_price {
-            if app.live_updates_enabled && app.update_throttle.should_update() {
-                app.update_live_price(live_price.price, live_price.volume);
-                needs_redraw = true;
-            }
+        // Alerts fired by quotes or live trades: bell and desktop notification
+        for message in app.take_fired_alerts() {
+            use std::io::Write;
+            let mut stdout = io::stdout();
+            let _ = stdout.write_all(b"\x07");
+            let _ = stdout.flush();
+            let _ = std::process::Command::new("notify-send")
+                .arg("Charty Price Alert")
+                .arg(&message)
+                .spawn();
+            needs_redraw = true;
+        }
+        if app.live_updates_enabled && app.finalize_idle_candle(app.live_clock()) {
+            needs_redraw = true;
         }
 
         // Poll for a key event on a dedicated thread so the tokio runtime
@@ -214,7 +317,7 @@ async fn run_app(
 
         match poll_result {
             Ok(Ok(Some(Event::Key(key)))) if key.kind == KeyEventKind::Press => {
-                let quit = handle_input(app, key.code, &mut ws_task_handle, &tx, &status_tx, &update_tx, &quotes_tx).await;
+                let quit = handle_input(app, key, &mut ws_task_handle, &tx, &status_tx, &update_tx, &quotes_tx).await;
                 needs_redraw = true;
                 if quit {
                     stop_websocket(&mut ws_task_handle, &app.ws_should_stop).await;
@@ -222,7 +325,7 @@ async fn run_app(
                 }
             }
             Ok(Ok(None)) => {
-                if app.live_updates_enabled {
+                if app.expire_flash() || app.live_updates_enabled {
                     needs_redraw = true;
                 }
             }
@@ -234,26 +337,157 @@ async fn run_app(
     }
 }
 
+/// How long `stop_websocket` waits for the live task before aborting it
+const STOP_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Ask the live task to stop and wait for it, so a websocket unsubscribes and
+/// closes before anything new connects. The task notices within a second
+/// wherever it is; one stuck mid-connect is aborted after `STOP_TIMEOUT`,
+/// which is fine since it has nothing subscribed yet.
 async fn stop_websocket(
     ws_task_handle: &mut Option<tokio::task::JoinHandle<()>>,
     should_stop: &Arc<Mutex<bool>>,
 ) {
     *should_stop.lock().await = true;
-    if let Some(handle) = ws_task_handle.take() {
+    if let Some(mut handle) = ws_task_handle.take()
+        && tokio::time::timeout(STOP_TIMEOUT, &mut handle).await.is_err()
+    {
+        log_debug("[live] feed task didn't stop in time, aborting it");
         handle.abort();
     }
 }
 
-fn spawn_stock_fetch(symbol: String, timeframe: stock::TimeFrame, update_tx: mpsc::UnboundedSender<AppUpdate>) {
+/// Chart `symbol`: fetch its history and quote, and move the live feed over.
+/// A Finnhub connection that's still up swaps its subscription in place, so
+/// it stays connected; with polling, a dead socket or a symbol Finnhub can't
+/// stream, the feed is stopped instead.
+async fn switch_symbol(
+    app: &mut App,
+    symbol: String,
+    ws_task_handle: &mut Option<tokio::task::JoinHandle<()>>,
+    update_tx: &mpsc::UnboundedSender<AppUpdate>,
+    quotes_tx: &mpsc::UnboundedSender<HashMap<String, QuoteSnapshot>>,
+) {
+    let previous = std::mem::replace(&mut app.symbol, symbol);
+    let running = ws_task_handle.as_ref().is_some_and(|h| !h.is_finished());
+    // A connection only carries symbols of its own provider
+    let moved = match app.ws_manager.as_ref()
+        .filter(|m| running && m.provider() == websocket::Provider::for_symbol(&app.symbol))
+    {
+        Some(_) if previous == app.symbol => true,
+        Some(manager) => {
+            let _ = manager.unsubscribe(&previous);
+            match manager.subscribe(&app.symbol) {
+                Ok(()) => true,
+                Err(reason) => {
+                    app.add_error_to_log(format!("Live feed stopped: {}", reason));
+                    false
+                }
+            }
+        }
+        None => false,
+    };
+    if moved {
+        // The old symbol's ticks and candles don't belong to the new chart
+        app.clear_live_data();
+    } else {
+        stop_websocket(ws_task_handle, &app.ws_should_stop).await;
+        app.ws_manager = None;
+    }
+
+    app.fetch_data();
+    spawn_stock_fetch(app.provider.clone(), app.symbol.clone(), app.timeframe, app.interval_override, update_tx.clone());
+    spawn_quotes_fetch(vec![app.symbol.clone()], quotes_tx.clone());
+}
+
+/// Start the live price feed for `app.symbol`: Binance's websocket for the
+/// crypto pairs it lists, the Finnhub websocket when a key is configured,
+/// otherwise delayed Yahoo polling.
+async fn start_live_feed(
+    app: &mut App,
+    ws_task_handle: &mut Option<tokio::task::JoinHandle<()>>,
+    tx: &mpsc::UnboundedSender<LivePrice>,
+    status_tx: &mpsc::UnboundedSender<WebSocketStatus>,
+) {
+    const POLL_INTERVAL_SECS: u64 = 5;
+
+    stop_websocket(ws_task_handle, &app.ws_should_stop).await;
+    *app.ws_should_stop.lock().await = false;
+    app.ws_manager = None;
+    app.end_replay();
+    let symbol_clone = app.symbol.clone();
+    let tx_clone = tx.clone();
+    let status_tx_clone = status_tx.clone();
+    let should_stop = app.ws_should_stop.clone();
+
+    let provider = websocket::Provider::for_symbol(&app.symbol);
+    if app.live_polling_fallback && provider == websocket::Provider::Finnhub {
+        // Never poll faster than the UI is willing to apply updates
+        let interval = std::time::Duration::from_secs(POLL_INTERVAL_SECS)
+            .max(app.update_throttle.min_interval());
+        *ws_task_handle = Some(tokio::spawn(async move {
+            websocket::start_polling(symbol_clone, interval, tx_clone, status_tx_clone, should_stop).await;
+        }));
+    } else if provider == websocket::Provider::Binance {
+        *ws_task_handle = Some(spawn_websocket(app, websocket::BinanceProvider::new(), tx_clone, status_tx_clone));
+    } else {
+        let finnhub = websocket::FinnhubProvider::new(websocket::finnhub_api_key());
+        *ws_task_handle = Some(spawn_websocket(app, finnhub, tx_clone, status_tx_clone));
+    }
+}
+
+/// Run a websocket manager over `provider` for `app.symbol`, keeping a handle
+/// on it in the App for later subscription changes.
+fn spawn_websocket<P: websocket::LiveProvider>(
+    app: &mut App,
+    provider: P,
+    tx: mpsc::UnboundedSender<LivePrice>,
+    status_tx: mpsc::UnboundedSender<WebSocketStatus>,
+) -> tokio::task::JoinHandle<()> {
+    let base_price = app.get_base_price();
+    let (symbol, should_stop) = (app.symbol.clone(), app.ws_should_stop.clone());
+    let market_hours = app.market_hours.clone();
+    let manager = Arc::new(websocket::WebSocketManager::new(provider));
+    app.ws_manager = Some(manager.clone());
+    tokio::spawn(async move {
+        websocket::start_websocket(manager, vec![symbol], base_price, market_hours, tx, status_tx, should_stop).await;
+    })
+}
+
+fn spawn_stock_fetch(
+    provider: Arc<dyn DataProvider>,
+    symbol: String,
+    timeframe: stock::TimeFrame,
+    interval: Option<&'static str>,
+    update_tx: mpsc::UnboundedSender<AppUpdate>,
+) {
     tokio::spawn(async move {
         let sym = symbol.clone();
         let result = tokio::task::spawn_blocking(move || {
-            stock::fetch_stock_data(&sym, timeframe).map_err(|e| e.to_string())
+            provider.fetch_history(&sym, timeframe, interval).map(Box::new).map_err(|e| e.to_string())
         }).await.unwrap_or_else(|e| Err(e.to_string()));
         let _ = update_tx.send(AppUpdate::StockData { symbol, result });
     });
 }
 
+/// Fetch the comparison symbol with the main chart's timeframe and interval, so
+/// the two share bar timestamps.
+fn spawn_compare_fetch(
+    provider: Arc<dyn DataProvider>,
+    symbol: String,
+    timeframe: stock::TimeFrame,
+    interval: Option<&'static str>,
+    update_tx: mpsc::UnboundedSender<AppUpdate>,
+) {
+    tokio::spawn(async move {
+        let sym = symbol.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            provider.fetch_history(&sym, timeframe, interval).map(Box::new).map_err(|e| e.to_string())
+        }).await.unwrap_or_else(|e| Err(e.to_string()));
+        let _ = update_tx.send(AppUpdate::CompareData { symbol, timeframe, result });
+    });
+}
+
 fn spawn_market_fetch(update_tx: mpsc::UnboundedSender<AppUpdate>) {
     tokio::spawn(async move {
         let result = tokio::task::spawn_blocking(|| {
@@ -282,50 +516,147 @@ fn spawn_quotes_fetch(symbols: Vec<String>, quotes_tx: mpsc::UnboundedSender<Has
     });
 }
 
-fn spawn_candles_fetch(symbol: String, interval: String, update_tx: mpsc::UnboundedSender<AppUpdate>) {
+/// Today's intraday series for each landing-page symbol, fetched in parallel.
+fn spawn_sparkline_fetch(symbols: Vec<String>, update_tx: mpsc::UnboundedSender<AppUpdate>) {
+    tokio::spawn(async move {
+        let results = tokio::task::spawn_blocking(move || {
+            let fetched = stock::fetch_many(&symbols, stock::TimeFrame::OneDay);
+            symbols.into_iter().zip(fetched).map(|(symbol, result)| {
+                let sparkline = result.map(|data| ui::Sparkline { prices: data.prices, change_percent: data.change_percent });
+                (symbol, sparkline)
+            }).collect()
+        }).await.unwrap_or_default();
+        let _ = update_tx.send(AppUpdate::Sparklines(results));
+    });
+}
+
+fn spawn_candles_fetch(
+    provider: Arc<dyn DataProvider>,
+    symbol: String,
+    interval: CandleInterval,
+    count: usize,
+    update_tx: mpsc::UnboundedSender<AppUpdate>,
+) {
+    tokio::spawn(async move {
+        let result = tokio::task::spawn_blocking(move || {
+            let to = chrono::Utc::now();
+            let from = stock::market_hours_lookback(to, interval.to_secs() as i64, count);
+            provider.fetch_candles(&symbol, interval.to_string(), from, to).map_err(|e| e.to_string())
+        }).await.unwrap_or_else(|e| Err(e.to_string()));
+        match result {
+            Ok(candles) => { let _ = update_tx.send(AppUpdate::HistoricalCandles { interval, candles }); }
+            Err(e) => { let _ = update_tx.send(AppUpdate::CandlesError(e)); }
+        }
+    });
+}
+
+/// Daily candles for the last couple of weeks, reduced to pivot levels from
+/// the most recent session before today's (in exchange time).
+fn spawn_pivot_fetch(
+    provider: Arc<dyn DataProvider>,
+    symbol: String,
+    gmt_offset: i64,
+    update_tx: mpsc::UnboundedSender<AppUpdate>,
+) {
+    const LOOKBACK_DAYS: i64 = 14;
+
+    tokio::spawn(async move {
+        let sym = symbol.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            let to = chrono::Utc::now();
+            let from = to - chrono::Duration::days(LOOKBACK_DAYS);
+            let daily = provider.fetch_candles(&sym, "1d", from, to).map_err(|e| e.to_string())?;
+            Ok(stock::previous_session(&daily, to, gmt_offset)
+                .map(|c| indicators::pivot_points(c.high, c.low, c.close)))
+        }).await.unwrap_or_else(|e| Err(e.to_string()));
+        let _ = update_tx.send(AppUpdate::Pivots { symbol, result });
+    });
+}
+
+fn spawn_earnings_fetch(symbol: String, update_tx: mpsc::UnboundedSender<AppUpdate>) {
+    let Some(api_key) = websocket::finnhub_api_key() else { return; };
+    tokio::spawn(async move {
+        let sym = symbol.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            stock::fetch_earnings(&sym, &api_key).map_err(|e| e.to_string())
+        }).await.unwrap_or_else(|e| Err(e.to_string()));
+        // Earnings are supplementary; on failure the header line is simply hidden
+        match result {
+            Ok(earnings) => { let _ = update_tx.send(AppUpdate::Earnings { symbol, earnings }); }
+            Err(e) => log_debug(&format!("[earnings] {} failed: {}", symbol, e)),
+        }
+    });
+}
+
+fn spawn_profile_fetch(symbol: String, update_tx: mpsc::UnboundedSender<AppUpdate>) {
+    let Some(api_key) = websocket::finnhub_api_key() else { return; };
+    tokio::spawn(async move {
+        let sym = symbol.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            stock::fetch_company_profile(&sym, &api_key).map_err(|e| e.to_string())
+        }).await.unwrap_or_else(|e| Err(e.to_string()));
+        let _ = update_tx.send(AppUpdate::Profile { symbol, result });
+    });
+}
+
+fn spawn_bid_ask_fetch(symbol: String, update_tx: mpsc::UnboundedSender<AppUpdate>) {
+    let Some(api_key) = websocket::finnhub_api_key() else { return; };
+    tokio::spawn(async move {
+        let sym = symbol.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            // A symbol Finnhub doesn't know has no quote to show
+            let Ok(feed) = websocket::yahoo_to_finnhub_symbol(&sym) else { return Ok(None) };
+            stock::fetch_bid_ask(&feed, &api_key).map_err(|e| e.to_string())
+        }).await.unwrap_or_else(|e| Err(e.to_string()));
+        if let Err(ref e) = result {
+            log_debug(&format!("[bid/ask] {} failed: {}", symbol, e));
+        }
+        let _ = update_tx.send(AppUpdate::BidAsk { symbol, result });
+    });
+}
+
+fn spawn_symbol_search(query: String, update_tx: mpsc::UnboundedSender<AppUpdate>) {
     tokio::spawn(async move {
+        let q = query.clone();
         let result = tokio::task::spawn_blocking(move || {
-            stock::fetch_historical_candles(&symbol, &interval).map_err(|e| e.to_string())
+            stock::search_symbols(&q).map_err(|e| e.to_string())
         }).await.unwrap_or_else(|e| Err(e.to_string()));
-        if let Ok(candles) = result {
-            let _ = update_tx.send(AppUpdate::HistoricalCandles(candles));
+        // Search is best-effort: on failure the typed text still works as a ticker
+        match result {
+            Ok(results) => { let _ = update_tx.send(AppUpdate::SearchResults { query, results }); }
+            Err(e) => log_debug(&format!("[search] {} failed: {}", query, e)),
         }
     });
 }
 
 async fn handle_input(
     app: &mut App,
-    key: KeyCode,
+    key_event: KeyEvent,
     ws_task_handle: &mut Option<tokio::task::JoinHandle<()>>,
     tx: &mpsc::UnboundedSender<LivePrice>,
     status_tx: &mpsc::UnboundedSender<WebSocketStatus>,
     update_tx: &mpsc::UnboundedSender<AppUpdate>,
     quotes_tx: &mpsc::UnboundedSender<HashMap<String, QuoteSnapshot>>,
 ) -> bool {
-    // Normalize char keys to lowercase so Caps Lock doesn't break shortcuts.
-    let key = match key {
-        KeyCode::Char(c) => KeyCode::Char(c.to_ascii_lowercase()),
+    // Normalize char keys to lowercase so Caps Lock doesn't break shortcuts,
+    // except for the few deliberately shifted bindings.
+    const SHIFTED_KEYS: &[char] = &['A', 'E', 'R', 'M', 'O', 'T'];
+    let shift = key_event.modifiers.contains(KeyModifiers::SHIFT);
+    let key = match key_event.code {
+        KeyCode::Char(c) if !SHIFTED_KEYS.contains(&c) => KeyCode::Char(c.to_ascii_lowercase()),
         other => other,
     };
 
     // Alert input popup is modal — handle it before any state-specific logic
     if app.show_alert_input {
         match key {
-            KeyCode::Enter => {
-                if let Ok(target) = app.alert_input_buffer.parse::<f64>() {
-                    let sym = app.alert_target_symbol.clone();
-                    let current = app.current_price_for(&sym).unwrap_or(target);
-                    app.set_price_alert(sym, target, current);
-                }
-                app.alert_input_buffer.clear();
-                app.show_alert_input = false;
-            }
+            KeyCode::Enter => app.confirm_alert_input(),
             KeyCode::Esc => {
                 app.alert_input_buffer.clear();
                 app.show_alert_input = false;
             }
             KeyCode::Backspace => { app.alert_input_buffer.pop(); }
-            KeyCode::Char(c) if c.is_ascii_digit() || c == '.' => {
+            KeyCode::Char(c) if c.is_ascii_digit() || matches!(c, '.' | '<' | '>' | '=' | '≥' | '≤' | ' ') => {
                 app.alert_input_buffer.push(c);
             }
             _ => {}
@@ -333,6 +664,43 @@ async fn handle_input(
         return false;
     }
 
+    // So is the alert list, over whichever view opened it
+    if app.show_alert_list {
+        match key {
+            KeyCode::Esc | KeyCode::Char('A') => app.show_alert_list = false,
+            KeyCode::Up | KeyCode::Char('k') => app.select_previous_alert(),
+            KeyCode::Down | KeyCode::Char('j') => app.select_next_alert(),
+            KeyCode::Char('d') | KeyCode::Delete => app.delete_selected_alert(),
+            KeyCode::Char('r') => app.toggle_selected_alert_repeat(),
+            _ => {}
+        }
+        return false;
+    }
+
+    if app.show_compare_input {
+        match key {
+            KeyCode::Enter => {
+                let symbol = app.compare_input_buffer.trim().to_uppercase();
+                if !symbol.is_empty() && symbol != app.symbol {
+                    app.set_compare(symbol.clone());
+                    spawn_compare_fetch(app.provider.clone(), symbol, app.timeframe, app.interval_override, update_tx.clone());
+                }
+                app.compare_input_buffer.clear();
+                app.show_compare_input = false;
+            }
+            KeyCode::Esc => {
+                app.compare_input_buffer.clear();
+                app.show_compare_input = false;
+            }
+            KeyCode::Backspace => { app.compare_input_buffer.pop(); }
+            KeyCode::Char(c) if c.is_ascii_alphanumeric() || ".-^=".contains(c) => {
+                app.compare_input_buffer.push(c.to_ascii_uppercase());
+            }
+            _ => {}
+        }
+        return false;
+    }
+
     match app.state {
         AppState::Landing => {
             // Handle help popup first
@@ -350,26 +718,23 @@ async fn handle_input(
             if app.input_mode {
                 match key {
                     KeyCode::Enter => {
-                        if !app.input_buffer.is_empty() {
-                            app.symbol = app.input_buffer.to_uppercase();
-                            app.input_buffer.clear();
-                            app.input_mode = false;
-
-                            stop_websocket(ws_task_handle, &app.ws_should_stop).await;
-                            app.fetch_data();
-                            spawn_stock_fetch(app.symbol.clone(), app.timeframe, update_tx.clone());
-                            spawn_quotes_fetch(vec![app.symbol.clone()], quotes_tx.clone());
+                        if let Some(symbol) = app.search_selection() {
+                            app.close_search();
+                            switch_symbol(app, symbol, ws_task_handle, update_tx, quotes_tx).await;
                         }
                     }
                     KeyCode::Esc => {
-                        app.input_buffer.clear();
-                        app.input_mode = false;
+                        app.close_search();
                     }
+                    KeyCode::Up => app.previous_search_result(),
+                    KeyCode::Down => app.next_search_result(),
                     KeyCode::Backspace => {
                         app.input_buffer.pop();
+                        app.mark_search_edited();
                     }
                     KeyCode::Char(c) => {
                         app.input_buffer.push(c);
+                        app.mark_search_edited();
                     }
                     _ => {}
                 }
@@ -398,36 +763,28 @@ async fn handle_input(
                         }
                     }
                     KeyCode::Enter => {
-                        stop_websocket(ws_task_handle, &app.ws_should_stop).await;
+                        let previous = app.symbol.clone();
                         match app.landing_panel {
                             LandingPanel::Popular => app.select_popular(),
                             LandingPanel::Watchlist => app.select_watchlist(),
                         }
                         if !app.symbol.is_empty() {
-                            app.fetch_data();
-                            spawn_stock_fetch(app.symbol.clone(), app.timeframe, update_tx.clone());
-                            spawn_quotes_fetch(vec![app.symbol.clone()], quotes_tx.clone());
+                            let symbol = std::mem::replace(&mut app.symbol, previous);
+                            switch_symbol(app, symbol, ws_task_handle, update_tx, quotes_tx).await;
                         }
                     }
-                    KeyCode::Char('d') => {
-                        if app.landing_panel == LandingPanel::Watchlist {
-                            app.remove_from_watchlist();
-                        }
+                    KeyCode::Char('d') if app.landing_panel == LandingPanel::Watchlist => {
+                        app.remove_from_watchlist();
                     }
                     KeyCode::Char('h') => {
                         app.show_help = !app.show_help;
                     }
                     KeyCode::Char('a') => {
                         if let Some(sym) = app.selected_symbol() {
-                            if app.alert_for_symbol(&sym).is_some() {
-                                app.clear_price_alert(&sym);
-                            } else {
-                                app.alert_target_symbol = sym;
-                                app.alert_input_buffer.clear();
-                                app.show_alert_input = true;
-                            }
+                            app.open_alert_input(sym);
                         }
                     }
+                    KeyCode::Char('A') => app.toggle_alert_list(),
                     KeyCode::Char('m') => {
                         app.state = AppState::Market;
                         app.fetch_market_data();
@@ -438,7 +795,7 @@ async fn handle_input(
                         for s in &app.watchlist {
                             if !symbols.contains(s) { symbols.push(s.clone()); }
                         }
-                        for a in app.alerts.iter().filter(|a| !a.triggered) {
+                        for a in app.alerts.iter().filter(|a| !a.triggered || a.repeat) {
                             if !symbols.contains(&a.symbol) { symbols.push(a.symbol.clone()); }
                         }
                         spawn_quotes_fetch(symbols, quotes_tx.clone());
@@ -468,12 +825,11 @@ async fn handle_input(
                 KeyCode::Up => app.previous_market(),
                 KeyCode::Down => app.next_market(),
                 KeyCode::Enter => {
-                    stop_websocket(ws_task_handle, &app.ws_should_stop).await;
+                    let previous = app.symbol.clone();
                     app.select_market();
                     if !app.symbol.is_empty() {
-                        app.fetch_data();
-                        spawn_stock_fetch(app.symbol.clone(), app.timeframe, update_tx.clone());
-                        spawn_quotes_fetch(vec![app.symbol.clone()], quotes_tx.clone());
+                        let symbol = std::mem::replace(&mut app.symbol, previous);
+                        switch_symbol(app, symbol, ws_task_handle, update_tx, quotes_tx).await;
                     }
                 }
                 _ => {}
@@ -504,47 +860,201 @@ async fn handle_input(
                 return false;
             }
 
+            if app.show_earnings {
+                match key {
+                    KeyCode::Char('q') => return true,
+                    KeyCode::Char('E') | KeyCode::Esc => {
+                        app.show_earnings = false;
+                    }
+                    _ => {}
+                }
+                return false;
+            }
+
+            if app.show_profile {
+                match key {
+                    KeyCode::Char('q') => return true,
+                    KeyCode::Char('p') | KeyCode::Esc => {
+                        app.show_profile = false;
+                    }
+                    _ => {}
+                }
+                return false;
+            }
+
+            if app.show_stats {
+                match key {
+                    KeyCode::Char('q') => return true,
+                    KeyCode::Char('n') | KeyCode::Esc => {
+                        app.show_stats = false;
+                    }
+                    _ => {}
+                }
+                return false;
+            }
+
+            if app.show_candle_detail {
+                match key {
+                    KeyCode::Char('q') => return true,
+                    KeyCode::Enter | KeyCode::Esc => {
+                        app.show_candle_detail = false;
+                    }
+                    _ => {}
+                }
+                return false;
+            }
+
+            if app.show_pattern_list {
+                match key {
+                    KeyCode::Char('q') => return true,
+                    KeyCode::Char('k') | KeyCode::Esc => {
+                        app.show_pattern_list = false;
+                    }
+                    _ => {}
+                }
+                return false;
+            }
+
+            if app.show_params {
+                let count = app.editable_params().len();
+                if app.param_editing {
+                    match key {
+                        KeyCode::Enter => app.commit_param_edit(),
+                        KeyCode::Esc => {
+                            app.param_editing = false;
+                            app.param_error = None;
+                            app.param_input_buffer.clear();
+                        }
+                        KeyCode::Backspace => { app.param_input_buffer.pop(); }
+                        KeyCode::Char(c) if c.is_ascii_digit() || c == ',' => {
+                            app.param_input_buffer.push(c);
+                        }
+                        _ => {}
+                    }
+                    return false;
+                }
+                match key {
+                    KeyCode::Char('q') => return true,
+                    KeyCode::Char('p') | KeyCode::Esc => {
+                        app.show_params = false;
+                    }
+                    KeyCode::Up if count > 0 => {
+                        app.param_cursor = (app.param_cursor + count - 1) % count;
+                    }
+                    KeyCode::Down if count > 0 => {
+                        app.param_cursor = (app.param_cursor + 1) % count;
+                    }
+                    KeyCode::Enter => app.begin_param_edit(),
+                    _ => {}
+                }
+                return false;
+            }
+
+            if app.show_indicators {
+                let count = Indicator::ALL.len();
+                match key {
+                    KeyCode::Char('q') => return true,
+                    KeyCode::Char('i') | KeyCode::Esc => {
+                        app.show_indicators = false;
+                    }
+                    KeyCode::Up => {
+                        app.indicator_cursor = (app.indicator_cursor + count - 1) % count;
+                    }
+                    KeyCode::Down => {
+                        app.indicator_cursor = (app.indicator_cursor + 1) % count;
+                    }
+                    KeyCode::Char(' ') | KeyCode::Enter => {
+                        app.toggle_indicator(Indicator::ALL[app.indicator_cursor]);
+                    }
+                    KeyCode::Char('p') => {
+                        app.show_params = true;
+                        app.param_cursor = 0;
+                        app.param_error = None;
+                    }
+                    _ => {}
+                }
+                return false;
+            }
+
+            if app.show_appearance {
+                let count = AppearanceOption::ALL.len();
+                match key {
+                    KeyCode::Char('q') => return true,
+                    KeyCode::Char('d') | KeyCode::Esc => {
+                        app.show_appearance = false;
+                    }
+                    KeyCode::Up => {
+                        app.appearance_cursor = (app.appearance_cursor + count - 1) % count;
+                    }
+                    KeyCode::Down => {
+                        app.appearance_cursor = (app.appearance_cursor + 1) % count;
+                    }
+                    KeyCode::Char(' ') | KeyCode::Enter => {
+                        app.toggle_appearance(AppearanceOption::ALL[app.appearance_cursor]);
+                    }
+                    _ => {}
+                }
+                return false;
+            }
+
             if app.show_live_mode_select {
                 match key {
                     KeyCode::Char('1') => {
                         // Live Ticker mode
                         app.show_live_mode_select = false;
                         app.clear_live_data();
+                        if let Some(sym) = app.take_pivot_request() {
+                            let gmt_offset = app.market_hours.as_ref().map_or(0, |h| h.gmt_offset);
+                            spawn_pivot_fetch(app.provider.clone(), sym, gmt_offset, update_tx.clone());
+                        }
                         app.live_updates_enabled = true;
                         app.state = AppState::LiveTicker;
 
-                        // Start WebSocket
-                        stop_websocket(ws_task_handle, &app.ws_should_stop).await;
-                        *app.ws_should_stop.lock().await = false;
-                        let symbol_clone = app.symbol.clone();
-                        let base_price = app.get_base_price();
-                        let tx_clone = tx.clone();
-                        let status_tx_clone = status_tx.clone();
-                        let should_stop = app.ws_should_stop.clone();
-                        *ws_task_handle = Some(tokio::spawn(async move {
-                            websocket::start_websocket(symbol_clone, base_price, tx_clone, status_tx_clone, should_stop).await;
-                        }));
+                        if app.should_auto_start_live_mode() {
+                            start_live_feed(app, ws_task_handle, tx, status_tx).await;
+                        } else {
+                            stop_websocket(ws_task_handle, &app.ws_should_stop).await;
+                            app.ws_status = app.idle_live_status();
+                        }
                         return false;
                     }
                     KeyCode::Char('2') => {
                         // Live Candles mode
                         app.show_live_mode_select = false;
                         app.clear_live_data();
-                        spawn_candles_fetch(app.symbol.clone(), app.candle_interval.to_string().to_owned(), update_tx.clone());
+                        app.candles_pending = true;
+                        spawn_candles_fetch(app.provider.clone(), app.symbol.clone(), app.candle_interval, app.candle_buffer, update_tx.clone());
+                        if let Some(sym) = app.take_pivot_request() {
+                            let gmt_offset = app.market_hours.as_ref().map_or(0, |h| h.gmt_offset);
+                            spawn_pivot_fetch(app.provider.clone(), sym, gmt_offset, update_tx.clone());
+                        }
                         app.live_updates_enabled = true;
                         app.state = AppState::LiveCandles;
 
-                        // Start WebSocket
-                        stop_websocket(ws_task_handle, &app.ws_should_stop).await;
-                        *app.ws_should_stop.lock().await = false;
-                        let symbol_clone = app.symbol.clone();
-                        let base_price = app.get_base_price();
-                        let tx_clone = tx.clone();
-                        let status_tx_clone = status_tx.clone();
-                        let should_stop = app.ws_should_stop.clone();
-                        *ws_task_handle = Some(tokio::spawn(async move {
-                            websocket::start_websocket(symbol_clone, base_price, tx_clone, status_tx_clone, should_stop).await;
-                        }));
+                        if app.should_auto_start_live_mode() {
+                            start_live_feed(app, ws_task_handle, tx, status_tx).await;
+                        } else {
+                            stop_websocket(ws_task_handle, &app.ws_should_stop).await;
+                            app.ws_status = app.idle_live_status();
+                        }
+                        return false;
+                    }
+                    KeyCode::Char('3') => {
+                        // Stream onto the historical line, or stop doing so
+                        app.show_live_mode_select = false;
+                        app.clear_live_data();
+                        if app.live_updates_enabled {
+                            app.live_updates_enabled = false;
+                            stop_websocket(ws_task_handle, &app.ws_should_stop).await;
+                            app.ws_status = WebSocketStatus::Idle;
+                        } else if app.should_auto_start_live_mode() {
+                            app.live_updates_enabled = true;
+                            start_live_feed(app, ws_task_handle, tx, status_tx).await;
+                        } else if let Some(reason) = app.live_stream_unavailable() {
+                            app.set_flash(reason);
+                        } else {
+                            app.set_flash("Market closed — no live ticks to add".to_string());
+                        }
                         return false;
                     }
                     KeyCode::Esc => {
@@ -555,11 +1065,77 @@ async fn handle_input(
                 }
             }
 
+            // Crosshair: '/' toggles it, Shift+←/→ step it, Home/End jump to the ends.
+            // Plain ←/→ keep switching the timeframe.
+            match key {
+                KeyCode::Char('/') => {
+                    app.toggle_chart_cursor();
+                    return false;
+                }
+                KeyCode::Left if shift => {
+                    app.move_chart_cursor(-1);
+                    return false;
+                }
+                KeyCode::Right if shift => {
+                    app.move_chart_cursor(1);
+                    return false;
+                }
+                KeyCode::Home | KeyCode::End => {
+                    app.jump_chart_cursor(key == KeyCode::End);
+                    return false;
+                }
+                KeyCode::Enter if app.chart_cursor.is_some() => {
+                    app.show_candle_detail = true;
+                    return false;
+                }
+                KeyCode::Esc if app.chart_cursor.is_some() => {
+                    app.chart_cursor = None;
+                    return false;
+                }
+                KeyCode::Char('f') => {
+                    app.full_screen = !app.full_screen;
+                    return false;
+                }
+                KeyCode::Esc if app.full_screen => {
+                    app.full_screen = false;
+                    return false;
+                }
+                // Zoom works on the loaded bars, so it's fine for CSV data too
+                KeyCode::Char('+' | '=') => {
+                    app.zoom(true);
+                    return false;
+                }
+                KeyCode::Char('-') => {
+                    app.zoom(false);
+                    return false;
+                }
+                // One tenth of the window per press
+                KeyCode::Char('[' | ']') => {
+                    let step = (app.visible_range().len() / 10).max(1) as isize;
+                    app.pan(if key == KeyCode::Char('[') { -step } else { step });
+                    return false;
+                }
+                _ => {}
+            }
+
+            // CSV data has no symbol behind it to fetch, stream or look up
+            if let Some(path) = app.csv_path.clone() {
+                match key {
+                    KeyCode::Char('r') => {
+                        app.load_csv(path);
+                        return false;
+                    }
+                    KeyCode::Char('l' | 'p' | 'E' | 'w' | 'a' | 't' | 'c') | KeyCode::Left | KeyCode::Right => return false,
+                    _ => {}
+                }
+            }
+
             match key {
                 KeyCode::Char('q') => true,
                 KeyCode::Char('b') => {
                     app.state = AppState::Landing;
                     app.stock_data = None;
+                    app.csv_path = None;
                     app.error_message = None;
                     app.live_updates_enabled = false;
                     stop_websocket(ws_task_handle, &app.ws_should_stop).await;
@@ -567,6 +1143,7 @@ async fn handle_input(
                 }
                 KeyCode::Char('s') => {
                     app.state = AppState::Landing;
+                    app.csv_path = None;
                     app.input_mode = true;
                     false
                 }
@@ -579,6 +1156,17 @@ async fn handle_input(
                     app.show_live_mode_select = true;
                     false
                 }
+                KeyCode::Char('p') => {
+                    if app.open_profile() {
+                        spawn_profile_fetch(app.symbol.clone(), update_tx.clone());
+                    }
+                    false
+                }
+                KeyCode::Char('E') => {
+                    let has_earnings = app.stock_data.as_ref().is_some_and(|d| d.earnings.is_some());
+                    app.show_earnings = has_earnings;
+                    false
+                }
                 KeyCode::Char('h') => {
                     app.show_help = !app.show_help;
                     false
@@ -587,20 +1175,12 @@ async fn handle_input(
                     app.add_to_watchlist();
                     false
                 }
-                // Candlestick toggle disabled for now
-                // KeyCode::Char('c') => {
-                //     app.show_candlesticks = !app.show_candlesticks;
-                //     false
-                // }
                 KeyCode::Char('a') => {
-                    let sym = app.symbol.clone();
-                    if app.alert_for_symbol(&sym).is_some() {
-                        app.clear_price_alert(&sym);
-                    } else {
-                        app.alert_target_symbol = sym;
-                        app.alert_input_buffer.clear();
-                        app.show_alert_input = true;
-                    }
+                    app.open_alert_input(app.symbol.clone());
+                    false
+                }
+                KeyCode::Char('A') => {
+                    app.toggle_alert_list();
                     false
                 }
                 KeyCode::Char('v') => {
@@ -608,34 +1188,111 @@ async fn handle_input(
                     false
                 }
                 KeyCode::Char('i') => {
-                    app.show_sma = !app.show_sma;
+                    app.show_indicators = true;
+                    false
+                }
+                KeyCode::Char('g') => {
+                    app.log_scale = !app.log_scale;
+                    false
+                }
+                KeyCode::Char('d') => {
+                    app.show_appearance = true;
+                    false
+                }
+                KeyCode::Char('n') => {
+                    app.show_stats = app.stock_data.is_some();
+                    false
+                }
+                KeyCode::Char('k') => {
+                    app.show_pattern_list = app.stock_data.is_some();
+                    false
+                }
+                KeyCode::Char('c') => {
+                    if app.compare_symbol.is_some() {
+                        app.clear_compare();
+                    } else {
+                        app.show_compare_input = true;
+                    }
+                    false
+                }
+                KeyCode::Char('m') => {
+                    app.show_ema = !app.show_ema;
+                    false
+                }
+                KeyCode::Char('R') => {
+                    app.toggle_pane(IndicatorPane::Rsi);
+                    false
+                }
+                KeyCode::Char('M') => {
+                    app.toggle_pane(IndicatorPane::Macd);
+                    false
+                }
+                KeyCode::Char('O') => {
+                    app.toggle_pane(IndicatorPane::Stochastic);
+                    false
+                }
+                KeyCode::Char('x') => {
+                    app.export_chart_csv();
+                    false
+                }
+                KeyCode::Char('P') => {
+                    app.export_chart_png();
+                    false
+                }
+                KeyCode::Char('y') => {
+                    let (width, height) = crossterm::terminal::size().unwrap_or((120, 40));
+                    app.save_snapshot(width, height);
+                    false
+                }
+                KeyCode::Char('z') => {
+                    app.cycle_time_zone();
+                    false
+                }
+                KeyCode::Char('T') => {
+                    if app.live_updates_enabled {
+                        app.show_trade_feed = !app.show_trade_feed;
+                    } else {
+                        app.set_flash("Stream live ticks first (l, then 3)".to_string());
+                    }
+                    false
+                }
+                KeyCode::Char('$') => {
+                    app.toggle_currency();
+                    false
+                }
+                KeyCode::Char('t') => {
+                    app.cycle_interval();
+                    app.fetch_data();
+                    spawn_stock_fetch(app.provider.clone(), app.symbol.clone(), app.timeframe, app.interval_override, update_tx.clone());
                     false
                 }
                 KeyCode::Char('r') => {
                     app.fetch_data();
-                    spawn_stock_fetch(app.symbol.clone(), app.timeframe, update_tx.clone());
+                    spawn_stock_fetch(app.provider.clone(), app.symbol.clone(), app.timeframe, app.interval_override, update_tx.clone());
                     spawn_quotes_fetch(vec![app.symbol.clone()], quotes_tx.clone());
                     false
                 }
                 KeyCode::Left => {
                     if app.show_candlesticks {
                         app.candle_interval = app.candle_interval.prev();
+                        app.refresh_indicators();
                         false
                     } else {
-                        app.timeframe = app.timeframe.prev();
+                        app.set_timeframe(app.timeframe.prev());
                         app.fetch_data();
-                        spawn_stock_fetch(app.symbol.clone(), app.timeframe, update_tx.clone());
+                        spawn_stock_fetch(app.provider.clone(), app.symbol.clone(), app.timeframe, app.interval_override, update_tx.clone());
                         false
                     }
                 }
                 KeyCode::Right => {
                     if app.show_candlesticks {
                         app.candle_interval = app.candle_interval.next();
+                        app.refresh_indicators();
                         false
                     } else {
-                        app.timeframe = app.timeframe.next();
+                        app.set_timeframe(app.timeframe.next());
                         app.fetch_data();
-                        spawn_stock_fetch(app.symbol.clone(), app.timeframe, update_tx.clone());
+                        spawn_stock_fetch(app.provider.clone(), app.symbol.clone(), app.timeframe, app.interval_override, update_tx.clone());
                         false
                     }
                 }
@@ -666,6 +1323,50 @@ async fn handle_input(
                 return false;
             }
 
+            if app.show_stats {
+                match key {
+                    KeyCode::Char('q') => return true,
+                    KeyCode::Char('n') | KeyCode::Esc => {
+                        app.show_stats = false;
+                    }
+                    _ => {}
+                }
+                return false;
+            }
+
+            if app.show_candle_detail {
+                match key {
+                    KeyCode::Char('q') => return true,
+                    KeyCode::Enter | KeyCode::Esc => {
+                        app.show_candle_detail = false;
+                    }
+                    _ => {}
+                }
+                return false;
+            }
+
+            if app.show_pattern_list {
+                match key {
+                    KeyCode::Char('q') => return true,
+                    KeyCode::Char('k') | KeyCode::Esc => {
+                        app.show_pattern_list = false;
+                    }
+                    _ => {}
+                }
+                return false;
+            }
+
+            if app.show_pivots {
+                match key {
+                    KeyCode::Char('q') => return true,
+                    KeyCode::Char('f') | KeyCode::Esc => {
+                        app.show_pivots = false;
+                    }
+                    _ => {}
+                }
+                return false;
+            }
+
             if app.show_live_mode_select {
                 match key {
                     KeyCode::Char('1') => {
@@ -681,6 +1382,8 @@ async fn handle_input(
                         if !matches!(app.state, AppState::LiveCandles) {
                             app.clear_live_data();
                             app.state = AppState::LiveCandles;
+                            app.candles_pending = true;
+                            spawn_candles_fetch(app.provider.clone(), app.symbol.clone(), app.candle_interval, app.candle_buffer, update_tx.clone());
                         }
                         return false;
                     }
@@ -692,6 +1395,57 @@ async fn handle_input(
                 }
             }
 
+            // Ahead of the scrollback below, so pausing keeps a scrolled-back view
+            if key == KeyCode::Char(' ') {
+                app.toggle_live_pause();
+                return false;
+            }
+
+            // Scrollback: PgUp/PgDn page through the candle buffer, and any
+            // other key snaps back to the live edge before doing its job.
+            // The crosshair keys move through the buffer without snapping back.
+            if matches!(app.state, AppState::LiveCandles) {
+                const SCROLL_STEP: isize = 10;
+                let width = crossterm::terminal::size().map_or(80, |(w, _)| w);
+                match key {
+                    KeyCode::Char('/') => {
+                        app.toggle_live_cursor();
+                        return false;
+                    }
+                    KeyCode::Left | KeyCode::Right if shift => {
+                        app.move_live_cursor(if key == KeyCode::Left { -1 } else { 1 }, width);
+                        return false;
+                    }
+                    KeyCode::Home | KeyCode::End => {
+                        app.jump_live_cursor(key == KeyCode::End, width);
+                        return false;
+                    }
+                    KeyCode::Enter if app.live_cursor.is_some() => {
+                        app.show_candle_detail = true;
+                        return false;
+                    }
+                    KeyCode::Esc if app.live_cursor.is_some() => {
+                        app.live_cursor = None;
+                        return false;
+                    }
+                    KeyCode::PageUp => {
+                        app.scroll_live_candles(SCROLL_STEP);
+                        return false;
+                    }
+                    KeyCode::PageDown => {
+                        app.scroll_live_candles(-SCROLL_STEP);
+                        return false;
+                    }
+                    _ if app.live_scroll > 0 => {
+                        app.live_scroll = 0;
+                        if key == KeyCode::Esc {
+                            return false;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+
             match key {
                 KeyCode::Char('q') => true,
                 KeyCode::Char('b') => {
@@ -715,30 +1469,86 @@ async fn handle_input(
                     app.show_error_log = !app.show_error_log;
                     false
                 }
+                KeyCode::Char('x') => {
+                    app.export_live_session();
+                    false
+                }
+                KeyCode::Char('R') => {
+                    app.toggle_recording();
+                    false
+                }
+                KeyCode::Char('y') => {
+                    let (width, height) = crossterm::terminal::size().unwrap_or((120, 40));
+                    app.save_snapshot(width, height);
+                    false
+                }
+                KeyCode::Char('z') => {
+                    app.cycle_time_zone();
+                    false
+                }
+                KeyCode::Char('m') => {
+                    app.show_ema = !app.show_ema;
+                    false
+                }
+                KeyCode::Char('v') => {
+                    app.show_volume = !app.show_volume;
+                    false
+                }
+                KeyCode::Char('u') => {
+                    app.show_candle_counts = !app.show_candle_counts;
+                    false
+                }
+                KeyCode::Char('t') if matches!(app.state, AppState::LiveTicker) => {
+                    app.cycle_tape_filter();
+                    false
+                }
+                KeyCode::Char('O') => {
+                    app.toggle_pane(IndicatorPane::Stochastic);
+                    false
+                }
+                KeyCode::Char('n') => {
+                    app.show_stats = true;
+                    false
+                }
+                KeyCode::Char('k') => {
+                    app.show_pattern_list = true;
+                    false
+                }
+                KeyCode::Char('f') => {
+                    app.show_pivots = true;
+                    false
+                }
+                KeyCode::Char('r') if matches!(app.ws_status, WebSocketStatus::Idle) => {
+                    // Connect even though the market is closed
+                    start_live_feed(app, ws_task_handle, tx, status_tx).await;
+                    false
+                }
+                KeyCode::Char('r') if app.can_reconnect() => {
+                    // A fresh task starts with a fresh retry policy; the trades
+                    // and candles gathered so far stay put
+                    app.add_error_to_log(format!("Manual reconnect for {}", app.symbol));
+                    start_live_feed(app, ws_task_handle, tx, status_tx).await;
+                    false
+                }
                 KeyCode::Char('a') | KeyCode::Char('p') => {
-                    let sym = app.symbol.clone();
-                    if app.alert_for_symbol(&sym).is_some() {
-                        app.clear_price_alert(&sym);
-                    } else {
-                        app.alert_target_symbol = sym;
-                        app.alert_input_buffer.clear();
-                        app.show_alert_input = true;
-                    }
+                    app.open_alert_input(app.symbol.clone());
+                    false
+                }
+                KeyCode::Char('A') => {
+                    app.toggle_alert_list();
                     false
                 }
                 KeyCode::Left => {
                     if matches!(app.state, AppState::LiveCandles) {
-                        app.candle_interval = app.candle_interval.prev();
-                        app.clear_live_data();
-                        spawn_candles_fetch(app.symbol.clone(), app.candle_interval.to_string().to_owned(), update_tx.clone());
+                        app.set_candle_interval(app.candle_interval.prev());
+                        spawn_candles_fetch(app.provider.clone(), app.symbol.clone(), app.candle_interval, app.candle_buffer, update_tx.clone());
                     }
                     false
                 }
                 KeyCode::Right => {
                     if matches!(app.state, AppState::LiveCandles) {
-                        app.candle_interval = app.candle_interval.next();
-                        app.clear_live_data();
-                        spawn_candles_fetch(app.symbol.clone(), app.candle_interval.to_string().to_owned(), update_tx.clone());
+                        app.set_candle_interval(app.candle_interval.next());
+                        spawn_candles_fetch(app.provider.clone(), app.symbol.clone(), app.candle_interval, app.candle_buffer, update_tx.clone());
                     }
                     false
                 }
@@ -746,4 +1556,67 @@ async fn handle_input(
             }
         },
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::StreamExt;
+    use std::time::{Duration, Instant};
+    use tokio_tungstenite::tungstenite::Message;
+
+    /// A local websocket server that forwards every frame a client sends,
+    /// pings aside, until it closes.
+    async fn frame_server() -> (String, mpsc::UnboundedReceiver<Message>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let (frames_tx, frames_rx) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+            while let Some(Ok(frame)) = ws.next().await {
+                let closed = frame.is_close();
+                if !frame.is_ping() && !frame.is_pong() {
+                    let _ = frames_tx.send(frame);
+                }
+                if closed {
+                    break;
+                }
+            }
+        });
+        (url, frames_rx)
+    }
+
+    /// The `type` and `symbol` of a Finnhub subscription frame.
+    fn subscription(frame: &Message) -> (String, String) {
+        let Message::Text(text) = frame else { panic!("expected text, got {:?}", frame) };
+        let json: serde_json::Value = serde_json::from_str(text).unwrap();
+        (json["type"].as_str().unwrap().to_string(), json["symbol"].as_str().unwrap().to_string())
+    }
+
+    #[tokio::test]
+    async fn stopping_unsubscribes_before_closing() {
+        let (url, mut frames) = frame_server().await;
+        let manager = Arc::new(websocket::WebSocketManager::new(websocket::FinnhubProvider::at(&url, "test")));
+        let should_stop = Arc::new(Mutex::new(false));
+        let (tx, _prices) = mpsc::unbounded_channel();
+        let (status_tx, _statuses) = mpsc::unbounded_channel();
+        let mut handle = Some(tokio::spawn(websocket::start_websocket(
+            manager, vec!["AAPL".to_string()], 100.0, None, tx, status_tx, should_stop.clone(),
+        )));
+        let mut next_frame = async || {
+            tokio::time::timeout(Duration::from_secs(5), frames.recv()).await
+                .expect("timed out waiting for a frame")
+                .expect("server went away")
+        };
+        assert_eq!(subscription(&next_frame().await), ("subscribe".to_string(), "AAPL".to_string()));
+
+        let started = Instant::now();
+        stop_websocket(&mut handle, &should_stop).await;
+        assert!(started.elapsed() < STOP_TIMEOUT, "took {:?}", started.elapsed());
+        assert!(handle.is_none());
+
+        assert_eq!(subscription(&next_frame().await), ("unsubscribe".to_string(), "AAPL".to_string()));
+        assert!(next_frame().await.is_close());
+    }
+}
@@ -0,0 +1,283 @@
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+
+use crate::stock::{self, StockData, TimeFrame};
+use crate::ui::Candlestick;
+
+/// A source of historical price data. The app talks to this rather than to a
+/// particular API so a different backend (or canned fixture data) can be
+/// dropped in at startup.
+pub trait DataProvider: Send + Sync {
+    /// Chart series for `symbol` over `timeframe`, including quote metadata.
+    /// `interval` overrides the timeframe's default sampling (e.g. "1m").
+    fn fetch_history(
+        &self,
+        symbol: &str,
+        timeframe: TimeFrame,
+        interval: Option<&str>,
+    ) -> Result<StockData, Box<dyn std::error::Error>>;
+
+    /// OHLC candles for `[from, to)` at `interval` (e.g. "5m").
+    fn fetch_candles(
+        &self,
+        symbol: &str,
+        interval: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Candlestick>, Box<dyn std::error::Error>>;
+}
+
+/// Yahoo Finance chart API. Needs no key and serves both history and intraday candles.
+pub struct YahooProvider;
+
+impl DataProvider for YahooProvider {
+    fn fetch_history(
+        &self,
+        symbol: &str,
+        timeframe: TimeFrame,
+        interval: Option<&str>,
+    ) -> Result<StockData, Box<dyn std::error::Error>> {
+        stock::fetch_stock_data(symbol, timeframe, interval)
+    }
+
+    fn fetch_candles(
+        &self,
+        symbol: &str,
+        interval: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Candlestick>, Box<dyn std::error::Error>> {
+        stock::fetch_historical_candles(symbol, interval, from, to)
+    }
+}
+
+/// Provider used when nothing else is configured.
+pub fn default_provider() -> Arc<dyn DataProvider> {
+    Arc::new(YahooProvider)
+}
+
+/// Canned data for tests: `FIXTURE_SYMBOL` climbs from $100 with a wobble,
+/// one bar per interval, and anything else is an unknown symbol.
+#[cfg(test)]
+pub mod fixture {
+    use super::*;
+
+    /// A provider that answers for `FIXTURE_SYMBOL` only.
+    pub struct FixtureProvider;
+
+    pub const FIXTURE_SYMBOL: &str = "FIXT";
+
+    /// Where the fixture's history ends: 2023-11-14 22:13:20 UTC.
+    pub const FIXTURE_END: i64 = 1_700_000_000;
+
+    pub fn fixture_price(bar: i64) -> f64 {
+        100.0 + bar as f64 * 0.25 + (bar as f64 * 0.9).sin() * 2.0
+    }
+
+    /// Seconds in a Yahoo interval such as "5m", "1h" or "1wk".
+    fn interval_secs(interval: &str) -> i64 {
+        let split = interval.find(|c: char| !c.is_ascii_digit()).unwrap_or(interval.len());
+        let count: i64 = interval[..split].parse().unwrap_or(1);
+        count * match &interval[split..] {
+            "h" => 3_600,
+            "d" => 86_400,
+            "wk" => 7 * 86_400,
+            "mo" => 30 * 86_400,
+            _ => 60,
+        }
+    }
+
+    /// `bars` of fixture history for `symbol` at `interval`, ending at `FIXTURE_END`.
+    pub fn fixture_data(symbol: &str, interval: &str, bars: usize) -> StockData {
+        use std::collections::VecDeque;
+
+        let step = interval_secs(interval);
+        let first = FIXTURE_END - (bars as i64 - 1) * step;
+        let timestamps: Vec<DateTime<Utc>> = (0..bars as i64)
+            .map(|i| DateTime::from_timestamp(first + i * step, 0).unwrap())
+            .collect();
+        let prices: Vec<f64> = (0..bars as i64).map(fixture_price).collect();
+        let volumes = (0..bars).map(|i| 1_000.0 + (i % 5) as f64 * 250.0).collect();
+        let (first_price, current_price) = (prices[0], prices[prices.len() - 1]);
+        let previous_close = prices[prices.len().saturating_sub(13)];
+        StockData {
+            symbol: symbol.to_string(),
+            timestamps,
+            prices,
+            volumes,
+            current_price,
+            change: current_price - previous_close,
+            change_percent: (current_price - previous_close) / previous_close * 100.0,
+            period_change: current_price - first_price,
+            period_change_percent: (current_price - first_price) / first_price * 100.0,
+            live_ticks: VecDeque::new(),
+            live_current_price: None,
+            base_historical_price: previous_close,
+            previous_close: Some(previous_close),
+            market_state: stock::MarketState::Closed,
+            market_hours: None,
+            earnings: None,
+            long_name: Some("Fixture Industries".to_string()),
+            currency: Some("USD".to_string()),
+            exchange_name: Some("NasdaqGS".to_string()),
+            exchange_timezone: Some(("UTC".to_string(), 0)),
+            regular_market_price: Some(current_price),
+            fifty_two_week_high: Some(150.0),
+            fifty_two_week_low: Some(90.0),
+            fx: None,
+            fx_error: None,
+            converted: false,
+        }
+    }
+
+    /// `count` fixture candles at `interval` seconds, the first opening at `from`.
+    pub fn fixture_candles(from: DateTime<Utc>, interval: i64, count: usize) -> Vec<Candlestick> {
+        (0..count as i64)
+            .map(|i| {
+                let (open, close) = (fixture_price(i), fixture_price(i + 1));
+                Candlestick {
+                    open,
+                    high: open.max(close) + 0.5,
+                    low: open.min(close) - 0.5,
+                    close,
+                    volume: 1_000 + (i as u64 % 4) * 500,
+                    timestamp: from + chrono::Duration::seconds(i * interval),
+                    trade_count: 0,
+                }
+            })
+            .collect()
+    }
+
+    impl DataProvider for FixtureProvider {
+        fn fetch_history(
+            &self,
+            symbol: &str,
+            timeframe: TimeFrame,
+            interval: Option<&str>,
+        ) -> Result<StockData, Box<dyn std::error::Error>> {
+            if symbol != FIXTURE_SYMBOL {
+                return Err(format!("No data found for {}", symbol).into());
+            }
+            Ok(fixture_data(symbol, interval.unwrap_or(timeframe.to_interval()), 60))
+        }
+
+        fn fetch_candles(
+            &self,
+            symbol: &str,
+            interval: &str,
+            from: DateTime<Utc>,
+            to: DateTime<Utc>,
+        ) -> Result<Vec<Candlestick>, Box<dyn std::error::Error>> {
+            if symbol != FIXTURE_SYMBOL {
+                return Err(format!("No data found for {}", symbol).into());
+            }
+            let step = interval_secs(interval);
+            let first = from.timestamp().div_euclid(step) * step;
+            let count = ((to.timestamp() - first) / step).max(0) as usize;
+            Ok(fixture_candles(DateTime::from_timestamp(first, 0).unwrap(), step, count))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::fixture::*;
+    use crate::ui::{App, CandleInterval};
+    use crate::AppUpdate;
+    use tokio::sync::mpsc;
+
+    /// An app on the fixture symbol, fetching through `FixtureProvider`.
+    fn fixture_app() -> App {
+        let mut app = App::new();
+        app.provider = Arc::new(FixtureProvider);
+        app.symbol = FIXTURE_SYMBOL.to_string();
+        app
+    }
+
+    /// Fetch `symbol` the way a keypress does and apply what comes back.
+    async fn fetch_history(app: &mut App, symbol: &str) {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        app.fetch_data();
+        crate::spawn_stock_fetch(app.provider.clone(), symbol.to_string(), app.timeframe, app.interval_override, tx);
+        match rx.recv().await {
+            Some(AppUpdate::StockData { symbol, result }) => app.apply_stock_data(&symbol, result.map(|d| *d)),
+            _ => panic!("expected stock data"),
+        }
+    }
+
+    #[tokio::test]
+    async fn history_reaches_the_chart() {
+        let mut app = fixture_app();
+        fetch_history(&mut app, FIXTURE_SYMBOL).await;
+
+        assert!(!app.loading);
+        assert!(app.error_message.is_none());
+        let data = app.stock_data.as_ref().unwrap();
+        assert_eq!(data.prices.len(), 60);
+        assert_eq!(data.current_price, fixture_price(59));
+        let screen = crate::ui::render_to_text(&app, 120, 40).unwrap();
+        assert!(screen.contains("Fixture Industries"), "{}", screen);
+    }
+
+    #[tokio::test]
+    async fn unknown_symbol_shows_an_error() {
+        let mut app = fixture_app();
+        app.symbol = "NOPE".to_string();
+        fetch_history(&mut app, "NOPE").await;
+
+        assert!(app.stock_data.is_none());
+        assert!(app.error_message.as_deref().is_some_and(|m| m.contains("\"NOPE\"")));
+        assert!(app.ws_error_log.iter().any(|e| e.contains("No data found for NOPE")));
+    }
+
+    #[tokio::test]
+    async fn stale_response_is_dropped() {
+        let mut app = fixture_app();
+        // Moved on to another symbol before the fixture's answer arrived
+        app.symbol = "OTHER".to_string();
+        fetch_history(&mut app, FIXTURE_SYMBOL).await;
+
+        assert!(!app.loading);
+        assert!(app.stock_data.is_none() && app.error_message.is_none());
+    }
+
+    #[tokio::test]
+    async fn candle_history_fills_the_live_chart() {
+        let mut app = fixture_app();
+        app.candle_interval = CandleInterval::FiveMinutes;
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        crate::spawn_candles_fetch(app.provider.clone(), app.symbol.clone(), app.candle_interval, 30, tx);
+        match rx.recv().await {
+            Some(AppUpdate::HistoricalCandles { interval, candles }) => app.apply_historical_candles(interval, candles),
+            _ => panic!("expected candles"),
+        }
+
+        assert!(!app.live_candles.is_empty() && app.live_candles.len() <= app.candle_buffer);
+        assert!(app.live_candles.iter().all(|c| c.timestamp.timestamp() % 300 == 0));
+        assert!(app.live_candles.iter().zip(app.live_candles.iter().skip(1)).all(|(a, b)| a.timestamp < b.timestamp));
+    }
+
+    #[tokio::test]
+    async fn history_aggregates_into_candles() {
+        let mut app = fixture_app();
+        app.interval_override = Some("5m");
+        app.candle_interval = CandleInterval::FifteenMinutes;
+        fetch_history(&mut app, FIXTURE_SYMBOL).await;
+
+        // Sixty 5m bars fall three to a 15m bucket
+        let candles = app.convert_to_candlesticks();
+        assert_eq!(candles.len(), 20);
+        for (k, candle) in candles.iter().enumerate() {
+            let bars: Vec<i64> = (3 * k as i64..3 * k as i64 + 3).collect();
+            let prices: Vec<f64> = bars.iter().map(|&b| fixture_price(b)).collect();
+            assert_eq!(candle.open, prices[0]);
+            assert_eq!(candle.close, prices[2]);
+            assert_eq!(candle.high, prices.iter().cloned().fold(f64::NEG_INFINITY, f64::max));
+            assert_eq!(candle.low, prices.iter().cloned().fold(f64::INFINITY, f64::min));
+            let volume: u64 = bars.iter().map(|&b| 1_000 + (b as u64 % 5) * 250).sum();
+            assert_eq!((candle.volume, candle.trade_count), (volume, 3));
+        }
+        assert_eq!(candles[19].timestamp.timestamp(), FIXTURE_END - 2 * 300);
+    }
+}
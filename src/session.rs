@@ -0,0 +1,258 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::ui::{CandleInterval, Candlestick, Trade};
+use crate::websocket::LivePrice;
+
+/// A captured stretch of live data, written by 'x' in live mode and
+/// replayed with `--replay <file>`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LiveSession {
+    pub symbol: String,
+    pub candle_interval: CandleInterval,
+    pub exported_at: DateTime<Utc>,
+    /// Newest first, as held in `App::live_trades`
+    pub trades: Vec<Trade>,
+    pub candles: Vec<Candlestick>,
+    pub current_candle: Option<Candlestick>,
+}
+
+/// Write `session` to `charty_live_SYMBOL_YYYYMMDD_HHMMSS.json` in `dir`.
+pub fn export(session: &LiveSession, dir: &Path) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let stamp = session.exported_at.with_timezone(&chrono::Local).format("%Y%m%d_%H%M%S");
+    let path = dir.join(format!(
+        "charty_live_{}_{}.json",
+        crate::stock::file_safe_symbol(&session.symbol),
+        stamp
+    ));
+    let json = serde_json::to_string_pretty(session)?;
+    std::fs::write(&path, json).map_err(|e| format!("Could not write {}: {}", path.display(), e))?;
+    Ok(path)
+}
+
+pub fn load(path: &Path) -> Result<LiveSession, Box<dyn std::error::Error>> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| format!("Could not read {}: {}", path.display(), e))?;
+    serde_json::from_str(&contents)
+        .map_err(|e| format!("{} is not a charty live session: {}", path.display(), e).into())
+}
+
+/// One line of a recording: a live update as it came off the feed, tagged
+/// with when charty received it.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Record {
+    pub received_ms: i64,
+    #[serde(flatten)]
+    pub price: LivePrice,
+}
+
+/// Appends every live update to a JSON-lines file while recording is on
+/// ('R' in live mode). Replayed with `--replay <file>.jsonl`.
+pub struct Recorder {
+    pub path: PathBuf,
+    pub records: usize,
+    file: std::fs::File,
+}
+
+impl Recorder {
+    /// Start `charty_rec_SYMBOL_YYYYMMDD_HHMMSS.jsonl` in `dir`.
+    pub fn start(symbol: &str, dir: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let stamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+        let path = dir.join(format!("charty_rec_{}_{}.jsonl", crate::stock::file_safe_symbol(symbol), stamp));
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|e| format!("Could not create {}: {}", path.display(), e))?;
+        Ok(Self { path, records: 0, file })
+    }
+
+    pub fn record(&mut self, price: &LivePrice) -> Result<(), Box<dyn std::error::Error>> {
+        let record = Record { received_ms: Utc::now().timestamp_millis(), price: price.clone() };
+        let mut line = serde_json::to_string(&record)?;
+        line.push('\n');
+        self.file
+            .write_all(line.as_bytes())
+            .map_err(|e| format!("Could not write {}: {}", self.path.display(), e))?;
+        self.records += 1;
+        Ok(())
+    }
+}
+
+pub fn load_recording(path: &Path) -> Result<Vec<Record>, Box<dyn std::error::Error>> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| format!("Could not read {}: {}", path.display(), e))?;
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str(line)
+                .map_err(|e| format!("{} line {} is not a charty recording: {}", path.display(), i + 1, e).into())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A fresh directory under the temp dir for one test's files.
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("charty-session-{}-{}", std::process::id(), name));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn recording_round_trips() {
+        let dir = scratch_dir("record");
+        let mut recorder = Recorder::start("BTC/USD", &dir).unwrap();
+        let prices = [
+            LivePrice::trade("BTC/USD".to_string(), 64_000.5, 1_700_000_000, Some(2)),
+            LivePrice::trade("BTC/USD".to_string(), 64_001.0, 1_700_000_001, None),
+        ];
+        for price in &prices {
+            recorder.record(price).unwrap();
+        }
+
+        assert_eq!(recorder.records, 2);
+        assert!(recorder.path.file_name().unwrap().to_string_lossy().starts_with("charty_rec_BTC_USD_"));
+        let records = load_recording(&recorder.path).unwrap();
+        assert_eq!(records.len(), 2);
+        for (record, price) in records.iter().zip(&prices) {
+            assert_eq!(record.price.symbol, price.symbol);
+            assert_eq!((record.price.price, record.price.timestamp), (price.price, price.timestamp));
+            assert_eq!(record.price.volume, price.volume);
+            assert_eq!(record.price.notional, price.notional);
+        }
+        assert!(records[0].received_ms <= records[1].received_ms);
+    }
+
+    #[test]
+    fn older_recordings_default_the_notional() {
+        let dir = scratch_dir("legacy");
+        let path = dir.join("old.jsonl");
+        std::fs::write(&path, concat!(
+            r#"{"received_ms":1700000000000,"symbol":"AAPL","price":190.5,"timestamp":1700000000,"volume":10,"open":190.5,"high":190.5,"low":190.5,"trades":1}"#,
+            "\n\n",
+            r#"{"received_ms":1700000000500,"symbol":"AAPL","price":190.75,"timestamp":1700000000,"volume":null,"open":190.5,"high":190.75,"low":190.5,"trades":2,"notional":381.25}"#,
+            "\n",
+        )).unwrap();
+
+        let records = load_recording(&path).unwrap();
+        assert_eq!(records[0].price.notional, 0.0);
+        assert_eq!(records[0].price.volume, Some(10));
+        assert_eq!(records[1].price.notional, 381.25);
+    }
+
+    #[test]
+    fn bad_recording_line_is_named() {
+        let dir = scratch_dir("bad");
+        let path = dir.join("bad.jsonl");
+        let good = serde_json::to_string(&Record {
+            received_ms: 0,
+            price: LivePrice::trade("AAPL".to_string(), 1.0, 0, None),
+        }).unwrap();
+        std::fs::write(&path, format!("{}\n{{not json\n", good)).unwrap();
+
+        let err = load_recording(&path).unwrap_err().to_string();
+        assert!(err.contains("line 2 is not a charty recording"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn replay_sends_records_in_recorded_order() {
+        use tokio::sync::{mpsc, Mutex};
+
+        let records: Vec<Record> = [(0, 100.0), (40, 101.0), (40, 99.5), (90, 102.25)]
+            .into_iter()
+            .map(|(ms, price)| Record {
+                received_ms: 1_700_000_000_000 + ms,
+                price: LivePrice::trade("AAPL".to_string(), price, 1_700_000_000, Some(1)),
+            })
+            .collect();
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let (status_tx, mut status_rx) = mpsc::unbounded_channel();
+        let started = std::time::Instant::now();
+        crate::websocket::start_replay(records, 10.0, tx, status_tx, std::sync::Arc::new(Mutex::new(false))).await;
+
+        let mut played = Vec::new();
+        while let Ok(price) = rx.try_recv() {
+            played.push(price.price);
+        }
+        assert_eq!(played, vec![100.0, 101.0, 99.5, 102.25]);
+        // 90ms of recording at 10x
+        assert!(started.elapsed() >= std::time::Duration::from_millis(9));
+        assert!(matches!(status_rx.try_recv(), Ok(crate::ui::WebSocketStatus::Replay { speed }) if speed == 10.0));
+        assert!(matches!(status_rx.try_recv(), Ok(crate::ui::WebSocketStatus::Disconnected)));
+    }
+
+    /// One-minute session on a minute boundary: a candle from before the
+    /// trades, a stale copy of the one they rebuild, and three trades.
+    fn live_session() -> LiveSession {
+        let at = |secs: i64| DateTime::from_timestamp(1_699_999_980 + secs, 0).unwrap();
+        let candle = |secs: i64, close: f64| Candlestick {
+            open: close, high: close, low: close, close, volume: 5, timestamp: at(secs), trade_count: 1,
+        };
+        let trade = |secs: i64, price: f64, volume: u64| Trade { price, timestamp: at(secs), volume: Some(volume) };
+        LiveSession {
+            symbol: "AAPL".to_string(),
+            candle_interval: CandleInterval::OneMinute,
+            exported_at: at(90),
+            trades: vec![trade(70, 12.0, 3), trade(10, 11.0, 2), trade(5, 10.0, 1)],
+            candles: vec![candle(-120, 9.0), candle(0, 50.0)],
+            current_candle: Some(candle(60, 12.0)),
+        }
+    }
+
+    #[test]
+    fn live_session_round_trips() {
+        let dir = scratch_dir("export");
+        let session = live_session();
+        let path = export(&session, &dir).unwrap();
+        assert!(path.file_name().unwrap().to_string_lossy().starts_with("charty_live_AAPL_"));
+
+        let loaded = load(&path).unwrap();
+        assert_eq!(loaded.symbol, "AAPL");
+        assert_eq!(loaded.candle_interval, CandleInterval::OneMinute);
+        assert_eq!(loaded.exported_at, session.exported_at);
+        let prices: Vec<f64> = loaded.trades.iter().map(|t| t.price).collect();
+        assert_eq!(prices, vec![12.0, 11.0, 10.0]);
+        assert_eq!(loaded.candles.len(), 2);
+        assert_eq!(loaded.current_candle.map(|c| c.close), Some(12.0));
+    }
+
+    #[test]
+    fn non_session_file_is_rejected() {
+        let dir = scratch_dir("reject");
+        let path = dir.join("notes.json");
+        std::fs::write(&path, r#"{"symbol":"AAPL"}"#).unwrap();
+
+        let err = load(&path).unwrap_err().to_string();
+        assert!(err.contains("is not a charty live session"), "{}", err);
+        assert!(load(&dir.join("missing.json")).unwrap_err().to_string().starts_with("Could not read"));
+    }
+
+    #[test]
+    fn replayed_session_rebuilds_the_candles() {
+        let dir = scratch_dir("replay");
+        let path = export(&live_session(), &dir).unwrap();
+        let mut app = crate::ui::App::new();
+        app.replay_live_session(&path);
+
+        assert_eq!(app.symbol, "AAPL");
+        assert!(app.error_message.is_none());
+        let prices: Vec<f64> = app.live_trades.iter().map(|t| t.price).collect();
+        assert_eq!(prices, vec![12.0, 11.0, 10.0]);
+        // The old candle is kept; the one the trades cover is rebuilt from them
+        let closes: Vec<f64> = app.live_candles.iter().map(|c| c.close).collect();
+        assert_eq!(closes, vec![9.0, 11.0]);
+        let rebuilt = &app.live_candles[1];
+        assert_eq!((rebuilt.open, rebuilt.high, rebuilt.low, rebuilt.volume), (10.0, 11.0, 10.0, 3));
+        let current = app.current_candle.as_ref().unwrap();
+        assert_eq!((current.close, current.volume), (12.0, 3));
+    }
+}
@@ -28,41 +28,238 @@ pub struct StockData {
     pub prices: Vec<f64>,
     pub volumes: Vec<f64>,
     pub current_price: f64,
+    /// Change against the previous session's close
     pub change: f64,
     pub change_percent: f64,
+    /// Change across the whole loaded timeframe (first point → last point)
+    pub period_change: f64,
+    pub period_change_percent: f64,
     pub live_ticks: VecDeque<LiveTick>,
     pub live_current_price: Option<f64>,
     pub base_historical_price: f64,
+    /// Previous session's close from the chart meta (or the series itself),
+    /// the baseline of the 1D baseline chart
+    pub previous_close: Option<f64>,
     pub market_state: MarketState,
+    pub market_hours: Option<MarketHours>,
+    /// Filled in asynchronously after the chart loads (Finnhub)
+    pub earnings: Option<EarningsInfo>,
+    // Instrument metadata from the chart response's `meta` object
+    pub long_name: Option<String>,
+    pub currency: Option<String>,
+    pub exchange_name: Option<String>,
+    /// Exchange time zone abbreviation (e.g. "EDT") and its offset east of
+    /// UTC in seconds, for showing times on the exchange's clock
+    pub exchange_timezone: Option<(String, i64)>,
+    pub regular_market_price: Option<f64>,
+    pub fifty_two_week_high: Option<f64>,
+    pub fifty_two_week_low: Option<f64>,
+    /// Set when the listing trades in something other than the display currency
+    pub fx: Option<FxRate>,
+    /// Why `fx` is missing when the listing needed one
+    pub fx_error: Option<String>,
+    /// Whether prices are currently shown converted with `fx`
+    pub converted: bool,
 }
 
-#[derive(Debug, Clone, Copy)]
+/// Exchange rate from a listing's own currency to the display currency.
+#[derive(Debug, Clone)]
+pub struct FxRate {
+    pub native_currency: String,
+    pub display_currency: String,
+    /// Display-currency units per native unit
+    pub rate: f64,
+}
+
+impl StockData {
+    /// Switch every price field between native and display currency. Prices are
+    /// rescaled in place so the chart, header and live views need no changes;
+    /// percentages are unaffected.
+    pub fn set_converted(&mut self, converted: bool) {
+        let Some(ref fx) = self.fx else { return; };
+        if self.converted == converted {
+            return;
+        }
+        let factor = if converted { fx.rate } else { 1.0 / fx.rate };
+        self.currency = Some(if converted { fx.display_currency.clone() } else { fx.native_currency.clone() });
+        for p in self.prices.iter_mut() {
+            *p *= factor;
+        }
+        for tick in self.live_ticks.iter_mut() {
+            tick.price *= factor;
+        }
+        self.current_price *= factor;
+        self.change *= factor;
+        self.period_change *= factor;
+        self.base_historical_price *= factor;
+        for p in [
+            &mut self.live_current_price,
+            &mut self.previous_close,
+            &mut self.regular_market_price,
+            &mut self.fifty_two_week_high,
+            &mut self.fifty_two_week_low,
+        ].into_iter().flatten() {
+            *p *= factor;
+        }
+        self.converted = converted;
+    }
+
+    /// Multiplier for incoming live prices, which always arrive in the native currency.
+    pub fn live_price_factor(&self) -> f64 {
+        match self.fx {
+            Some(ref fx) if self.converted => fx.rate,
+            _ => 1.0,
+        }
+    }
+
+    /// "AAPL · Apple Inc. · NASDAQ · USD", skipping any fields Yahoo didn't return.
+    pub fn description(&self) -> String {
+        let mut parts = vec![self.symbol.as_str()];
+        for field in [&self.long_name, &self.exchange_name, &self.currency] {
+            if let Some(s) = field.as_deref().filter(|s| !s.is_empty()) {
+                parts.push(s);
+            }
+        }
+        parts.join(" · ")
+    }
+
+    pub fn currency_prefix(&self) -> String {
+        currency_prefix(self.currency.as_deref())
+    }
+
+    /// The exchange's currency, which live prices arrive in, whichever one the
+    /// prices are shown in.
+    pub fn native_currency(&self) -> Option<&str> {
+        match self.fx {
+            Some(ref fx) if self.converted => Some(&fx.native_currency),
+            _ => self.currency.as_deref(),
+        }
+    }
+
+    /// Index and price of the series high and low. Live ticks count too and sit
+    /// on the last index, since they extend the chart's final point.
+    pub fn session_extremes(&self) -> Option<((usize, f64), (usize, f64))> {
+        let last = self.prices.len().checked_sub(1)?;
+        let points = self.prices.iter()
+            .copied()
+            .enumerate()
+            .chain(self.live_ticks.iter().map(|t| (last, t.price)));
+        let mut high = (0, f64::NEG_INFINITY);
+        let mut low = (0, f64::INFINITY);
+        for (i, p) in points {
+            if p > high.1 {
+                high = (i, p);
+            }
+            if p < low.1 {
+                low = (i, p);
+            }
+        }
+        Some((high, low))
+    }
+
+    /// True once the (live) price trades above the 52-week high.
+    pub fn above_52w_high(&self) -> bool {
+        self.fifty_two_week_high.is_some_and(|h| self.current_price > h)
+    }
+}
+
+/// Price prefix for an ISO currency code. Unknown codes are shown verbatim.
+pub fn currency_prefix(currency: Option<&str>) -> String {
+    match currency {
+        None | Some("USD") => "$".to_string(),
+        Some("EUR") => "€".to_string(),
+        Some("GBP") => "£".to_string(),
+        Some("JPY") => "JP¥".to_string(),
+        Some("CNY") => "CN¥".to_string(),
+        Some("INR") => "₹".to_string(),
+        Some("KRW") => "₩".to_string(),
+        Some("CAD") => "C$".to_string(),
+        Some("AUD") => "A$".to_string(),
+        Some("HKD") => "HK$".to_string(),
+        Some(code) => format!("{} ", code),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum TimeFrame {
     OneDay,
     OneWeek,
     OneMonth,
     ThreeMonths,
+    SixMonths,
+    YearToDate,
     OneYear,
+    FiveYears,
+    Max,
 }
 
 impl TimeFrame {
-    pub fn to_api_string(&self) -> &str {
+    pub fn to_api_string(self) -> &'static str {
         match self {
             TimeFrame::OneDay => "1d",
             TimeFrame::OneWeek => "5d",
             TimeFrame::OneMonth => "1mo",
             TimeFrame::ThreeMonths => "3mo",
+            TimeFrame::SixMonths => "6mo",
+            TimeFrame::YearToDate => "ytd",
             TimeFrame::OneYear => "1y",
+            TimeFrame::FiveYears => "5y",
+            TimeFrame::Max => "max",
         }
     }
 
-    pub fn to_interval(&self) -> &str {
+    pub fn to_interval(self) -> &'static str {
         match self {
             TimeFrame::OneDay => "5m",
             TimeFrame::OneWeek => "30m",
             TimeFrame::OneMonth => "1d",
             TimeFrame::ThreeMonths => "1d",
+            TimeFrame::SixMonths => "1d",
+            TimeFrame::YearToDate => "1d",
             TimeFrame::OneYear => "1wk",
+            TimeFrame::FiveYears => "1wk",
+            TimeFrame::Max => "1mo",
+        }
+    }
+
+    /// Intervals Yahoo will serve for this range, finest first.
+    pub fn supported_intervals(self) -> &'static [&'static str] {
+        match self {
+            TimeFrame::OneDay => &["1m", "2m", "5m", "15m", "30m", "60m"],
+            TimeFrame::OneWeek => &["1m", "5m", "15m", "30m", "60m", "1d"],
+            TimeFrame::OneMonth => &["5m", "15m", "30m", "60m", "1d"],
+            TimeFrame::ThreeMonths | TimeFrame::SixMonths | TimeFrame::YearToDate => &["60m", "1d", "1wk"],
+            TimeFrame::OneYear => &["60m", "1d", "1wk", "1mo"],
+            TimeFrame::FiveYears | TimeFrame::Max => &["1d", "1wk", "1mo"],
+        }
+    }
+
+    /// Roughly how many calendar days the range reaches back.
+    fn span_days(self) -> i64 {
+        match self {
+            TimeFrame::OneDay => 1,
+            TimeFrame::OneWeek => 7,
+            TimeFrame::OneMonth => 31,
+            TimeFrame::ThreeMonths => 92,
+            TimeFrame::SixMonths => 183,
+            TimeFrame::YearToDate => chrono::Datelike::ordinal(&chrono::Local::now()) as i64,
+            TimeFrame::OneYear => 366,
+            TimeFrame::FiveYears => 5 * 366,
+            TimeFrame::Max => i64::MAX,
+        }
+    }
+
+    pub fn short_label(self) -> &'static str {
+        match self {
+            TimeFrame::OneDay => "1D",
+            TimeFrame::OneWeek => "1W",
+            TimeFrame::OneMonth => "1M",
+            TimeFrame::ThreeMonths => "3M",
+            TimeFrame::SixMonths => "6M",
+            TimeFrame::YearToDate => "YTD",
+            TimeFrame::OneYear => "1Y",
+            TimeFrame::FiveYears => "5Y",
+            TimeFrame::Max => "Max",
         }
     }
 
@@ -72,7 +269,11 @@ impl TimeFrame {
             TimeFrame::OneWeek => "1 Week",
             TimeFrame::OneMonth => "1 Month",
             TimeFrame::ThreeMonths => "3 Months",
+            TimeFrame::SixMonths => "6 Months",
+            TimeFrame::YearToDate => "Year to Date",
             TimeFrame::OneYear => "1 Year",
+            TimeFrame::FiveYears => "5 Years",
+            TimeFrame::Max => "Max",
         }
     }
 
@@ -81,22 +282,115 @@ impl TimeFrame {
             TimeFrame::OneDay => TimeFrame::OneWeek,
             TimeFrame::OneWeek => TimeFrame::OneMonth,
             TimeFrame::OneMonth => TimeFrame::ThreeMonths,
-            TimeFrame::ThreeMonths => TimeFrame::OneYear,
-            TimeFrame::OneYear => TimeFrame::OneDay,
+            TimeFrame::ThreeMonths => TimeFrame::SixMonths,
+            TimeFrame::SixMonths => TimeFrame::YearToDate,
+            TimeFrame::YearToDate => TimeFrame::OneYear,
+            TimeFrame::OneYear => TimeFrame::FiveYears,
+            TimeFrame::FiveYears => TimeFrame::Max,
+            TimeFrame::Max => TimeFrame::OneDay,
         }
     }
 
     pub fn prev(&self) -> Self {
         match self {
-            TimeFrame::OneDay => TimeFrame::OneYear,
+            TimeFrame::OneDay => TimeFrame::Max,
             TimeFrame::OneWeek => TimeFrame::OneDay,
             TimeFrame::OneMonth => TimeFrame::OneWeek,
             TimeFrame::ThreeMonths => TimeFrame::OneMonth,
-            TimeFrame::OneYear => TimeFrame::ThreeMonths,
+            TimeFrame::SixMonths => TimeFrame::ThreeMonths,
+            TimeFrame::YearToDate => TimeFrame::SixMonths,
+            TimeFrame::OneYear => TimeFrame::YearToDate,
+            TimeFrame::FiveYears => TimeFrame::OneYear,
+            TimeFrame::Max => TimeFrame::FiveYears,
         }
     }
 }
 
+/// How far back Yahoo keeps intraday data at each interval, in days.
+fn interval_history_days(interval: &str) -> Option<i64> {
+    match interval {
+        "1m" => Some(7),
+        "2m" | "5m" | "15m" | "30m" | "90m" => Some(60),
+        "60m" | "1h" => Some(730),
+        _ => None,
+    }
+}
+
+/// Reject interval/range pairs Yahoo would refuse, with a message fit for the UI.
+pub fn check_interval(timeframe: TimeFrame, interval: &str) -> Result<(), String> {
+    match interval_history_days(interval) {
+        Some(days) if timeframe.span_days() > days => Err(format!(
+            "{} data only goes back {} days, too short for {}",
+            interval, days, timeframe.display()
+        )),
+        _ => Ok(()),
+    }
+}
+
+// ── HTTP agent ───────────────────────────────────────────────────────────────
+
+fn builder_with_proxy() -> ureq::AgentBuilder {
+    builder(crate::config::request_timeout(), crate::config::proxy_url())
+}
+
+fn builder(timeout: std::time::Duration, proxy: Option<String>) -> ureq::AgentBuilder {
+    let builder = ureq::AgentBuilder::new()
+        .timeout_connect(timeout)
+        .timeout_read(timeout);
+    match proxy {
+        Some(url) => match ureq::Proxy::new(&url) {
+            Ok(proxy) => builder.proxy(proxy),
+            Err(e) => {
+                log_debug(&format!("[http] ignoring invalid proxy {:?}: {}", url, e));
+                builder
+            }
+        },
+        None => builder,
+    }
+}
+
+/// Shared agent for one-off requests, routed through the configured proxy.
+fn agent() -> &'static ureq::Agent {
+    static AGENT: std::sync::OnceLock<ureq::Agent> = std::sync::OnceLock::new();
+    AGENT.get_or_init(|| builder_with_proxy().build())
+}
+
+/// Shown instead of the raw transport error when Yahoo doesn't answer in time.
+pub const TIMEOUT_MESSAGE: &str = "Timed out contacting Yahoo — check your connection";
+
+fn is_timeout(err: &ureq::Error) -> bool {
+    let mut source = std::error::Error::source(err);
+    while let Some(e) = source {
+        if let Some(io) = e.downcast_ref::<std::io::Error>()
+            && matches!(io.kind(), std::io::ErrorKind::TimedOut | std::io::ErrorKind::WouldBlock)
+        {
+            return true;
+        }
+        source = e.source();
+    }
+    err.to_string().contains("timed out")
+}
+
+/// GET a Yahoo endpoint, turning timeouts into `TIMEOUT_MESSAGE` tagged with the host.
+fn yahoo_get(url: &str) -> Result<ureq::Response, Box<dyn std::error::Error>> {
+    get_with(agent(), url)
+}
+
+fn get_with(agent: &ureq::Agent, url: &str) -> Result<ureq::Response, Box<dyn std::error::Error>> {
+    agent.get(url)
+        .set("User-Agent", "Mozilla/5.0")
+        .call()
+        .map_err(|e| {
+            if is_timeout(&e) {
+                let host = url.split("://").nth(1).and_then(|rest| rest.split('/').next()).unwrap_or(url);
+                log_debug(&format!("[http] timeout after {:?} contacting {}", crate::config::request_timeout(), host));
+                format!("{} ({})", TIMEOUT_MESSAGE, host).into()
+            } else {
+                e.into()
+            }
+        })
+}
+
 // ── Yahoo session (crumb + cookie jar) ───────────────────────────────────────
 
 pub struct YahooSession {
@@ -107,7 +401,7 @@ pub struct YahooSession {
 impl YahooSession {
     pub fn new() -> Result<Self, Box<dyn std::error::Error>> {
         let timeout = std::time::Duration::from_secs(10);
-        let agent = ureq::AgentBuilder::new()
+        let agent = builder_with_proxy()
             .cookie_store(cookie_store::CookieStore::default())
             .timeout(timeout)
             .build();
@@ -171,11 +465,112 @@ impl MarketState {
     }
 }
 
+/// Trading session boundaries for the current/most recent exchange day, from the
+/// chart meta's `currentTradingPeriod`. Extended hours count as open because
+/// trades still stream during them.
+#[derive(Debug, Clone)]
+pub struct MarketHours {
+    pub session_start: i64,
+    pub session_end: i64,
+    pub regular_start: i64,
+    pub regular_end: i64,
+    pub gmt_offset: i64,
+    /// 24/7 instruments such as crypto
+    pub always_open: bool,
+}
+
+impl MarketHours {
+    pub fn is_open_at(&self, now: i64) -> bool {
+        self.always_open || (now >= self.session_start && now < self.session_end)
+    }
+
+    /// Which part of these hours `now` falls in: the regular session, the
+    /// extended hours either side of it, or none.
+    pub fn state_at(&self, now: i64) -> MarketState {
+        if self.always_open || (self.regular_start > 0 && now >= self.regular_start && now < self.regular_end) {
+            MarketState::Regular
+        } else if now >= self.session_start && now < self.regular_start {
+            MarketState::Pre
+        } else if now >= self.regular_end && now < self.session_end {
+            MarketState::Post
+        } else {
+            MarketState::Closed
+        }
+    }
+
+    /// Whether `ts` falls in regular trading hours, judged by the time of day on
+    /// the exchange's clock so it holds for any day of the chart, not just today.
+    pub fn is_regular_at(&self, ts: i64) -> bool {
+        if self.always_open || self.regular_start <= 0 || self.regular_end <= self.regular_start {
+            return true;
+        }
+        let time_of_day = |t: i64| (t + self.gmt_offset).rem_euclid(86_400);
+        let (open, close) = (time_of_day(self.regular_start), time_of_day(self.regular_end));
+        let now = time_of_day(ts);
+        if open < close {
+            now >= open && now < close
+        } else {
+            // Sessions that run past midnight on the exchange's clock
+            now >= open || now < close
+        }
+    }
+
+    /// Next regular-session open after `now`, skipping weekends (holidays are not known).
+    pub fn next_open_after(&self, now: i64) -> Option<i64> {
+        if self.always_open || self.regular_start <= 0 {
+            return None;
+        }
+        self.next_weekday_time(self.regular_start, now)
+    }
+
+    /// Next start of trading, extended hours included, after `now`.
+    pub fn next_session_after(&self, now: i64) -> Option<i64> {
+        if self.always_open || self.session_start <= 0 {
+            return None;
+        }
+        self.next_weekday_time(self.session_start, now)
+    }
+
+    /// Whether trades stream at `ts` on any weekday, not just the day these
+    /// hours were read for: `is_open_at` goes stale once that day is over.
+    pub fn is_trading_at(&self, ts: i64) -> bool {
+        use chrono::{Datelike, Weekday};
+
+        let length = self.session_end - self.session_start;
+        if self.always_open || self.session_start <= 0 || !(1..86_400).contains(&length) {
+            return self.is_open_at(ts);
+        }
+        let since_open = (ts - self.session_start).rem_euclid(86_400);
+        let opened = DateTime::from_timestamp(ts - since_open + self.gmt_offset, 0);
+        since_open < length && !opened.is_some_and(|d| matches!(d.weekday(), Weekday::Sat | Weekday::Sun))
+    }
+
+    /// First weekday repeat of the time of day at `first` that's after `now`.
+    fn next_weekday_time(&self, first: i64, now: i64) -> Option<i64> {
+        use chrono::{Datelike, Weekday};
+
+        let mut time = first;
+        if time <= now {
+            // Skip straight to the day before `now`, however stale `first` is
+            time += (now - time) / 86_400 * 86_400;
+        }
+        loop {
+            let local = DateTime::from_timestamp(time + self.gmt_offset, 0)?;
+            let weekend = matches!(local.weekday(), Weekday::Sat | Weekday::Sun);
+            if time > now && !weekend {
+                return Some(time);
+            }
+            time += 86_400;
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct QuoteSnapshot {
     pub price: f64,
     pub change_percent: f64,
     pub market_state: MarketState,
+    pub currency: Option<String>,
 }
 
 pub fn fetch_batch_quotes(
@@ -184,7 +579,7 @@ pub fn fetch_batch_quotes(
 ) -> Result<HashMap<String, QuoteSnapshot>, Box<dyn std::error::Error>> {
     let joined = symbols.join(",");
     let url = format!(
-        "https://query1.finance.yahoo.com/v7/finance/quote?symbols={}&crumb={}&fields=regularMarketPrice,regularMarketChangePercent,marketState",
+        "https://query1.finance.yahoo.com/v7/finance/quote?symbols={}&crumb={}&fields=regularMarketPrice,regularMarketChangePercent,marketState,currency",
         joined, session.crumb
     );
 
@@ -214,6 +609,7 @@ pub fn fetch_batch_quotes(
                 price,
                 change_percent: chg,
                 market_state: state,
+                currency: q["currency"].as_str().map(String::from),
             });
         }
     }
@@ -223,46 +619,75 @@ pub fn fetch_batch_quotes(
 
 // ── Stock chart data ──────────────────────────────────────────────────────────
 
-pub fn fetch_stock_data(symbol: &str, timeframe: TimeFrame) -> Result<StockData, Box<dyn std::error::Error>> {
+/// Fetch the chart for `timeframe`, at `interval` if given or the range's default otherwise.
+pub fn fetch_stock_data(
+    symbol: &str,
+    timeframe: TimeFrame,
+    interval: Option<&str>,
+) -> Result<StockData, Box<dyn std::error::Error>> {
+    let interval = interval.unwrap_or(timeframe.to_interval());
+    check_interval(timeframe, interval)?;
+
     // Include pre/post market data for intraday view
     let include_prepost = matches!(timeframe, TimeFrame::OneDay);
     let url = format!(
         "https://query1.finance.yahoo.com/v8/finance/chart/{}?interval={}&range={}&includePrePost={}",
         symbol,
-        timeframe.to_interval(),
+        interval,
         timeframe.to_api_string(),
         include_prepost,
     );
 
-    let response = ureq::get(&url)
-        .set("User-Agent", "Mozilla/5.0")
-        .timeout(std::time::Duration::from_secs(10))
-        .call()?;
+    let response = yahoo_get(&url)?;
     let json: serde_json::Value = response.into_json()?;
 
     let chart = &json["chart"]["result"][0];
 
-    let market_state = {
-        let now = Utc::now().timestamp();
-        let tp = &chart["meta"]["currentTradingPeriod"];
-        let reg_start = tp["regular"]["start"].as_i64().unwrap_or(0);
-        let reg_end   = tp["regular"]["end"].as_i64().unwrap_or(0);
-        let pre_start = tp["pre"]["start"].as_i64().unwrap_or(0);
-        let pre_end   = tp["pre"]["end"].as_i64().unwrap_or(0);
-        let post_start = tp["post"]["start"].as_i64().unwrap_or(0);
-        let post_end   = tp["post"]["end"].as_i64().unwrap_or(0);
-        log_debug(&format!("[chart API] {} now={} reg={}-{} pre={}-{} post={}-{}", symbol, now, reg_start, reg_end, pre_start, pre_end, post_start, post_end));
-        if reg_start > 0 && now >= reg_start && now < reg_end {
-            MarketState::Regular
-        } else if pre_start > 0 && now >= pre_start && now < pre_end {
-            MarketState::Pre
-        } else if post_start > 0 && now >= post_start && now < post_end {
-            MarketState::Post
+    let meta = &chart["meta"];
+    let long_name = meta["longName"].as_str()
+        .or_else(|| meta["shortName"].as_str())
+        .map(|s| s.to_string());
+    let currency      = meta["currency"].as_str().map(|s| s.to_string());
+    let exchange_name = meta["exchangeName"].as_str().map(|s| s.to_string());
+    let exchange_timezone = meta["exchangeTimezoneShortName"].as_str()
+        .or_else(|| meta["exchangeTimezoneName"].as_str())
+        .zip(meta["gmtoffset"].as_i64())
+        .map(|(name, offset)| (name.to_string(), offset));
+    let regular_market_price = meta["regularMarketPrice"].as_f64();
+    let fifty_two_week_high  = meta["fiftyTwoWeekHigh"].as_f64();
+    let fifty_two_week_low   = meta["fiftyTwoWeekLow"].as_f64();
+    // `chartPreviousClose` is the close before the first bar of the range, which
+    // is only yesterday's close when the range itself is one day.
+    let meta_previous_close = meta["previousClose"].as_f64().or_else(|| {
+        if matches!(timeframe, TimeFrame::OneDay) {
+            meta["chartPreviousClose"].as_f64()
         } else {
-            MarketState::Closed
+            None
         }
+    });
+
+    let always_open = meta["instrumentType"].as_str() == Some("CRYPTOCURRENCY");
+
+    let market_hours = {
+        let tp = &meta["currentTradingPeriod"];
+        let regular_start = tp["regular"]["start"].as_i64().unwrap_or(0);
+        let regular_end   = tp["regular"]["end"].as_i64().unwrap_or(0);
+        let session_start = tp["pre"]["start"].as_i64().unwrap_or(regular_start);
+        let session_end   = tp["post"]["end"].as_i64().unwrap_or(regular_end);
+        (always_open || regular_start > 0).then(|| MarketHours {
+            session_start,
+            session_end,
+            regular_start,
+            regular_end,
+            gmt_offset: meta["gmtoffset"].as_i64().unwrap_or(0),
+            always_open,
+        })
     };
 
+    let now = Utc::now().timestamp();
+    log_debug(&format!("[chart API] {} now={} hours={:?}", symbol, now, market_hours));
+    let market_state = market_hours.as_ref().map_or(MarketState::Closed, |h| h.state_at(now));
+
     let raw_timestamps = chart["timestamp"].as_array().ok_or("No timestamp data")?;
     let quote = &chart["indicators"]["quote"][0];
     let raw_closes  = quote["close"].as_array().ok_or("No close data")?;
@@ -284,10 +709,38 @@ pub fn fetch_stock_data(symbol: &str, timeframe: TimeFrame) -> Result<StockData,
         }
     }
 
+    let display_currency = crate::config::display_currency();
+    let (fx, fx_error) = match currency.as_deref() {
+        Some(native) if native != display_currency => match cached_fx_rate(native, &display_currency) {
+            Ok(rate) => (Some(FxRate {
+                native_currency: native.to_string(),
+                display_currency: display_currency.clone(),
+                rate,
+            }), None),
+            Err(e) => {
+                log_debug(&format!("[fx] {} → {} unavailable: {}", native, display_currency, e));
+                (None, Some(format!("No {} → {} rate: {}", native, display_currency, e)))
+            }
+        },
+        _ => (None, None),
+    };
+
     let current_price = *prices.last().ok_or("No price data")?;
     let first_price   = *prices.first().ok_or("No price data")?;
-    let change         = current_price - first_price;
-    let change_percent = (change / first_price) * 100.0;
+    let period_change         = current_price - first_price;
+    let period_change_percent = (period_change / first_price) * 100.0;
+
+    // Fall back to the last close of an earlier trading day within the series
+    let previous_close = meta_previous_close.or_else(|| {
+        let last_day = timestamps.last()?.date_naive();
+        timestamps.iter().zip(prices.iter())
+            .rev()
+            .find(|(ts, _)| ts.date_naive() < last_day)
+            .map(|(_, &p)| p)
+    });
+    let base_price     = previous_close.unwrap_or(first_price);
+    let change         = current_price - base_price;
+    let change_percent = (change / base_price) * 100.0;
 
     Ok(StockData {
         symbol: symbol.to_string(),
@@ -297,13 +750,278 @@ pub fn fetch_stock_data(symbol: &str, timeframe: TimeFrame) -> Result<StockData,
         current_price,
         change,
         change_percent,
+        period_change,
+        period_change_percent,
         live_ticks: VecDeque::new(),
         live_current_price: None,
-        base_historical_price: current_price,
+        base_historical_price: base_price,
+        previous_close,
         market_state,
+        market_hours,
+        earnings: None,
+        long_name,
+        currency,
+        exchange_name,
+        exchange_timezone,
+        regular_market_price,
+        fifty_two_week_high,
+        fifty_two_week_low,
+        fx,
+        fx_error,
+        converted: false,
     })
 }
 
+/// How long a fetched exchange rate is reused before asking Yahoo again.
+const FX_CACHE_TTL: std::time::Duration = std::time::Duration::from_secs(15 * 60);
+
+/// `fetch_fx_rate`, but reusing a rate fetched for the same pair within
+/// `FX_CACHE_TTL` so switching between listings doesn't refetch it each time.
+fn cached_fx_rate(from: &str, to: &str) -> Result<f64, Box<dyn std::error::Error>> {
+    fx_rate_at(from, to, std::time::Instant::now(), fetch_fx_rate)
+}
+
+type FxCache = std::sync::Mutex<HashMap<(String, String), (f64, std::time::Instant)>>;
+
+fn fx_rate_at(
+    from: &str,
+    to: &str,
+    now: std::time::Instant,
+    fetch: impl FnOnce(&str, &str) -> Result<f64, Box<dyn std::error::Error>>,
+) -> Result<f64, Box<dyn std::error::Error>> {
+    static CACHE: std::sync::OnceLock<FxCache> = std::sync::OnceLock::new();
+    let cache = CACHE.get_or_init(Default::default);
+    let key = (from.to_string(), to.to_string());
+    if let Some(&(rate, fetched)) = cache.lock().unwrap().get(&key)
+        && now.saturating_duration_since(fetched) < FX_CACHE_TTL
+    {
+        return Ok(rate);
+    }
+    // Failures aren't cached, so the next load tries again
+    let rate = fetch(from, to)?;
+    cache.lock().unwrap().insert(key, (rate, now));
+    Ok(rate)
+}
+
+/// Units of `to` per unit of `from`, from Yahoo's `FROMTO=X` pair.
+pub fn fetch_fx_rate(from: &str, to: &str) -> Result<f64, Box<dyn std::error::Error>> {
+    // London listings quote in pence
+    let (from, scale) = if from == "GBp" { ("GBP", 0.01) } else { (from, 1.0) };
+    if from.eq_ignore_ascii_case(to) {
+        return Ok(scale);
+    }
+    let url = format!(
+        "https://query1.finance.yahoo.com/v8/finance/chart/{}{}=X?interval=1d&range=1d",
+        from, to
+    );
+    let json: serde_json::Value = yahoo_get(&url)?.into_json()?;
+    let rate = json["chart"]["result"][0]["meta"]["regularMarketPrice"]
+        .as_f64()
+        .filter(|r| *r > 0.0)
+        .ok_or_else(|| format!("No {}/{} rate", from, to))?;
+    Ok(rate * scale)
+}
+
+/// Fetch several charts in parallel, one thread per symbol. Results come back
+/// in `symbols` order; a symbol that fails or hasn't answered once the shared
+/// deadline passes gets its own `Err` without holding up the others.
+pub fn fetch_many(symbols: &[String], timeframe: TimeFrame) -> Vec<Result<StockData, String>> {
+    use std::sync::mpsc;
+
+    // Connect + read for a single request, plus slack for parsing
+    let deadline = std::time::Instant::now() + crate::config::request_timeout() * 2 + std::time::Duration::from_secs(1);
+    let (tx, rx) = mpsc::channel();
+    for (idx, symbol) in symbols.iter().enumerate() {
+        let tx = tx.clone();
+        let symbol = symbol.clone();
+        std::thread::spawn(move || {
+            let result = fetch_stock_data(&symbol, timeframe, None).map_err(|e| e.to_string());
+            let _ = tx.send((idx, result));
+        });
+    }
+    drop(tx);
+
+    let mut results: Vec<Option<Result<StockData, String>>> = symbols.iter().map(|_| None).collect();
+    let mut pending = symbols.len();
+    while pending > 0 {
+        let remaining = deadline.saturating_duration_since(std::time::Instant::now());
+        match rx.recv_timeout(remaining) {
+            Ok((idx, result)) => {
+                results[idx] = Some(result);
+                pending -= 1;
+            }
+            // Deadline passed or every sender is gone; stragglers are abandoned
+            Err(_) => break,
+        }
+    }
+
+    results.into_iter()
+        .zip(symbols)
+        .map(|(result, symbol)| {
+            result.unwrap_or_else(|| {
+                log_debug(&format!("[fetch_many] {} missed the deadline", symbol));
+                Err(TIMEOUT_MESSAGE.to_string())
+            })
+        })
+        .collect()
+}
+
+// ── CSV import ────────────────────────────────────────────────────────────────
+
+fn parse_csv_timestamp(s: &str) -> Option<DateTime<Utc>> {
+    if let Ok(secs) = s.parse::<i64>() {
+        // Millisecond epochs are common in broker exports
+        return if secs > 100_000_000_000 {
+            DateTime::from_timestamp_millis(secs)
+        } else {
+            DateTime::from_timestamp(secs, 0)
+        };
+    }
+    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+        return Some(dt.with_timezone(&Utc));
+    }
+    for fmt in ["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M:%S"] {
+        if let Ok(naive) = chrono::NaiveDateTime::parse_from_str(s, fmt) {
+            return Some(naive.and_utc());
+        }
+    }
+    chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .ok()
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .map(|naive| naive.and_utc())
+}
+
+/// Load a `timestamp,price[,volume]` CSV into a `StockData` named after the file.
+/// Timestamps may be epoch seconds/millis, RFC 3339, or `YYYY-MM-DD[ HH:MM[:SS]]`.
+/// Malformed rows are skipped and described in the returned warnings.
+pub fn load_from_csv(path: &std::path::Path) -> Result<(StockData, Vec<String>), Box<dyn std::error::Error>> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| format!("Could not read {}: {}", path.display(), e))?;
+    let name = path.file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string());
+
+    let mut rows: Vec<(DateTime<Utc>, f64, f64)> = Vec::new();
+    let mut warnings = Vec::new();
+    for (idx, line) in contents.lines().enumerate() {
+        let line_no = idx + 1;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let cols: Vec<&str> = line.split(',').map(|c| c.trim().trim_matches('"')).collect();
+        let timestamp = cols.first().and_then(|c| parse_csv_timestamp(c));
+        let price = cols.get(1).and_then(|c| c.parse::<f64>().ok()).filter(|p| p.is_finite());
+        // A leading header row is expected, not an error
+        if rows.is_empty() && warnings.is_empty() && timestamp.is_none() && price.is_none() {
+            continue;
+        }
+        match (timestamp, price) {
+            (Some(ts), Some(price)) => {
+                let volume = cols.get(2).and_then(|c| c.parse::<f64>().ok()).unwrap_or(0.0);
+                rows.push((ts, price, volume));
+            }
+            (None, _) => warnings.push(format!("{} line {}: unreadable timestamp {:?}", name, line_no, cols[0])),
+            (_, None) => warnings.push(format!("{} line {}: missing or invalid price", name, line_no)),
+        }
+    }
+
+    rows.sort_by_key(|(ts, _, _)| *ts);
+    rows.dedup_by_key(|(ts, _, _)| *ts);
+    if rows.is_empty() {
+        return Err(format!("{} has no valid timestamp,price rows", name).into());
+    }
+
+    let timestamps: Vec<DateTime<Utc>> = rows.iter().map(|r| r.0).collect();
+    let prices: Vec<f64> = rows.iter().map(|r| r.1).collect();
+    let volumes: Vec<f64> = rows.iter().map(|r| r.2).collect();
+
+    let current_price = prices[prices.len() - 1];
+    let first_price   = prices[0];
+    let period_change         = current_price - first_price;
+    let period_change_percent = (period_change / first_price) * 100.0;
+
+    // Same fallback as the chart API: last close of an earlier day in the series
+    let last_day = timestamps[timestamps.len() - 1].date_naive();
+    let base_price = timestamps.iter().zip(prices.iter())
+        .rev()
+        .find(|(ts, _)| ts.date_naive() < last_day)
+        .map_or(first_price, |(_, &p)| p);
+    let change         = current_price - base_price;
+    let change_percent = (change / base_price) * 100.0;
+
+    let data = StockData {
+        symbol: name,
+        timestamps,
+        prices,
+        volumes,
+        current_price,
+        change,
+        change_percent,
+        period_change,
+        period_change_percent,
+        live_ticks: VecDeque::new(),
+        live_current_price: None,
+        base_historical_price: base_price,
+        previous_close: None,
+        market_state: MarketState::Closed,
+        market_hours: None,
+        earnings: None,
+        long_name: None,
+        currency: None,
+        exchange_name: None,
+        exchange_timezone: None,
+        regular_market_price: None,
+        fifty_two_week_high: None,
+        fifty_two_week_low: None,
+        fx: None,
+        fx_error: None,
+        converted: false,
+    };
+    Ok((data, warnings))
+}
+
+/// `^GSPC` → `_GSPC`, `prices.csv` → `prices_csv`: safe to embed in a file name.
+pub fn file_safe_symbol(symbol: &str) -> String {
+    symbol.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '_' })
+        .collect()
+}
+
+/// Write `data` to `charty_SYMBOL_LABEL_YYYYMMDD.csv` in `dir`, adding `_2`, `_3`, …
+/// rather than overwriting an earlier export. Returns the path written.
+pub fn export_csv(
+    data: &StockData,
+    label: &str,
+    dir: &std::path::Path,
+) -> Result<std::path::PathBuf, Box<dyn std::error::Error>> {
+    use std::io::Write;
+
+    let stem = format!("charty_{}_{}_{}", file_safe_symbol(&data.symbol), label, chrono::Local::now().format("%Y%m%d"));
+    let mut path = dir.join(format!("{}.csv", stem));
+    let mut counter = 2;
+    while path.exists() {
+        path = dir.join(format!("{}_{}.csv", stem, counter));
+        counter += 1;
+    }
+
+    let with_volume = data.volumes.iter().any(|&v| v > 0.0);
+    let mut out = String::from(if with_volume { "timestamp,price,volume\n" } else { "timestamp,price\n" });
+    for (i, (ts, price)) in data.timestamps.iter().zip(data.prices.iter()).enumerate() {
+        out.push_str(&format!("{},{}", ts.to_rfc3339_opts(chrono::SecondsFormat::Secs, true), price));
+        if with_volume {
+            out.push_str(&format!(",{}", data.volumes.get(i).copied().unwrap_or(0.0)));
+        }
+        out.push('\n');
+    }
+
+    // create_new so a file appearing between the exists() check and now isn't clobbered
+    let mut file = std::fs::OpenOptions::new().write(true).create_new(true).open(&path)
+        .map_err(|e| format!("Could not create {}: {}", path.display(), e))?;
+    file.write_all(out.as_bytes())?;
+    Ok(path)
+}
+
 // ── Market movers ─────────────────────────────────────────────────────────────
 
 #[derive(Debug, Clone)]
@@ -322,10 +1040,7 @@ pub fn fetch_market_movers(scr_id: &str, count: usize) -> Result<Vec<MarketMover
         scr_id, count
     );
 
-    let response = ureq::get(&url)
-        .set("User-Agent", "Mozilla/5.0")
-        .timeout(std::time::Duration::from_secs(10))
-        .call()?;
+    let response = yahoo_get(&url)?;
     let json: serde_json::Value = response.into_json()?;
 
     let quotes = json["finance"]["result"][0]["quotes"]
@@ -352,40 +1067,295 @@ pub fn fetch_market_movers(scr_id: &str, count: usize) -> Result<Vec<MarketMover
     Ok(movers)
 }
 
+// ── Latest price (polling fallback) ─────────────────────────────────────────
+
+#[derive(Debug, Clone)]
+pub struct LatestPrice {
+    pub price: f64,
+    pub timestamp: i64,
+    pub day_volume: Option<u64>,
+}
+
+/// Most recent regular-market price from the chart meta. Used when no
+/// websocket feed is available.
+pub fn fetch_latest_price(symbol: &str) -> Result<LatestPrice, Box<dyn std::error::Error>> {
+    let url = format!(
+        "https://query1.finance.yahoo.com/v8/finance/chart/{}?interval=1m&range=1d",
+        symbol
+    );
+
+    let response = yahoo_get(&url)?;
+    let json: serde_json::Value = response.into_json()?;
+
+    let meta = &json["chart"]["result"][0]["meta"];
+    let price = meta["regularMarketPrice"].as_f64().ok_or("No price data")?;
+    let timestamp = meta["regularMarketTime"].as_i64().unwrap_or_else(|| Utc::now().timestamp());
+    let day_volume = meta["regularMarketVolume"].as_u64();
+
+    Ok(LatestPrice { price, timestamp, day_volume })
+}
+
+// ── Earnings (Finnhub) ───────────────────────────────────────────────────────
+
+#[derive(Debug, Clone)]
+pub struct EarningsSurprise {
+    pub period: String,
+    pub estimate: Option<f64>,
+    pub actual: Option<f64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct EarningsInfo {
+    pub next_date: Option<chrono::NaiveDate>,
+    /// Most recent quarter first
+    pub history: Vec<EarningsSurprise>,
+}
+
+/// Next earnings date plus the last four quarters of EPS estimate vs actual.
+/// Returns `Ok(None)` for instruments that don't report (ETFs, indices).
+pub fn fetch_earnings(symbol: &str, api_key: &str) -> Result<Option<EarningsInfo>, Box<dyn std::error::Error>> {
+    if symbol.starts_with('^') {
+        return Ok(None);
+    }
+
+    let today = Utc::now().date_naive();
+    let horizon = today + chrono::Duration::days(120);
+    let calendar: serde_json::Value = agent().get("https://finnhub.io/api/v1/calendar/earnings")
+        .query("symbol", symbol)
+        .query("from", &today.format("%Y-%m-%d").to_string())
+        .query("to", &horizon.format("%Y-%m-%d").to_string())
+        .query("token", api_key)
+        .timeout(std::time::Duration::from_secs(10))
+        .call()?
+        .into_json()?;
+
+    let next_date = calendar["earningsCalendar"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e["date"].as_str())
+        .filter_map(|d| chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+        .filter(|d| *d >= today)
+        .min();
+
+    let surprises: serde_json::Value = agent().get("https://finnhub.io/api/v1/stock/earnings")
+        .query("symbol", symbol)
+        .query("limit", "4")
+        .query("token", api_key)
+        .timeout(std::time::Duration::from_secs(10))
+        .call()?
+        .into_json()?;
+
+    let history: Vec<EarningsSurprise> = surprises
+        .as_array()
+        .into_iter()
+        .flatten()
+        .take(4)
+        .map(|q| EarningsSurprise {
+            period: q["period"].as_str().unwrap_or("").to_string(),
+            estimate: q["estimate"].as_f64(),
+            actual: q["actual"].as_f64(),
+        })
+        .collect();
+
+    if next_date.is_none() && history.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(EarningsInfo { next_date, history }))
+}
+
+// ── Company profile (Finnhub) ───────────────────────────────────────────────
+
+#[derive(Debug, Clone)]
+pub struct CompanyProfile {
+    pub name: String,
+    pub industry: String,
+    /// Millions of `currency`
+    pub market_cap: Option<f64>,
+    /// Millions of shares
+    pub shares_outstanding: Option<f64>,
+    pub currency: String,
+    pub ipo: String,
+    pub website: String,
+}
+
+/// Finnhub `/stock/profile2`. Returns `Ok(None)` when Finnhub has no profile for
+/// the symbol (it answers with an empty object rather than an error).
+pub fn fetch_company_profile(symbol: &str, api_key: &str) -> Result<Option<CompanyProfile>, Box<dyn std::error::Error>> {
+    if symbol.starts_with('^') {
+        return Ok(None);
+    }
+
+    let json: serde_json::Value = agent().get("https://finnhub.io/api/v1/stock/profile2")
+        .query("symbol", symbol)
+        .query("token", api_key)
+        .timeout(std::time::Duration::from_secs(10))
+        .call()?
+        .into_json()?;
+
+    let Some(name) = json["name"].as_str().filter(|s| !s.is_empty()) else {
+        return Ok(None);
+    };
+    let text = |key: &str| json[key].as_str().unwrap_or("").to_string();
+
+    Ok(Some(CompanyProfile {
+        name: name.to_string(),
+        industry: text("finnhubIndustry"),
+        market_cap: json["marketCapitalization"].as_f64(),
+        shares_outstanding: json["shareOutstanding"].as_f64(),
+        currency: text("currency"),
+        ipo: text("ipo"),
+        website: text("weburl"),
+    }))
+}
+
+/// The best bid and offer at last look.
+#[derive(Debug, Clone, Copy)]
+pub struct BidAsk {
+    pub bid: f64,
+    pub ask: f64,
+}
+
+/// Finnhub `/stock/bidask`; its `/quote` carries no bid or ask. `Ok(None)`
+/// when there's no two-sided quote (a halted or closed book answers with
+/// zeros). Plans without access get a 401/403, which the caller treats as
+/// the endpoint being unavailable.
+pub fn fetch_bid_ask(symbol: &str, api_key: &str) -> Result<Option<BidAsk>, Box<dyn std::error::Error>> {
+    let json: serde_json::Value = agent().get("https://finnhub.io/api/v1/stock/bidask")
+        .query("symbol", symbol)
+        .query("token", api_key)
+        .timeout(std::time::Duration::from_secs(5))
+        .call()?
+        .into_json()?;
+
+    Ok(match (json["b"].as_f64(), json["a"].as_f64()) {
+        (Some(bid), Some(ask)) if bid > 0.0 && ask >= bid => Some(BidAsk { bid, ask }),
+        _ => None,
+    })
+}
+
+/// Whether a fetch failed because the key's plan doesn't include the endpoint.
+pub fn is_access_denied(error: &str) -> bool {
+    error.contains("status code 401") || error.contains("status code 403")
+}
+
+// ── Symbol search ───────────────────────────────────────────────────────────
+
+/// `(symbol, name, exchange)` for one search hit.
+pub type SymbolMatch = (String, String, String);
+
+/// Look up tickers matching a free-form query.
+pub fn search_symbols(query: &str) -> Result<Vec<SymbolMatch>, Box<dyn std::error::Error>> {
+    let response = agent().get("https://query2.finance.yahoo.com/v1/finance/search")
+        .query("q", query)
+        .query("quotesCount", "8")
+        .query("newsCount", "0")
+        .set("User-Agent", "Mozilla/5.0")
+        .timeout(std::time::Duration::from_secs(5))
+        .call()?;
+    let json: serde_json::Value = response.into_json()?;
+
+    let quotes = json["quotes"].as_array().ok_or("No search results")?;
+
+    let results = quotes
+        .iter()
+        .filter_map(|q| {
+            let symbol = q["symbol"].as_str()?.to_string();
+            let name = q["shortname"].as_str()
+                .or_else(|| q["longname"].as_str())
+                .unwrap_or("")
+                .to_string();
+            let exchange = q["exchDisp"].as_str()
+                .or_else(|| q["exchange"].as_str())
+                .unwrap_or("")
+                .to_string();
+            Some((symbol, name, exchange))
+        })
+        .collect();
+
+    Ok(results)
+}
+
 // ── Historical candles (Yahoo Finance v8) ────────────────────────────────────
 
+/// Longest window Yahoo serves in a single intraday chart request.
+fn max_request_span_secs(interval: &str) -> i64 {
+    const DAY: i64 = 86_400;
+    match interval {
+        "1m" => 7 * DAY,
+        "2m" | "5m" | "15m" | "30m" | "90m" => 59 * DAY,
+        _ => 729 * DAY,
+    }
+}
+
+/// Start of a window ending at `to` that should hold at least `count` regular
+/// session bars of `interval_secs`, counting only weekdays. One spare weekday is
+/// added so a market holiday doesn't leave the window short.
+pub fn market_hours_lookback(to: DateTime<Utc>, interval_secs: i64, count: usize) -> DateTime<Utc> {
+    use chrono::{Datelike, Duration, Weekday};
+
+    const SESSION_SECS: i64 = 23_400; // 6.5h regular session
+    let bars_per_day = (SESSION_SECS / interval_secs.max(1)).max(1) as usize;
+    let weekdays_needed = count.div_ceil(bars_per_day) + 1;
+
+    let mut from = to;
+    let mut weekdays = 0;
+    while weekdays < weekdays_needed {
+        from -= Duration::days(1);
+        if !matches!(from.weekday(), Weekday::Sat | Weekday::Sun) {
+            weekdays += 1;
+        }
+    }
+    from
+}
+
+/// Fetch OHLC candles for `[from, to)`. Windows longer than Yahoo's per-request
+/// limit for the interval are split into several requests and stitched together.
 pub fn fetch_historical_candles(
     symbol: &str,
     interval: &str,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
 ) -> Result<Vec<crate::ui::Candlestick>, Box<dyn std::error::Error>> {
-    use crate::ui::Candlestick;
+    let span = max_request_span_secs(interval);
+    let end = to.timestamp();
+    let mut page_start = from.timestamp();
+    let mut candles = Vec::new();
 
-    // Pick a range wide enough to yield ~60+ candles per interval
-    let range = match interval {
-        "1m" => "1d",
-        "5m" => "1d",
-        "15m" => "5d",
-        "30m" => "5d",
-        "1h" => "1mo",
-        _ => "1d",
-    };
+    while page_start < end {
+        let page_end = (page_start + span).min(end);
+        candles.extend(fetch_candle_page(symbol, interval, page_start, page_end)?);
+        page_start = page_end;
+    }
+
+    // Adjacent pages can overlap at the boundary bar
+    candles.sort_by_key(|c| c.timestamp);
+    candles.dedup_by_key(|c| c.timestamp);
+    Ok(candles)
+}
+
+fn fetch_candle_page(
+    symbol: &str,
+    interval: &str,
+    period1: i64,
+    period2: i64,
+) -> Result<Vec<crate::ui::Candlestick>, Box<dyn std::error::Error>> {
+    use crate::ui::Candlestick;
 
     let url = format!(
-        "https://query1.finance.yahoo.com/v8/finance/chart/{}?interval={}&range={}&includePrePost=false",
-        symbol, interval, range
+        "https://query1.finance.yahoo.com/v8/finance/chart/{}?interval={}&period1={}&period2={}&includePrePost=false",
+        symbol, interval, period1, period2
     );
 
-    let response = ureq::get(&url)
-        .set("User-Agent", "Mozilla/5.0")
-        .timeout(std::time::Duration::from_secs(10))
-        .call()?;
+    let response = yahoo_get(&url)?;
     let json: serde_json::Value = response.into_json()?;
 
     let chart = &json["chart"]["result"][0];
 
-    let timestamps = chart["timestamp"]
-        .as_array()
-        .ok_or("No timestamp data")?;
+    // A window with no trading (e.g. a weekend page) has no timestamp array
+    let Some(timestamps) = chart["timestamp"].as_array() else {
+        return Ok(Vec::new());
+    };
 
     let quote = &chart["indicators"]["quote"][0];
     let opens   = quote["open"].as_array().ok_or("No open data")?;
@@ -420,3 +1390,228 @@ pub fn fetch_historical_candles(
 
     Ok(candles)
 }
+
+/// The last daily candle from a session before today's, with days counted in
+/// exchange time (`gmt_offset` seconds east of UTC) so an evening in Asia or
+/// a late session in New York doesn't roll over to the wrong date.
+pub fn previous_session(
+    daily: &[crate::ui::Candlestick],
+    now: DateTime<Utc>,
+    gmt_offset: i64,
+) -> Option<&crate::ui::Candlestick> {
+    let exchange_day = |t: DateTime<Utc>| (t.timestamp() + gmt_offset).div_euclid(86_400);
+    let today = exchange_day(now);
+    daily.iter().rev().find(|c| exchange_day(c.timestamp) < today)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::time::{Duration, Instant};
+
+    /// A local server that accepts one connection, reads the request and
+    /// answers with `response` after `delay`, or never with `None`.
+    fn slow_server(delay: Duration, response: Option<&'static str>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = [0u8; 1024];
+            let _ = stream.read(&mut request);
+            std::thread::sleep(delay);
+            if let Some(response) = response {
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+        format!("http://{}/v8/finance/chart/AAPL", addr)
+    }
+
+    #[test]
+    fn stalled_server_times_out() {
+        let timeout = Duration::from_millis(300);
+        let url = slow_server(Duration::from_secs(5), None);
+        let agent = builder(timeout, None).build();
+
+        let started = Instant::now();
+        let err = get_with(&agent, &url).unwrap_err().to_string();
+        let elapsed = started.elapsed();
+        assert!(err.starts_with(TIMEOUT_MESSAGE), "{}", err);
+        assert!(err.contains("127.0.0.1:"), "names the host: {}", err);
+        assert!(elapsed >= timeout && elapsed < timeout + Duration::from_secs(1), "{:?}", elapsed);
+    }
+
+    #[test]
+    fn slow_answer_within_the_timeout_arrives() {
+        let url = slow_server(Duration::from_millis(100), Some("HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\n{}"));
+        let agent = builder(Duration::from_secs(2), None).build();
+
+        let response = get_with(&agent, &url).unwrap();
+        assert_eq!(response.into_string().unwrap(), "{}");
+    }
+
+    /// Monday 2023-11-13 on the NYSE: pre-market from 04:00 ET, the regular
+    /// session 09:30–16:00 and after hours until 20:00, at UTC−5.
+    const MONDAY: i64 = 1_699_833_600;
+    const HOUR: i64 = 3_600;
+    const DAY: i64 = 86_400;
+
+    fn nyse_hours() -> MarketHours {
+        MarketHours {
+            session_start: MONDAY + 9 * HOUR,
+            session_end: MONDAY + 25 * HOUR,
+            regular_start: MONDAY + 14 * HOUR + HOUR / 2,
+            regular_end: MONDAY + 21 * HOUR,
+            gmt_offset: -5 * HOUR,
+            always_open: false,
+        }
+    }
+
+    fn crypto_hours() -> MarketHours {
+        MarketHours { always_open: true, ..nyse_hours() }
+    }
+
+    #[test]
+    fn state_follows_the_session_bounds() {
+        let hours = nyse_hours();
+        assert_eq!(hours.state_at(MONDAY + 8 * HOUR), MarketState::Closed);
+        assert_eq!(hours.state_at(MONDAY + 10 * HOUR), MarketState::Pre);
+        assert_eq!(hours.state_at(hours.regular_start), MarketState::Regular);
+        assert_eq!(hours.state_at(MONDAY + 20 * HOUR), MarketState::Regular);
+        assert_eq!(hours.state_at(hours.regular_end), MarketState::Post);
+        assert_eq!(hours.state_at(hours.session_end), MarketState::Closed);
+        assert_eq!(crypto_hours().state_at(MONDAY), MarketState::Regular);
+    }
+
+    #[test]
+    fn is_trading_at_repeats_on_every_weekday() {
+        let hours = nyse_hours();
+        // 10:00 ET on the Monday, and on the Wednesday after these hours went stale
+        assert!(hours.is_trading_at(MONDAY + 15 * HOUR));
+        assert!(hours.is_trading_at(MONDAY + 2 * DAY + 15 * HOUR));
+        assert!(!hours.is_open_at(MONDAY + 2 * DAY + 15 * HOUR));
+        // 03:00 ET is before pre-market
+        assert!(!hours.is_trading_at(MONDAY + 8 * HOUR));
+        // Friday 19:30 ET is after hours, though it's Saturday in UTC
+        assert!(hours.is_trading_at(MONDAY + 4 * DAY + 24 * HOUR + HOUR / 2));
+        // Nothing on Saturday or Sunday
+        assert!(!hours.is_trading_at(MONDAY + 5 * DAY + 15 * HOUR));
+        assert!(!hours.is_trading_at(MONDAY + 6 * DAY + 15 * HOUR));
+        assert!(crypto_hours().is_trading_at(MONDAY + 5 * DAY + 15 * HOUR));
+    }
+
+    #[test]
+    fn next_open_skips_the_weekend() {
+        let hours = nyse_hours();
+        let open = hours.regular_start;
+        assert_eq!(hours.next_weekday_time(open, open - 1), Some(open));
+        // Once it's open, the next one is tomorrow's
+        assert_eq!(hours.next_weekday_time(open, open), Some(open + DAY));
+        // After Friday's close comes Monday's open
+        assert_eq!(hours.next_open_after(MONDAY + 4 * DAY + 21 * HOUR), Some(open + 7 * DAY));
+        assert_eq!(hours.next_open_after(MONDAY + 5 * DAY), Some(open + 7 * DAY));
+        // However stale the hours, the answer is the next one after `now`
+        assert_eq!(hours.next_open_after(open + 30 * DAY), Some(open + 31 * DAY));
+        assert_eq!(hours.next_session_after(MONDAY + 4 * DAY + 22 * HOUR), Some(hours.session_start + 7 * DAY));
+        assert_eq!(crypto_hours().next_open_after(MONDAY), None);
+        assert_eq!(crypto_hours().next_session_after(MONDAY), None);
+    }
+
+    /// Write `contents` to a fresh file under the temp dir and return its path.
+    fn csv_file(name: &str, contents: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("charty-csv-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn csv_rows_load_in_time_order() {
+        let path = csv_file("valid.csv", "timestamp,price,volume\n\
+            2024-03-05 14:31,101.5,300\n\
+            1709649000,100.25,200\n\
+            \n\
+            \"2024-03-05T14:32:00Z\",102,400\n");
+        let (data, warnings) = load_from_csv(&path).unwrap();
+
+        assert!(warnings.is_empty(), "{:?}", warnings);
+        assert_eq!(data.symbol, "valid.csv");
+        assert_eq!(data.prices, vec![100.25, 101.5, 102.0]);
+        assert_eq!(data.volumes, vec![200.0, 300.0, 400.0]);
+        assert_eq!(data.timestamps[0].timestamp(), 1_709_649_000);
+        assert_eq!(data.current_price, 102.0);
+        assert_eq!(data.period_change, 102.0 - 100.25);
+    }
+
+    #[test]
+    fn csv_volume_column_is_optional() {
+        let path = csv_file("no-volume.csv", "2024-03-05,10\n2024-03-06,11,\n2024-03-07,12,n/a\n");
+        let (data, warnings) = load_from_csv(&path).unwrap();
+
+        assert!(warnings.is_empty(), "{:?}", warnings);
+        assert_eq!(data.prices, vec![10.0, 11.0, 12.0]);
+        assert_eq!(data.volumes, vec![0.0, 0.0, 0.0]);
+        // The day before the last row is the reference close
+        assert_eq!(data.change, 1.0);
+    }
+
+    #[test]
+    fn csv_bad_rows_are_reported_by_line() {
+        let path = csv_file("bad.csv", "time,price\n\
+            2024-03-05 14:30,100\n\
+            yesterday,101\n\
+            2024-03-05 14:32,\n\
+            2024-03-05 14:33,NaN\n\
+            2024-03-05 14:34,104\n");
+        let (data, warnings) = load_from_csv(&path).unwrap();
+
+        assert_eq!(data.prices, vec![100.0, 104.0]);
+        assert_eq!(warnings, vec![
+            "bad.csv line 3: unreadable timestamp \"yesterday\"".to_string(),
+            "bad.csv line 4: missing or invalid price".to_string(),
+            "bad.csv line 5: missing or invalid price".to_string(),
+        ]);
+    }
+
+    #[test]
+    fn csv_without_rows_is_an_error() {
+        for (name, contents) in [("empty.csv", ""), ("header.csv", "timestamp,price,volume\n")] {
+            let err = load_from_csv(&csv_file(name, contents)).unwrap_err();
+            assert_eq!(err.to_string(), format!("{} has no valid timestamp,price rows", name));
+        }
+        let missing = std::env::temp_dir().join("charty-csv-missing/none.csv");
+        assert!(load_from_csv(&missing).unwrap_err().to_string().starts_with("Could not read"));
+    }
+
+    #[test]
+    fn fx_rate_is_reused_until_the_ttl_passes() {
+        use std::cell::Cell;
+
+        let fetches = Cell::new(0);
+        let fetch = |rate: f64| {
+            let fetches = &fetches;
+            move |_: &str, _: &str| -> Result<f64, Box<dyn std::error::Error>> {
+                fetches.set(fetches.get() + 1);
+                Ok(rate)
+            }
+        };
+        let start = Instant::now();
+        assert_eq!(fx_rate_at("TSA", "TSB", start, fetch(1.25)).unwrap(), 1.25);
+        assert_eq!(fx_rate_at("TSA", "TSB", start + FX_CACHE_TTL / 2, fetch(9.0)).unwrap(), 1.25);
+        assert_eq!(fetches.get(), 1);
+        // Another pair has its own entry
+        assert_eq!(fx_rate_at("TSB", "TSA", start, fetch(0.8)).unwrap(), 0.8);
+        assert_eq!(fx_rate_at("TSA", "TSB", start + FX_CACHE_TTL, fetch(1.5)).unwrap(), 1.5);
+        assert_eq!(fetches.get(), 3);
+    }
+
+    #[test]
+    fn failed_fx_rate_is_retried() {
+        let start = Instant::now();
+        let err = fx_rate_at("TSC", "TSD", start, |_, _| Err("offline".into())).unwrap_err();
+        assert_eq!(err.to_string(), "offline");
+        assert_eq!(fx_rate_at("TSC", "TSD", start, |_, _| Ok(2.0)).unwrap(), 2.0);
+    }
+}
@@ -6,67 +6,1191 @@ use ratatui::{
 	text::{Line, Span},
 	Frame,
 };
-use chrono::{DateTime, Utc, Local};
-
-use super::{App, Candlestick, nav_key};
-use crate::stock::{TimeFrame, MarketState};
+use chrono::{DateTime, Utc};
+
+use super::{App, Candlestick, DisplayZone, IndicatorPane, NARROW_WIDTH, ZOOM_LEVELS, footer_rows, format_price, nav_key, sign_prefix};
+use super::live::render_trade_feed;
+use crate::indicators::{self, CandlePattern, PivotLevels};
+use crate::config::DisplayMode;
+use crate::stock::{StockData, TimeFrame, MarketState, MarketHours};
+
+/// Pre- and post-market parts of the 1D line and trade tape.
+pub(super) const EXTENDED_HOURS_COLOR: Color = Color::DarkGray;
+/// Rows given to each indicator pane under the chart.
+pub(super) const PANE_HEIGHT: u16 = 7;
+/// Smallest price chart worth keeping when panes compete for space.
+pub(super) const MIN_CHART_HEIGHT: u16 = 10;
+/// Rows given to the volume pane, bottom border included.
+pub(super) const VOLUME_HEIGHT: u16 = 6;
 
 pub fn render_chart_view(f: &mut Frame, app: &App) {
-    let show_vol = app.show_volume && app.stock_data.is_some();
+    // Full screen drops the header and footer; the chart title carries the price
+    let (header_height, footer_height) = if app.full_screen { (0, 0) } else { (3, footer_rows(f, 5)) };
+    // The volume pane is the first thing dropped on a short terminal
+    let show_vol = app.show_volume
+        && app.stock_data.is_some()
+        && f.area().height >= header_height + footer_height + MIN_CHART_HEIGHT + VOLUME_HEIGHT;
+    let panes = visible_panes(app, f.area().height.saturating_sub(header_height + footer_height), show_vol);
+
+    let outer = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(header_height), Constraint::Min(0), Constraint::Length(footer_height)])
+        .split(f.area());
+    if !app.full_screen {
+        render_header(f, app, outer[0]);
+        render_footer(f, app, outer[2]);
+    }
 
-    let constraints = if show_vol {
-        vec![
-            Constraint::Length(3),
-            Constraint::Min(10),
-            Constraint::Length(7),
-            Constraint::Length(5),
-        ]
-    } else {
-        vec![
-            Constraint::Length(3),
-            Constraint::Min(0),
-            Constraint::Length(5),
-        ]
-    };
+    // Trades take the right 30% while streaming, unless that would squeeze
+    // the chart
+    let mut body = outer[1];
+    if app.show_trade_feed && app.live_updates_enabled && f.area().width >= TRADE_FEED_MIN_WIDTH {
+        let split = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(70), Constraint::Percentage(30)])
+            .split(body);
+        body = split[0];
+        render_trade_feed(f, app, split[1], TRADE_FEED_LEN);
+    }
+
+    let mut constraints = vec![Constraint::Min(if show_vol || !panes.is_empty() { MIN_CHART_HEIGHT } else { 0 })];
+    if show_vol {
+        constraints.push(Constraint::Length(VOLUME_HEIGHT));
+    }
+    constraints.extend(panes.iter().map(|_| Constraint::Length(PANE_HEIGHT)));
 
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints(constraints)
-        .split(f.area());
+        .split(body);
 
-    render_header(f, app, chunks[0]);
-    render_chart(f, app, chunks[1]);
+    render_chart(f, app, chunks[0]);
+    // Mirror ratatui's internal graph_area.left() calculation so bars align exactly.
+    let offset = graph_left_offset(app, chunks[0]);
+    let mut next = 1;
     if show_vol {
-        // Mirror ratatui's internal graph_area.left() calculation so bars align exactly.
-        let offset = graph_left_offset(app, chunks[1]);
-        render_volume_bars(f, app, chunks[2], offset);
-        render_footer(f, app, chunks[3]);
+        if app.show_candlesticks {
+            render_historical_candle_volume(f, app, chunks[0], chunks[next]);
+        } else {
+            render_volume_bars(f, app, chunks[next], offset);
+        }
+        next += 1;
+    }
+    for &pane in &panes {
+        render_indicator_pane(f, app, chunks[next], pane, offset);
+        next += 1;
+    }
+}
+
+/// Narrowest terminal that gets the trade feed beside the chart.
+const TRADE_FEED_MIN_WIDTH: u16 = 100;
+/// Trades listed beside the chart.
+const TRADE_FEED_LEN: usize = 15;
+
+/// The enabled panes that fit in `height` rows below the chart, oldest first.
+/// When there isn't room for all of them the most recently toggled ones win.
+fn visible_panes(app: &App, height: u16, show_vol: bool) -> Vec<IndicatorPane> {
+    if app.stock_data.is_none() {
+        return Vec::new();
+    }
+    let fixed = MIN_CHART_HEIGHT + if show_vol { VOLUME_HEIGHT } else { 0 };
+    let room = (height.saturating_sub(fixed) / PANE_HEIGHT) as usize;
+    let skip = app.indicator_panes.len().saturating_sub(room);
+    app.indicator_panes[skip..].to_vec()
+}
+
+/// x-axis bounds of the bars on screen at the current zoom, shared by the
+/// line chart and the panes under it.
+fn visible_x_bounds(app: &App) -> [f64; 2] {
+    let range = app.visible_range();
+    let lo = range.start as f64;
+    let hi = live_segment(app).last().map_or((range.end.saturating_sub(1)) as f64, |&(x, _)| x);
+    [lo, hi.max(lo + 1.0)]
+}
+
+/// Bar index → x position for the line chart and the panes under it. With
+/// session gaps on, each session after an overnight break moves right by
+/// `gap`, opening a blank column or two between trading days.
+pub(super) struct XMap {
+    /// Bar indices that start a new session, ascending
+    breaks: Vec<usize>,
+    gap: f64,
+}
+
+impl XMap {
+    /// Plain index positions.
+    pub fn identity() -> Self {
+        XMap { breaks: Vec::new(), gap: 0.0 }
+    }
+
+    pub fn x(&self, index: f64) -> f64 {
+        index + self.gap * self.breaks.partition_point(|&b| b as f64 <= index) as f64
+    }
+
+    pub fn points(&self, points: &[(f64, f64)]) -> Vec<(f64, f64)> {
+        points.iter().map(|&(x, y)| (self.x(x), y)).collect()
+    }
+
+    pub fn bounds(&self, [lo, hi]: [f64; 2]) -> [f64; 2] {
+        [self.x(lo), self.x(hi)]
+    }
+
+    /// Sessions that end before `x`: the k-th break's session starts at
+    /// `break + k * gap`, and its predecessor ends one bar earlier.
+    fn sessions_before(&self, x: f64) -> usize {
+        self.breaks.iter()
+            .enumerate()
+            .take_while(|&(k, &b)| x > b as f64 - 1.0 + k as f64 * self.gap)
+            .count()
+    }
+
+    /// Inverse of `x`. Positions inside a gap map to the session after it.
+    pub fn index(&self, x: f64) -> f64 {
+        match self.sessions_before(x) {
+            0 => x,
+            k => (x - k as f64 * self.gap).max(self.breaks[k - 1] as f64),
+        }
+    }
+
+    /// Whether `x` falls in the blank space between two sessions.
+    pub fn in_gap(&self, x: f64) -> bool {
+        match self.sessions_before(x) {
+            0 => false,
+            k => x < self.breaks[k - 1] as f64 + k as f64 * self.gap,
+        }
+    }
+
+    /// Where the divider for the session starting at bar `index` goes: the
+    /// middle of its gap, or the bar itself without gaps.
+    fn divider_x(&self, index: usize) -> f64 {
+        self.x(index as f64) - self.gap / 2.0
+    }
+}
+
+/// The x mapping for a plot `width` cells wide. Session gaps of
+/// `session_gap` columns apply to line charts of intraday bars spanning
+/// several sessions, while they leave most of the width to the bars.
+fn x_map(app: &App, width: u16) -> XMap {
+    let Some(ref data) = app.stock_data else { return XMap::identity(); };
+    if !app.session_gaps || app.show_candlesticks || app.compare_data.is_some() {
+        return XMap::identity();
+    }
+    let range = visible_slice(app, data);
+    let timestamps = &data.timestamps[range.start.min(data.timestamps.len())..range.end.min(data.timestamps.len())];
+    let breaks: Vec<usize> = session_breaks(timestamps, app.display_zone()).into_iter().map(|i| range.start + i).collect();
+    let cols = app.session_gap as f64;
+    // Solve gap * width / (bars + breaks * gap) = cols for the gap in bars
+    let room = width as f64 - cols * breaks.len() as f64;
+    if breaks.is_empty() || room < width as f64 / 2.0 {
+        return XMap::identity();
+    }
+    let bars = range.len().saturating_sub(1).max(1) as f64;
+    XMap { gap: cols * bars / room, breaks }
+}
+
+/// Index bounds of the visible bars and the mapping for a pane in `area`
+/// whose plot starts `left_offset` cells in, as x bounds for the chart.
+fn pane_x(app: &App, area: Rect, left_offset: u16) -> (XMap, [f64; 2], [f64; 2]) {
+    let xmap = x_map(app, area.width.saturating_sub(2 + left_offset));
+    let index_bounds = visible_x_bounds(app);
+    let x_bounds = xmap.bounds(index_bounds);
+    (xmap, index_bounds, x_bounds)
+}
+
+/// Typical spacing between bars in seconds, from the most recent ones.
+fn bar_spacing(timestamps: &[DateTime<Utc>]) -> Option<f64> {
+    let mut gaps: Vec<i64> = timestamps.windows(2)
+        .rev()
+        .take(20)
+        .map(|w| (w[1] - w[0]).num_seconds())
+        .filter(|&gap| gap > 0)
+        .collect();
+    gaps.sort_unstable();
+    gaps.get(gaps.len() / 2).map(|&gap| gap as f64)
+}
+
+/// Live ticks after the last historical bar while the chart is streaming,
+/// placed at fractional bar positions by their time and led by the last
+/// close so the segment joins the line. Empty when not streaming, or when
+/// the latest bar is scrolled out of view.
+fn live_segment(app: &App) -> Vec<(f64, f64)> {
+    let Some(ref data) = app.stock_data else { return Vec::new(); };
+    if !app.live_updates_enabled
+        || !matches!(app.state, super::AppState::Chart)
+        || app.show_candlesticks
+        || app.compare_data.is_some()
+        || app.visible_range().end < data.prices.len()
+    {
+        return Vec::new();
+    }
+    let (Some(&last_ts), Some(&last_price), Some(step)) =
+        (data.timestamps.last(), data.prices.last(), bar_spacing(&data.timestamps))
+    else {
+        return Vec::new();
+    };
+    let last_x = (data.prices.len() - 1) as f64;
+    let mut points = vec![(last_x, last_price)];
+    points.extend(data.live_ticks.iter()
+        .filter(|t| t.timestamp > last_ts)
+        .map(|t| (last_x + (t.timestamp - last_ts).num_milliseconds() as f64 / 1000.0 / step, t.price)));
+    if points.len() < 2 { Vec::new() } else { points }
+}
+
+/// Column of bar `x` in a plot `width` cells wide, `None` when it's off screen.
+fn x_to_col(x: f64, x_bounds: [f64; 2], width: u16) -> Option<u16> {
+    let [lo, hi] = x_bounds;
+    if x < lo || x > hi || width < 2 {
+        return None;
+    }
+    Some(((x - lo) / (hi - lo) * (width - 1) as f64).round() as u16)
+}
+
+/// The part of an index-ordered series inside `x_bounds`.
+pub(super) fn visible_points(points: &[(f64, f64)], x_bounds: [f64; 2]) -> &[(f64, f64)] {
+    let start = points.partition_point(|&(x, _)| x < x_bounds[0]);
+    let end = points.partition_point(|&(x, _)| x <= x_bounds[1]).max(start);
+    &points[start..end]
+}
+
+fn render_indicator_pane(f: &mut Frame, app: &App, area: Rect, pane: IndicatorPane, left_offset: u16) {
+    match pane {
+        IndicatorPane::Rsi => render_rsi_pane(f, app, area, left_offset),
+        IndicatorPane::Macd => render_macd_pane(f, app, area, left_offset),
+        IndicatorPane::Obv => render_obv_pane(f, app, area, left_offset),
+        IndicatorPane::Volatility => render_volatility_pane(f, app, area, left_offset),
+        IndicatorPane::Stochastic => {
+            let (xmap, index_bounds, x_bounds) = pane_x(app, area, left_offset);
+            let stoch = &app.indicator_values.stochastic;
+            let (k, d) = (xmap.points(visible_points(&stoch.k, index_bounds)), xmap.points(visible_points(&stoch.d, index_bounds)));
+            render_stochastic_pane(f, area, stoch, &k, &d, app.stochastic_periods, x_bounds, left_offset);
+        }
+    }
+}
+
+/// 1.2B / 3.4M / 5.6K with the sign kept, for signed running totals like OBV.
+fn compact_number(value: f64) -> String {
+    let sign = if value < 0.0 { "-" } else { "" };
+    let v = value.abs();
+    if v >= 1e9 {
+        format!("{}{:.1}B", sign, v / 1e9)
+    } else if v >= 1e6 {
+        format!("{}{:.1}M", sign, v / 1e6)
+    } else if v >= 1e3 {
+        format!("{}{:.1}K", sign, v / 1e3)
+    } else {
+        format!("{}{:.0}", sign, v)
+    }
+}
+
+const OBV_TREND_PERIOD: usize = 20;
+
+/// On-balance volume with its 20-bar SMA; the title says which side of the
+/// average OBV is on.
+fn render_obv_pane(f: &mut Frame, app: &App, area: Rect, left_offset: u16) {
+    let points = &app.indicator_values.obv;
+    let values: Vec<f64> = points.iter().map(|&(_, v)| v).collect();
+    let average = indicators::sma(&values, OBV_TREND_PERIOD);
+    let (xmap, index_bounds, x_bounds) = pane_x(app, area, left_offset);
+    let shown = xmap.points(visible_points(points, index_bounds));
+    let shown_average = xmap.points(visible_points(&average, index_bounds));
+
+    let mut title = vec![Span::raw("OBV ")];
+    match (points.last(), average.last()) {
+        _ if values.iter().all(|&v| v == 0.0) => {
+            title.push(Span::styled("no volume data", Style::default().fg(Color::DarkGray)));
+        }
+        (Some(&(_, obv)), Some(&(_, avg))) => {
+            let (trend, color) = if obv >= avg { ("rising", Color::Green) } else { ("falling", Color::Red) };
+            title.push(Span::styled(compact_number(obv), Style::default().fg(Color::White).add_modifier(Modifier::BOLD)));
+            title.push(Span::styled(format!(" {} vs SMA{}", trend, OBV_TREND_PERIOD), Style::default().fg(color)));
+        }
+        (Some(&(_, obv)), None) => {
+            title.push(Span::styled(compact_number(obv), Style::default().fg(Color::White).add_modifier(Modifier::BOLD)));
+        }
+        _ => title.push(Span::styled("needs more data", Style::default().fg(Color::DarkGray))),
+    }
+
+    let lo = shown.iter().map(|&(_, v)| v).fold(f64::INFINITY, f64::min);
+    let hi = shown.iter().map(|&(_, v)| v).fold(f64::NEG_INFINITY, f64::max);
+    let (lo, hi) = if lo.is_finite() && hi > lo { (lo, hi) } else { (-1.0, 1.0) };
+    let datasets = vec![
+        Dataset::default()
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::DarkGray))
+            .data(&shown_average),
+        Dataset::default()
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Cyan))
+            .data(&shown),
+    ];
+    let labels = [compact_number(lo), compact_number(hi)];
+    render_pane_chart(f, area, Line::from(title), datasets, x_bounds, [lo, hi], labels, left_offset);
+}
+
+fn render_volatility_pane(f: &mut Frame, app: &App, area: Rect, left_offset: u16) {
+    let points = &app.indicator_values.volatility;
+    let (xmap, index_bounds, x_bounds) = pane_x(app, area, left_offset);
+    let shown = xmap.points(visible_points(points, index_bounds));
+
+    let mut title = vec![Span::raw(format!("Realized vol {} ", app.volatility_period))];
+    match points.last() {
+        Some(&(_, vol)) => title.push(Span::styled(
+            format!("{:.1}%", vol),
+            Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+        )),
+        None => title.push(Span::styled("needs more data", Style::default().fg(Color::DarkGray))),
+    }
+
+    let hi = shown.iter().map(|&(_, v)| v).fold(0.0, f64::max);
+    let hi = if hi > 0.0 { hi * 1.1 } else { 1.0 };
+    let datasets = vec![
+        Dataset::default()
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Magenta))
+            .data(&shown),
+    ];
+    let labels = ["0%".to_string(), format!("{:.0}%", hi)];
+    render_pane_chart(f, area, Line::from(title), datasets, x_bounds, [0.0, hi], labels, left_offset);
+}
+
+const COMPARE_COLOR: Color = Color::Yellow;
+
+/// Both symbols rebased to 100 on their common timestamps. Returns false when
+/// they share none, so the caller can fall back to the normal chart.
+fn render_compare_chart(f: &mut Frame, app: &App, area: Rect, main: &crate::stock::StockData, other: &crate::stock::StockData) -> bool {
+    // Daily-or-longer bars open at exchange-specific times; match those by date
+    let by_date = !app.active_interval().ends_with('m') && !app.active_interval().ends_with('h');
+    let points = crate::analytics::rebase_common(
+        (&main.timestamps, &main.prices),
+        (&other.timestamps, &other.prices),
+        by_date,
+    );
+    if points.len() < 2 {
+        return false;
+    }
+
+    let max_x = (points.len() - 1) as f64;
+    let base_data: Vec<(f64, f64)> = points.iter().enumerate().map(|(i, p)| (i as f64, p.base)).collect();
+    let other_data: Vec<(f64, f64)> = points.iter().enumerate().map(|(i, p)| (i as f64, p.other)).collect();
+    let base_data = downsample(base_data, area.width as usize * 2);
+    let other_data = downsample(other_data, area.width as usize * 2);
+
+    let lo = points.iter().map(|p| p.base.min(p.other)).fold(f64::INFINITY, f64::min);
+    let hi = points.iter().map(|p| p.base.max(p.other)).fold(f64::NEG_INFINITY, f64::max);
+    let padding = ((hi - lo) * 0.05).max(0.5);
+    let y_bounds = [lo - padding, hi + padding];
+
+    let last = points[points.len() - 1];
+    let legend = |symbol: &str, value: f64| format!("{} {:+.2}%", symbol, value - 100.0);
+    let base_name = legend(&main.symbol, last.base);
+    let other_name = legend(&other.symbol, last.other);
+    let main_color = if last.base >= last.other { Color::Green } else { Color::Red };
+
+    let datasets = vec![
+        Dataset::default()
+            .name(base_name.as_str())
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(main_color))
+            .data(&base_data),
+        Dataset::default()
+            .name(other_name.as_str())
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(COMPARE_COLOR))
+            .data(&other_data),
+    ];
+
+    let timestamps: Vec<DateTime<Utc>> = points.iter().map(|p| p.timestamp).collect();
+    let x_labels = x_axis_labels(&timestamps, &app.timeframe, app.display_zone(), x_label_room(f, area.width.saturating_sub(2)), &[], &XMap::identity(), 0);
+    let y_labels = vec![
+        Span::raw(format!("{:.1}", lo)),
+        Span::raw(format!("{:.1}", (lo + hi) / 2.0)),
+        Span::raw(format!("{:.1}", hi)),
+    ];
+
+    let title = format!(
+        "{} vs {} - {} ({}) · rebased to 100 · 'c' to clear",
+        main.symbol, other.symbol, app.timeframe.display(), app.active_interval()
+    );
+    let chart = Chart::new(datasets)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .x_axis(
+            Axis::default()
+                .style(Style::default().fg(Color::Gray))
+                .bounds([0.0, max_x])
+                .labels(x_labels),
+        )
+        .y_axis(
+            Axis::default()
+                .style(Style::default().fg(Color::Gray))
+                .bounds(y_bounds)
+                .labels(y_labels),
+        );
+    f.render_widget(chart, area);
+    true
+}
+
+/// Prompt for the symbol to compare against.
+pub fn render_compare_input(f: &mut Frame, app: &App) {
+    use ratatui::widgets::Clear;
+
+    let area = f.area();
+    let popup_width = 40u16.min(area.width);
+    let popup_height = 5u16.min(area.height);
+    let popup_area = Rect {
+        x: (area.width.saturating_sub(popup_width)) / 2,
+        y: (area.height.saturating_sub(popup_height)) / 2,
+        width: popup_width,
+        height: popup_height,
+    };
+
+    let text = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("> ", Style::default().fg(Color::Yellow)),
+            Span::styled(
+                format!("{}_", app.compare_input_buffer),
+                Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+            ),
+        ]),
+    ];
+
+    let popup = Paragraph::new(text)
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("Compare {} with  (Enter: confirm | Esc: cancel)", app.symbol))
+                .style(Style::default().bg(Color::Black)),
+        );
+
+    f.render_widget(Clear, popup_area);
+    f.render_widget(popup, popup_area);
+}
+
+/// Maps prices onto the y-axis: as-is, or through ln() in log mode. Log mode
+/// only engages when every price is positive.
+#[derive(Clone, Copy)]
+struct YScale {
+    log: bool,
+}
+
+impl YScale {
+    fn new(log_requested: bool, min_price: f64) -> Self {
+        YScale { log: log_requested && min_price > 0.0 }
+    }
+
+    fn apply(self, price: f64) -> f64 {
+        if self.log { price.ln() } else { price }
+    }
+
+    fn invert(self, y: f64) -> f64 {
+        if self.log { y.exp() } else { y }
+    }
+
+    /// Transform chart points, dropping any the log can't represent.
+    fn apply_points(self, points: Vec<(f64, f64)>) -> Vec<(f64, f64)> {
+        points.into_iter()
+            .map(|(x, p)| (x, self.apply(p)))
+            .filter(|(_, y)| y.is_finite())
+            .collect()
+    }
+}
+
+/// Decimals that tell neighbouring labels apart on an axis spanning `span`:
+/// cents for most listings, more for penny stocks and tight FX ranges.
+fn price_decimals(span: f64) -> usize {
+    if span.is_nan() || span <= 0.0 || span >= 1.0 {
+        return 2;
+    }
+    ((-span.log10()).ceil() as usize + 1).clamp(2, 6)
+}
+
+/// Price-axis bounds over `lo..=hi` in the scale's units, padded by `padding`
+/// (a price distance, e.g. one ATR) or 5% of the range. A flat series still
+/// gets a sliver of its price so the axis never collapses.
+fn padded_bounds(lo: f64, hi: f64, scale: YScale, padding: Option<f64>) -> [f64; 2] {
+    if scale.log {
+        let (lo, hi) = (lo.ln(), hi.ln());
+        let padding = ((hi - lo) * 0.05).max(0.001);
+        return [lo - padding, hi + padding];
+    }
+    let padding = padding
+        .unwrap_or((hi - lo) * 0.05)
+        .max(hi.abs() * 0.001)
+        .max(1e-6);
+    [lo - padding, hi + padding]
+}
+
+/// Scale, bounds and widest-case y labels of the line chart over the visible
+/// prices. The labels (the padded bounds and their midpoint) only size the
+/// axis gutter; `render_price_labels` fills it at round levels.
+fn line_y_axis(app: &App, data: &StockData) -> (YScale, [f64; 2], Vec<String>) {
+    // The baseline stays on screen even when the whole day is above or below
+    // it, and so do live ticks that leave the historical range
+    let prices: Vec<f64> = data.prices[visible_slice(app, data)].iter()
+        .cloned()
+        .chain(chart_baseline(app, data))
+        .chain(live_segment(app).into_iter().map(|(_, p)| p))
+        .collect();
+    let max_price = prices.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let min_price = prices.iter().cloned().fold(f64::INFINITY,     f64::min);
+    let scale = YScale::new(app.log_scale, min_price);
+    let y_bounds = padded_bounds(min_price, max_price, scale, app.indicator_values.atr);
+    let (lo, hi) = (scale.invert(y_bounds[0]), scale.invert(y_bounds[1]));
+    let decimals = price_decimals(hi - lo);
+    let cur = data.currency_prefix();
+    let labels = [lo, scale.invert((y_bounds[0] + y_bounds[1]) / 2.0), hi]
+        .iter()
+        .map(|v| format!("{}{:.*}", cur, decimals, v))
+        .collect();
+    (scale, y_bounds, labels)
+}
+
+/// Previous close the 1D line is coloured against, when baseline mode is on.
+fn chart_baseline(app: &App, data: &StockData) -> Option<f64> {
+    if !app.baseline || !matches!(app.timeframe, TimeFrame::OneDay) {
+        return None;
+    }
+    data.previous_close.filter(|&p| p > 0.0)
+}
+
+/// Indices of the bars that open a new session: the time since the previous
+/// bar is well over the usual spacing and the local date has changed. Only
+/// intraday series have sessions; daily bars would break at every weekend.
+fn session_breaks(timestamps: &[DateTime<Utc>], zone: DisplayZone) -> Vec<usize> {
+    let Some(spacing) = bar_spacing(timestamps).filter(|&s| s < 86_400.0) else {
+        return Vec::new();
+    };
+    (1..timestamps.len())
+        .filter(|&i| {
+            let (prev, next) = (timestamps[i - 1], timestamps[i]);
+            (next - prev).num_seconds() as f64 > 2.0 * spacing
+                && zone.date(&prev) != zone.date(&next)
+        })
+        .collect()
+}
+
+/// Split a line into one run per session, starting a run at each x in
+/// `breaks` (ascending), so overnight gaps aren't bridged.
+fn split_sessions(points: &[(f64, f64)], breaks: &[f64]) -> Vec<Vec<(f64, f64)>> {
+    let mut runs: Vec<Vec<(f64, f64)>> = vec![Vec::new()];
+    let mut next = breaks.iter().peekable();
+    for &(x, y) in points {
+        if next.next_if(|&&b| x >= b).is_some() {
+            while next.next_if(|&&b| x >= b).is_some() {}
+            if !runs[runs.len() - 1].is_empty() {
+                runs.push(Vec::new());
+            }
+        }
+        let last = runs.len() - 1;
+        runs[last].push((x, y));
+    }
+    runs
+}
+
+/// Faint vertical divider at each session break, with the new session's
+/// date at the top. Only blank or shaded cells are drawn over.
+fn render_session_dividers(f: &mut Frame, plot: Rect, breaks: &[(f64, String)], x_bounds: [f64; 2]) {
+    let style = Style::default().fg(Color::DarkGray);
+    let buf = f.buffer_mut();
+    for (x, date) in breaks {
+        let Some(col) = x_to_col(*x, x_bounds, plot.width) else { continue };
+        for row in 0..plot.height {
+            let cell = &mut buf[(plot.x + col, plot.y + row)];
+            if matches!(cell.symbol(), " " | "░") {
+                cell.set_symbol("┆").set_style(style);
+            }
+        }
+        for (i, ch) in date.chars().enumerate() {
+            let x = plot.x + col + 1 + i as u16;
+            if x >= plot.x + plot.width {
+                break;
+            }
+            let cell = &mut buf[(x, plot.y)];
+            if matches!(cell.symbol(), " " | "░") {
+                cell.set_char(ch).set_style(style);
+            }
+        }
+    }
+}
+
+/// Split a line where it crosses `base` into runs flagged above (or on) and
+/// below it. Each crossing is interpolated and ends one run and starts the
+/// next, so the line stays unbroken; a point exactly on the baseline stays
+/// with the run it continues.
+fn baseline_segments(points: &[(f64, f64)], base: f64) -> Vec<(bool, Vec<(f64, f64)>)> {
+    let mut segments: Vec<(bool, Vec<(f64, f64)>)> = Vec::new();
+    for &(x, y) in points {
+        let above = match segments.last() {
+            Some(&(above, _)) if y == base => above,
+            _ => y >= base,
+        };
+        match segments.last_mut() {
+            Some((run_above, run)) if *run_above == above => run.push((x, y)),
+            Some((_, run)) => {
+                // A run that ended on the baseline hands over from that point
+                let (x0, y0) = run[run.len() - 1];
+                let crossing = (x0 + (x - x0) * (base - y0) / (y - y0), base);
+                if y0 != base {
+                    run.push(crossing);
+                }
+                segments.push((above, vec![crossing, (x, y)]));
+            }
+            None => segments.push((above, vec![(x, y)])),
+        }
+    }
+    segments
+}
+
+/// Split a line into runs flagged as regular or extended hours by each
+/// point's x. A new run starts from the last point of the one before, so the
+/// line stays unbroken across the open and close.
+fn hours_segments(points: &[(f64, f64)], is_regular: impl Fn(f64) -> bool) -> Vec<(bool, Vec<(f64, f64)>)> {
+    let mut segments: Vec<(bool, Vec<(f64, f64)>)> = Vec::new();
+    for &(x, y) in points {
+        let regular = is_regular(x);
+        match segments.last_mut() {
+            Some((run_regular, run)) if *run_regular == regular => run.push((x, y)),
+            Some((_, run)) => {
+                let joint = run[run.len() - 1];
+                segments.push((regular, vec![joint, (x, y)]));
+            }
+            None => segments.push((regular, vec![(x, y)])),
+        }
+    }
+    segments
+}
+
+/// Trading hours to tell pre/post-market bars apart by, on the 1D chart
+/// (the only range fetched with extended hours). `None` for 24/7 listings.
+fn extended_hours(app: &App) -> Option<&MarketHours> {
+    app.stock_data.as_ref()?.market_hours.as_ref()
+        .filter(|h| matches!(app.timeframe, TimeFrame::OneDay) && !h.always_open)
+}
+
+/// Round price levels between `lo` and `hi` for log-axis labels: the coarsest
+/// 1-2-5 style ladder that still gives two levels, thinned to `max_levels`.
+/// Ranges too narrow for any ladder just get their two ends.
+fn nice_log_levels(lo: f64, hi: f64, max_levels: usize) -> Vec<f64> {
+    const LADDERS: [&[f64]; 4] = [
+        &[1.0],
+        &[1.0, 2.0, 5.0],
+        &[1.0, 1.5, 2.0, 3.0, 5.0, 7.0],
+        &[1.0, 1.5, 2.0, 2.5, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0],
+    ];
+    if lo <= 0.0 || hi <= lo {
+        return Vec::new();
+    }
+    let (first_exp, last_exp) = (lo.log10().floor() as i32, hi.log10().ceil() as i32);
+    for ladder in LADDERS {
+        let levels: Vec<f64> = (first_exp..=last_exp)
+            .flat_map(|e| ladder.iter().map(move |m| m * 10f64.powi(e)))
+            .filter(|&v| v >= lo && v <= hi)
+            .collect();
+        if levels.len() >= 2 {
+            let step = levels.len().div_ceil(max_levels.max(1));
+            return levels.into_iter().step_by(step).collect();
+        }
+    }
+    vec![lo, hi]
+}
+
+/// Rows of plot per y-axis label.
+const ROWS_PER_LABEL: usize = 5;
+
+/// Multiples of a round step (1, 2, 2.5 or 5 × 10ⁿ) between `lo` and `hi`, the
+/// finest step giving at most `max_levels`. Steps that `decimals` can't print
+/// exactly, like 0.25 at one decimal, are skipped.
+fn nice_linear_levels(lo: f64, hi: f64, max_levels: usize, decimals: usize) -> Vec<f64> {
+    if lo.is_nan() || hi.is_nan() || hi <= lo || max_levels == 0 {
+        return Vec::new();
+    }
+    let raw = (hi - lo) / max_levels as f64;
+    let magnitude = 10f64.powi(raw.log10().floor() as i32);
+    let printable = |step: f64| {
+        let scaled = step * 10f64.powi(decimals as i32);
+        (scaled - scaled.round()).abs() < 1e-6
+    };
+    let step = [1.0, 2.0, 2.5, 5.0, 10.0, 20.0]
+        .iter()
+        .map(|m| m * magnitude)
+        .find(|&step| step >= raw && printable(step))
+        .unwrap_or(raw);
+    let first = (lo / step).ceil() as i64;
+    let last = (hi / step).floor() as i64;
+    (first..=last).map(|i| i as f64 * step).collect()
+}
+
+/// Round y-axis levels inside `y_bounds` (in the scale's units), about one
+/// per `ROWS_PER_LABEL` rows of a plot `rows` tall, with their labels.
+fn axis_levels(y_bounds: [f64; 2], scale: YScale, rows: u16, cur: &str) -> Vec<(f64, String)> {
+    let max_levels = (rows as usize / ROWS_PER_LABEL).max(2);
+    let (lo, hi) = (scale.invert(y_bounds[0]), scale.invert(y_bounds[1]));
+    if scale.log {
+        nice_log_levels(lo, hi, max_levels)
+            .into_iter()
+            .map(|level| {
+                let text = if level >= AXIS_SUFFIX_FROM {
+                    suffixed_price(level, cur, hi - lo)
+                } else if level >= 100.0 {
+                    format!("{}{:.0}", cur, level)
+                } else {
+                    format!("{}{:.2}", cur, level)
+                };
+                (level, text)
+            })
+            .collect()
     } else {
-        render_footer(f, app, chunks[2]);
+        let decimals = price_decimals(hi - lo);
+        nice_linear_levels(lo, hi, max_levels, decimals)
+            .into_iter()
+            .map(|level| {
+                let text = if level.abs() >= AXIS_SUFFIX_FROM {
+                    suffixed_price(level, cur, hi - lo)
+                } else {
+                    format!("{}{:.*}", cur, decimals, level)
+                };
+                (level, text)
+            })
+            .collect()
+    }
+}
+
+/// Axis levels from here up are written with a k/M suffix to keep the
+/// gutter narrow.
+const AXIS_SUFFIX_FROM: f64 = 100_000.0;
+
+/// `level` in thousands or millions, with just enough decimals to tell
+/// levels `span` apart.
+fn suffixed_price(level: f64, cur: &str, span: f64) -> String {
+    let (unit, suffix) = if level.abs() >= 100_000_000.0 { (1e6, "M") } else { (1e3, "k") };
+    let scaled_span = span / unit;
+    let decimals = if scaled_span >= 10.0 { 0 } else if scaled_span >= 1.0 { 1 } else { 2 };
+    format!("{}{:.*}{}", cur, decimals, level / unit, suffix)
+}
+
+/// Right-aligned price labels at nice levels down the y-axis gutter `gutter`,
+/// which spans the same rows as the plot.
+fn render_price_labels(f: &mut Frame, gutter: Rect, cur: &str, y_bounds: [f64; 2], scale: YScale) {
+    if gutter.width == 0 || gutter.height < 2 {
+        return;
+    }
+    let [y_min, y_max] = y_bounds;
+    let buf = f.buffer_mut();
+    for (level, text) in axis_levels(y_bounds, scale, gutter.height, cur) {
+        let norm = (y_max - scale.apply(level)) / (y_max - y_min);
+        let row = (norm * (gutter.height - 1) as f64).round() as u16;
+        let text = format!("{:>width$}", text, width = gutter.width as usize);
+        buf.set_stringn(gutter.x, gutter.y + row, &text, gutter.width as usize, Style::default().fg(Color::Gray));
+    }
+}
+
+/// Golden/death crosses between the shortest and longest enabled SMA.
+fn sma_crosses(app: &App) -> Vec<indicators::Cross> {
+    if !app.show_sma || app.sma_periods.len() < 2 {
+        return Vec::new();
+    }
+    let periods = &app.sma_periods;
+    let series = |i: Option<usize>| i.and_then(|i| app.indicator_values.sma.get(i));
+    let fast = series((0..periods.len()).min_by_key(|&i| periods[i]));
+    let slow = series((0..periods.len()).max_by_key(|&i| periods[i]));
+    match (fast, slow) {
+        (Some(fast), Some(slow)) => indicators::crossovers(fast, slow),
+        _ => Vec::new(),
+    }
+}
+
+/// Dotted row at the latest price, `norm` of the way down `plot` (clamped onto
+/// it), with the price in a tag at the right edge. Drawn only over blank
+/// cells, so the line itself stays on top.
+fn render_price_marker(f: &mut Frame, plot: Rect, norm: f64, tag: &str, color: Color) {
+    if plot.width == 0 || plot.height == 0 || !norm.is_finite() {
+        return;
+    }
+    let row = (norm.clamp(0.0, 1.0) * (plot.height - 1) as f64).round() as u16;
+    let y = plot.y + row;
+    let buf = f.buffer_mut();
+    for x in plot.x..plot.x + plot.width {
+        let cell = &mut buf[(x, y)];
+        if cell.symbol() == " " {
+            cell.set_symbol("┈").set_style(Style::default().fg(color).add_modifier(Modifier::DIM));
+        }
+    }
+    let width = tag.chars().count() as u16;
+    if width <= plot.width {
+        buf.set_string(plot.x + plot.width - width, y, tag, Style::default().fg(Color::Black).bg(color));
+    }
+}
+
+/// Dim shade under the price line. Only blank cells are filled, so the line,
+/// overlays and reference dots stay readable; `plot` is the area inside the
+/// axes, which are never touched.
+fn render_area_fill(f: &mut Frame, plot: Rect, points: &[(f64, f64)], x_bounds: [f64; 2], y_bounds: [f64; 2], color: Color) {
+    if plot.width < 2 || plot.height == 0 || points.is_empty() {
+        return;
+    }
+    let ([x_lo, x_hi], [y_lo, y_hi]) = (x_bounds, y_bounds);
+    let style = Style::default().fg(color).add_modifier(Modifier::DIM);
+    let buf = f.buffer_mut();
+    for col in 0..plot.width {
+        let x = x_lo + (x_hi - x_lo) * col as f64 / (plot.width - 1) as f64;
+        let Some(y) = line_y_at(points, x) else { continue };
+        let norm = ((y_hi - y.clamp(y_lo, y_hi)) / (y_hi - y_lo)).clamp(0.0, 1.0);
+        let top = (norm * plot.height as f64).ceil() as u16;
+        for row in top..plot.height {
+            let cell = &mut buf[(plot.x + col, plot.y + row)];
+            if cell.symbol() == " " {
+                cell.set_symbol("░").set_style(style);
+            }
+        }
+    }
+}
+
+/// Height of the line through `points` at `x`, interpolated between the
+/// points either side and held flat past either end.
+fn line_y_at(points: &[(f64, f64)], x: f64) -> Option<f64> {
+    let i = points.partition_point(|&(px, _)| px < x);
+    match (i.checked_sub(1).map(|j| points[j]), points.get(i)) {
+        (Some((x0, y0)), Some(&(x1, y1))) if x1 > x0 => Some(y0 + (y1 - y0) * (x - x0) / (x1 - x0)),
+        (_, Some(&(_, y))) | (Some((_, y)), None) => Some(y),
+        (None, None) => None,
+    }
+}
+
+/// Dots between the line and its running high `peaks`, wherever the line is
+/// below it. Only blank cells are drawn over.
+fn render_drawdown_shading(f: &mut Frame, plot: Rect, points: &[(f64, f64)], peaks: &[(f64, f64)], x_bounds: [f64; 2], y_bounds: [f64; 2]) {
+    if plot.width < 2 || plot.height == 0 || points.is_empty() {
+        return;
+    }
+    let ([x_lo, x_hi], [y_lo, y_hi]) = (x_bounds, y_bounds);
+    let row_of = |y: f64| ((y_hi - y.clamp(y_lo, y_hi)) / (y_hi - y_lo) * plot.height as f64).clamp(0.0, plot.height as f64);
+    let style = Style::default().fg(Color::Red).add_modifier(Modifier::DIM);
+    let buf = f.buffer_mut();
+    for col in 0..plot.width {
+        let x = x_lo + (x_hi - x_lo) * col as f64 / (plot.width - 1) as f64;
+        let (Some(y), Some(peak)) = (line_y_at(points, x), line_y_at(peaks, x)) else { continue };
+        if y >= peak {
+            continue;
+        }
+        let (top, bottom) = (row_of(peak).ceil() as u16, row_of(y).floor() as u16);
+        for row in top..bottom.min(plot.height) {
+            let cell = &mut buf[(plot.x + col, plot.y + row)];
+            if cell.symbol() == " " {
+                cell.set_symbol("·").set_style(style);
+            }
+        }
+    }
+}
+
+/// Background tint for the crosshair column, leaving whatever is drawn there.
+fn highlight_column(f: &mut Frame, column: Rect) {
+    let buf = f.buffer_mut();
+    for y in column.y..column.y + column.height {
+        for x in column.x..column.x + column.width {
+            buf[(x, y)].set_bg(Color::DarkGray);
+        }
+    }
+}
+
+/// ▲/▼ at each crossover, written over the line chart in `plot`.
+fn render_cross_markers(f: &mut Frame, plot: Rect, crosses: &[indicators::Cross], xmap: &XMap, x_bounds: [f64; 2], y_bounds: [f64; 2], scale: YScale) {
+    if plot.width < 2 || plot.height < 2 {
+        return;
+    }
+    let [y_min, y_max] = y_bounds;
+    let buf = f.buffer_mut();
+    for cross in crosses {
+        let Some(col) = x_to_col(xmap.x(cross.index as f64), x_bounds, plot.width) else { continue };
+        let norm = ((y_max - scale.apply(cross.value)) / (y_max - y_min)).clamp(0.0, 1.0);
+        let row = (norm * (plot.height - 1) as f64).round() as u16;
+        let (symbol, color) = match cross.kind {
+            indicators::CrossKind::Golden => ("▲", Color::Green),
+            indicators::CrossKind::Death => ("▼", Color::Red),
+        };
+        buf[(plot.x + col, plot.y + row)]
+            .set_symbol(symbol)
+            .set_style(Style::default().fg(color).add_modifier(Modifier::BOLD));
+    }
+}
+
+/// "H 197.42" above the session high and "L 193.10" below the low, on the 1D chart.
+fn render_extreme_markers(f: &mut Frame, app: &App, plot: Rect, xmap: &XMap, x_bounds: [f64; 2], y_bounds: [f64; 2], scale: YScale) {
+    let Some(((high_i, high), (low_i, low))) = app.stock_data.as_ref().and_then(|d| d.session_extremes()) else { return; };
+    if plot.width < 2 || plot.height < 2 {
+        return;
+    }
+    let [y_min, y_max] = y_bounds;
+    let buf = f.buffer_mut();
+    for (index, price, label, color, row_shift) in [
+        (high_i, high, "H", Color::Green, -1i32),
+        (low_i, low, "L", Color::Red, 1),
+    ] {
+        let text = format!("{} {:.2}", label, price);
+        let width = text.chars().count() as u16;
+        let Some(col) = x_to_col(xmap.x(index as f64), x_bounds, plot.width) else { continue };
+        let norm = ((y_max - scale.apply(price)) / (y_max - y_min)).clamp(0.0, 1.0);
+        let row = (norm * (plot.height - 1) as f64).round() as i32 + row_shift;
+        let row = row.clamp(0, plot.height as i32 - 1) as u16;
+        // Keep the label inside the plot, centred on the point where possible
+        let x = col.saturating_sub(width / 2).min(plot.width.saturating_sub(width));
+        let mut style = Style::default().fg(color).add_modifier(Modifier::BOLD);
+        if app.extreme_flash.is_some() {
+            style = style.add_modifier(Modifier::REVERSED);
+        }
+        buf.set_string(plot.x + x, plot.y + row, &text, style);
+    }
+}
+
+/// Stochastic %K/%D pane with 20/80 reference lines, drawing the visible
+/// `k` and `d` points of `stoch`. Shared with the live candle view, which
+/// passes its own `x_bounds` and no label offset.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn render_stochastic_pane(
+    f: &mut Frame,
+    area: Rect,
+    stoch: &indicators::Stochastic,
+    k: &[(f64, f64)],
+    d: &[(f64, f64)],
+    periods: [usize; 3],
+    x_bounds: [f64; 2],
+    left_offset: u16,
+) {
+    let [period, smooth_k, smooth_d] = periods;
+
+    let mut title = vec![Span::raw(format!("Stoch({},{},{}) ", period, smooth_k, smooth_d))];
+    match (stoch.k.last(), stoch.d.last()) {
+        (Some(&(_, k)), Some(&(_, d))) => {
+            title.push(Span::styled(format!("%K {:.1}", k), Style::default().fg(Color::Cyan)));
+            title.push(Span::raw(" "));
+            title.push(Span::styled(format!("%D {:.1}", d), Style::default().fg(Color::Yellow)));
+        }
+        _ => title.push(Span::styled("needs more data", Style::default().fg(Color::DarkGray))),
     }
+
+    let [lo, hi] = x_bounds;
+    let overbought = [(lo, 80.0), (hi, 80.0)];
+    let oversold   = [(lo, 20.0), (hi, 20.0)];
+    let datasets = vec![
+        Dataset::default()
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Red).add_modifier(Modifier::DIM))
+            .data(&overbought),
+        Dataset::default()
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Green).add_modifier(Modifier::DIM))
+            .data(&oversold),
+        Dataset::default()
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Cyan))
+            .data(k),
+        Dataset::default()
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Yellow))
+            .data(d),
+    ];
+
+    render_pane_chart(f, area, Line::from(title), datasets, x_bounds, [0.0, 100.0], ["20".into(), "80".into()], left_offset);
+}
+
+fn render_rsi_pane(f: &mut Frame, app: &App, area: Rect, left_offset: u16) {
+    let (xmap, index_bounds, x_bounds) = pane_x(app, area, left_offset);
+    let points = xmap.points(visible_points(&app.indicator_values.rsi, index_bounds));
+
+    let mut title = vec![Span::raw(format!("RSI({}) ", app.rsi_period))];
+    match points.last() {
+        Some(&(_, value)) => {
+            let color = if value >= 70.0 {
+                Color::Red
+            } else if value <= 30.0 {
+                Color::Green
+            } else {
+                Color::White
+            };
+            title.push(Span::styled(format!("{:.1}", value), Style::default().fg(color).add_modifier(Modifier::BOLD)));
+        }
+        None => title.push(Span::styled("needs more data", Style::default().fg(Color::DarkGray))),
+    }
+
+    let [lo, hi] = x_bounds;
+    let overbought = [(lo, 70.0), (hi, 70.0)];
+    let oversold   = [(lo, 30.0), (hi, 30.0)];
+    let datasets = vec![
+        Dataset::default()
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Red).add_modifier(Modifier::DIM))
+            .data(&overbought),
+        Dataset::default()
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Green).add_modifier(Modifier::DIM))
+            .data(&oversold),
+        Dataset::default()
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Magenta))
+            .data(&points),
+    ];
+
+    render_pane_chart(f, area, Line::from(title), datasets, x_bounds, [0.0, 100.0], ["30".into(), "70".into()], left_offset);
+}
+
+fn render_macd_pane(f: &mut Frame, app: &App, area: Rect, left_offset: u16) {
+    let [fast, slow, signal] = app.macd_periods;
+    let macd = &app.indicator_values.macd;
+    let (xmap, index_bounds, x_bounds) = pane_x(app, area, left_offset);
+    let (line, signal_line, histogram) = (
+        xmap.points(visible_points(&macd.macd, index_bounds)),
+        xmap.points(visible_points(&macd.signal, index_bounds)),
+        xmap.points(visible_points(&macd.histogram, index_bounds)),
+    );
+
+    let mut title = vec![Span::raw(format!("MACD({},{},{}) ", fast, slow, signal))];
+    match (macd.macd.last(), macd.signal.last()) {
+        (Some(&(_, m)), Some(&(_, s))) => {
+            title.push(Span::styled(format!("{:.2}", m), Style::default().fg(Color::Cyan)));
+            title.push(Span::raw(" / "));
+            title.push(Span::styled(format!("{:.2}", s), Style::default().fg(Color::Yellow)));
+        }
+        _ => title.push(Span::styled("needs more data", Style::default().fg(Color::DarkGray))),
+    }
+
+    // Symmetric bounds keep zero on the middle row
+    let extent = line.iter()
+        .chain(&signal_line)
+        .chain(&histogram)
+        .map(|&(_, v)| v.abs())
+        .fold(0.0, f64::max)
+        .max(f64::EPSILON);
+    let y_bounds = [-extent, extent];
+
+    // Histogram first, straight into the buffer; the chart only paints the cells
+    // its lines pass through, so the bars stay visible underneath
+    let block = Block::default().borders(Borders::LEFT | Borders::RIGHT | Borders::BOTTOM).title(Line::from(title.clone()));
+    let inner = block.inner(area);
+    let plot = Rect {
+        x: inner.x + left_offset.min(inner.width),
+        y: inner.y,
+        width: inner.width.saturating_sub(left_offset),
+        height: inner.height,
+    };
+    render_histogram(f, plot, &histogram, x_bounds, extent);
+
+    let datasets = vec![
+        Dataset::default()
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Cyan))
+            .data(&line),
+        Dataset::default()
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(Color::Yellow))
+            .data(&signal_line),
+    ];
+    let labels = [format!("{:.2}", -extent), format!("{:.2}", extent)];
+    render_pane_chart(f, area, Line::from(title), datasets, x_bounds, y_bounds, labels, left_offset);
+}
+
+/// Half-cell bars from zero, green above and red below, over `[-extent, extent]`.
+fn render_histogram(f: &mut Frame, plot: Rect, bars: &[(f64, f64)], x_bounds: [f64; 2], extent: f64) {
+    if plot.width < 2 || plot.height == 0 || bars.is_empty() {
+        return;
+    }
+    let cell_span = 2.0 * extent / plot.height as f64;
+    let buf = f.buffer_mut();
+    for col in 0..plot.width {
+        // Same x mapping as the chart: column → the bar within half a step,
+        // none inside a session gap
+        let [lo, hi] = x_bounds;
+        let x = lo + col as f64 * (hi - lo) / (plot.width - 1) as f64;
+        let i = bars.partition_point(|&(bx, _)| bx < x - 0.5);
+        let Some(&(_, value)) = bars.get(i).filter(|&&(bx, _)| bx <= x + 0.5) else { continue; };
+        let color = if value >= 0.0 { Color::Green } else { Color::Red };
+        let (bar_lo, bar_hi) = if value >= 0.0 { (0.0, value) } else { (value, 0.0) };
+        for row in 0..plot.height {
+            let cell_hi = extent - row as f64 * cell_span;
+            let cell_lo = cell_hi - cell_span;
+            let covered = (bar_hi.min(cell_hi) - bar_lo.max(cell_lo)).max(0.0) / cell_span;
+            let symbol = if covered >= 0.75 {
+                "█"
+            } else if covered >= 0.25 {
+                // Bars grow away from zero, so the filled half faces the zero line
+                if value >= 0.0 { "▄" } else { "▀" }
+            } else {
+                continue;
+            };
+            buf[(plot.x + col, plot.y + row)].set_symbol(symbol).set_fg(color);
+        }
+    }
+}
+
+/// Draw a pane chart whose plot area starts in the same column as the price
+/// chart above: y labels are padded to the price chart's label width. A zero
+/// offset drops the labels so the plot spans the whole pane.
+#[allow(clippy::too_many_arguments)]
+fn render_pane_chart(
+    f: &mut Frame,
+    area: Rect,
+    title: Line,
+    datasets: Vec<Dataset>,
+    x_bounds: [f64; 2],
+    y_bounds: [f64; 2],
+    y_labels: [String; 2],
+    left_offset: u16,
+) {
+    let label_w = left_offset.saturating_sub(1) as usize;
+    let labels: Vec<Span> = if left_offset == 0 {
+        Vec::new()
+    } else {
+        y_labels.iter()
+            .map(|l| Span::raw(format!("{:>width$}", l, width = label_w)))
+            .collect()
+    };
+
+    let chart = Chart::new(datasets)
+        .block(Block::default().borders(Borders::LEFT | Borders::RIGHT | Borders::BOTTOM).title(title))
+        .x_axis(Axis::default().bounds(x_bounds))
+        .y_axis(
+            Axis::default()
+                .style(Style::default().fg(Color::Gray))
+                .bounds(y_bounds)
+                .labels(labels),
+        );
+    f.render_widget(chart, area);
 }
 
 /// Replicates ratatui's Chart::layout() to find how many columns are consumed
 /// to the left of the actual plot area (y-axis labels + the axis line itself).
+/// The zoom window clamped to the loaded series, which the stored
+/// indicator values may briefly outlive.
+fn visible_slice(app: &App, data: &StockData) -> std::ops::Range<usize> {
+    let range = app.visible_range();
+    let len = data.prices.len();
+    if range.end > len || range.is_empty() {
+        return 0..len;
+    }
+    range
+}
+
 fn graph_left_offset(app: &App, chart_area: Rect) -> u16 {
     let Some(ref data) = app.stock_data else { return 0; };
     if data.prices.is_empty() || data.timestamps.is_empty() { return 0; }
 
-    let max_price = data.prices.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
-    let min_price = data.prices.iter().cloned().fold(f64::INFINITY,     f64::min);
+    let range = visible_slice(app, data);
 
-    // Same three y-labels used in render_chart
-    let y_label_w = [
-        format!("${:.2}", min_price).len() as u16,
-        format!("${:.2}", (min_price + max_price) / 2.0).len() as u16,
-        format!("${:.2}", max_price).len() as u16,
-    ]
-    .into_iter()
-    .max()
-    .unwrap_or(0);
+    // Same y-labels used in render_chart
+    let (_, _, y_labels) = line_y_axis(app, data);
+    let y_label_w = y_labels.iter().map(|l| l.chars().count() as u16).max().unwrap_or(0);
 
     // First x-label width (Alignment::Left, has_y_axis=true → subtract 1)
-    let first_x_w = format_timestamp(data.timestamps.first().unwrap(), &app.timeframe)
+    let first_x_w = format_timestamp(&data.timestamps[range.start.min(data.timestamps.len() - 1)], &app.timeframe, app.display_zone())
         .len() as u16;
     let x_contribution = first_x_w.saturating_sub(1);
 
@@ -80,6 +1204,12 @@ fn graph_left_offset(app: &App, chart_area: Rect) -> u16 {
     labels_w + 1
 }
 
+/// " · $123.45 ▲1.23%" for the chart title while the header is hidden.
+fn full_screen_quote(data: &StockData) -> String {
+    let change_symbol = if data.change >= 0.0 { "▲" } else { "▼" };
+    format!(" · {} {}{:.2}%", format_price(data.current_price, &data.currency_prefix()), change_symbol, data.change_percent.abs())
+}
+
 fn render_header(f: &mut Frame, app: &App, area: ratatui::layout::Rect) {
     if let Some(ref stock_data) = app.stock_data {
         let price_color = if stock_data.change >= 0.0 {
@@ -91,38 +1221,119 @@ fn render_header(f: &mut Frame, app: &App, area: ratatui::layout::Rect) {
         let change_symbol = if stock_data.change >= 0.0 { "▲" } else { "▼" };
 
         let (market_badge, badge_color) = match stock_data.market_state {
+            _ if app.csv_path.is_some() => (Some(" ◇ CSV (offline)"), Color::DarkGray),
             MarketState::Regular => (Some(" ● Market Open"), Color::Green),
             MarketState::Pre    => (Some(" ◑ Pre-Market"), Color::Yellow),
             MarketState::Post   => (Some(" ☾ After Hours"), Color::Yellow),
             MarketState::Closed => (Some(" ● Market Closed"), Color::DarkGray),
         };
 
+        let cur = stock_data.currency_prefix();
         let mut spans = vec![
-            Span::raw(format!("{} ", stock_data.symbol)),
+            Span::raw(format!("{}  ", stock_data.description())),
             Span::styled(
-                format!("${:.2}", stock_data.current_price),
+                format_price(stock_data.current_price, &cur),
                 Style::default().fg(price_color).add_modifier(Modifier::BOLD),
             ),
-            Span::raw("  "),
+            Span::raw("  Today "),
             Span::styled(
                 format!(
-                    "{} ${:.2} ({:.2}%)",
+                    "{} {sign}{} ({sign}{:.2}%)",
                     change_symbol,
-                    stock_data.change.abs(),
-                    stock_data.change_percent.abs()
+                    format_price(stock_data.change.abs(), &cur),
+                    stock_data.change_percent.abs(),
+                    // Colour-blind mode spells the direction out as a sign too
+                    sign = if app.display_mode == DisplayMode::Colorblind { sign_prefix(stock_data.change) } else { "" },
                 ),
                 Style::default().fg(price_color),
             ),
-            Span::raw(format!("  [{}]", app.timeframe.display())),
         ];
 
-        if let Some(badge) = market_badge {
+        // Period change is redundant on the 1D view, where it's ~today's move
+        if !matches!(app.timeframe, TimeFrame::OneDay) {
+            let period_color = if stock_data.period_change >= 0.0 { Color::Green } else { Color::Red };
+            let sign = if stock_data.period_change >= 0.0 { "+" } else { "" };
+            spans.push(Span::raw(format!(" · {} ", app.timeframe.short_label())));
+            spans.push(Span::styled(
+                format!("{}{:.2}%", sign, stock_data.period_change_percent),
+                Style::default().fg(period_color),
+            ));
+        }
+        spans.push(Span::raw(format!("  [{}]", app.timeframe.display())));
+        spans.push(Span::styled(format!(" {}", app.time_zone_label()), Style::default().fg(Color::DarkGray)));
+        if let Some(cross) = sma_crosses(app).last()
+            && let Some(ts) = app.indicator_values.timestamps.get(cross.index)
+        {
+            let (name, color) = match cross.kind {
+                indicators::CrossKind::Golden => ("▲ Golden cross", Color::Green),
+                indicators::CrossKind::Death => ("▼ Death cross", Color::Red),
+            };
+            let when = app.display_zone().format(ts, "%b %-d");
+            spans.push(Span::styled(format!("  {} {}", name, when), Style::default().fg(color)));
+        }
+        if matches!(app.timeframe, TimeFrame::OneDay)
+            && let Some(((_, high), (_, low))) = stock_data.session_extremes()
+        {
+            spans.push(Span::styled(format!("  H {:.2}", high), Style::default().fg(Color::Green)));
+            spans.push(Span::styled(format!(" L {:.2}", low), Style::default().fg(Color::Red)));
+        }
+        if let Some(atr) = app.indicator_values.atr {
+            spans.push(Span::styled(format!("  ATR {:.2}", atr), Style::default().fg(Color::Gray)));
+        }
+        if let Some(&(_, vol)) = app.indicator_values.volatility.last() {
+            spans.push(Span::styled(
+                format!("  RV{} {:.1}%", app.volatility_period, vol),
+                Style::default().fg(Color::Gray),
+            ));
+        }
+
+        if let Some(banner) = app.market_closed_banner() {
+            spans.push(Span::styled(format!("  {}", banner), Style::default().fg(Color::DarkGray)));
+        } else if let Some(badge) = market_badge {
             spans.push(Span::styled(badge, Style::default().fg(badge_color)));
         }
 
+        if let (Some(high), Some(low)) = (stock_data.fifty_two_week_high, stock_data.fifty_two_week_low) {
+            let from_high = (stock_data.current_price - high) / high * 100.0;
+            let style = if stock_data.above_52w_high() {
+                Style::default().fg(Color::Black).bg(Color::Green).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::Gray)
+            };
+            let distance = if stock_data.above_52w_high() {
+                "NEW 52w HIGH".to_string()
+            } else {
+                let sign = if from_high < 0.0 { "−" } else { "" };
+                format!("{}{:.1}% from high", sign, from_high.abs())
+            };
+            spans.push(Span::styled(
+                format!("  52w: {:.2} – {:.2} ({})", low, high, distance),
+                style,
+            ));
+        }
+
+        if let Some(date) = stock_data.earnings.as_ref().and_then(|e| e.next_date) {
+            let days = (date - Utc::now().date_naive()).num_days();
+            let when = match days {
+                0 => "today".to_string(),
+                1 => "tomorrow".to_string(),
+                n => format!("in {} days", n),
+            };
+            spans.push(Span::styled(
+                format!("  Earnings: {} ({})", date.format("%b %-d"), when),
+                Style::default().fg(Color::Magenta),
+            ));
+        }
+
         if app.show_sma {
-            spans.push(Span::styled("  SMA20", Style::default().fg(Color::Cyan)));
-            spans.push(Span::styled("  SMA50", Style::default().fg(Color::Yellow)));
+            for (&period, &color) in app.sma_periods.iter().zip(SMA_COLORS.iter()) {
+                spans.push(Span::styled(format!("  SMA{}", period), Style::default().fg(color)));
+            }
+        }
+        if app.show_ema {
+            for (&period, &color) in app.ema_periods.iter().zip(EMA_COLORS.iter()) {
+                spans.push(Span::styled(format!("  EMA{}", period), Style::default().fg(color)));
+            }
         }
 
         let header = Paragraph::new(Line::from(spans))
@@ -135,14 +1346,96 @@ fn render_header(f: &mut Frame, app: &App, area: ratatui::layout::Rect) {
     }
 }
 
-fn compute_sma(prices: &[f64], period: usize) -> Vec<(f64, f64)> {
-    if prices.len() < period {
-        return Vec::new();
+/// One color per configured SMA period, in order.
+const SMA_COLORS: [Color; 4] = [Color::Cyan, Color::Yellow, Color::Magenta, Color::LightBlue];
+pub(super) const EMA_COLORS: [Color; 4] = [Color::LightMagenta, Color::LightGreen, Color::LightRed, Color::White];
+
+/// A named indicator line drawn over the price series.
+pub(super) struct Overlay {
+    pub name: String,
+    pub color: Color,
+    pub points: Vec<(f64, f64)>,
+}
+
+/// Enabled SMA/EMA lines for the configured periods, skipping any the series
+/// is too short for.
+fn indicator_overlays(app: &App) -> Vec<Overlay> {
+    let values = &app.indicator_values;
+    let mut overlays = Vec::new();
+    if app.show_sma {
+        for ((&period, &color), points) in app.sma_periods.iter().zip(SMA_COLORS.iter()).zip(&values.sma) {
+            overlays.push(Overlay { name: format!("SMA{}", period), color, points: points.clone() });
+        }
     }
-    prices
-        .windows(period)
-        .enumerate()
-        .map(|(i, w)| ((i + period - 1) as f64, w.iter().sum::<f64>() / period as f64))
+    if app.show_ema {
+        for ((&period, &color), points) in app.ema_periods.iter().zip(EMA_COLORS.iter()).zip(&values.ema) {
+            overlays.push(Overlay { name: format!("EMA{}", period), color, points: points.clone() });
+        }
+    }
+    if app.show_trend {
+        overlays.extend(trend_overlays(app));
+    }
+    overlays.retain(|o| !o.points.is_empty());
+    overlays
+}
+
+/// Regression line over the indicator series, named with its slope in %/day,
+/// plus the ±1 standard error channel. Only the upper channel line carries a
+/// name so the legend lists it once.
+fn trend_overlays(app: &App) -> Vec<Overlay> {
+    let values = &app.indicator_values;
+    let Some(fit) = values.trend else { return Vec::new() };
+    let (Some(first), Some(last)) = (values.timestamps.first(), values.timestamps.last()) else { return Vec::new() };
+
+    let count = values.closes.len();
+    let last_x = (count - 1) as f64;
+    let days_per_index = (*last - *first).num_seconds() as f64 / 86_400.0 / last_x;
+    let mean = fit.at(last_x / 2.0);
+    let pct_per_day = if days_per_index > 0.0 && mean != 0.0 {
+        fit.slope / days_per_index / mean * 100.0
+    } else {
+        0.0
+    };
+
+    // A point per index so the candlestick renderer can place it on every candle
+    let line = |offset: f64| -> Vec<(f64, f64)> {
+        (0..count).map(|i| (i as f64, fit.at(i as f64) + offset)).collect()
+    };
+    vec![
+        Overlay { name: format!("Trend {:+.2}%/day", pct_per_day), color: Color::White, points: line(0.0) },
+        Overlay { name: "±1σ".to_string(), color: Color::DarkGray, points: line(fit.std_error) },
+        Overlay { name: String::new(), color: Color::DarkGray, points: line(-fit.std_error) },
+    ]
+}
+
+
+/// Thin `points` to at most `max_points`, always keeping the last point so the
+/// line still ends at the current price.
+fn downsample(points: Vec<(f64, f64)>, max_points: usize) -> Vec<(f64, f64)> {
+    if max_points < 2 || points.len() <= max_points {
+        return points;
+    }
+    let step = points.len().div_ceil(max_points);
+    let last = points[points.len() - 1];
+    let mut thinned: Vec<(f64, f64)> = points.into_iter().step_by(step).collect();
+    if thinned.last() != Some(&last) {
+        thinned.push(last);
+    }
+    thinned
+}
+
+/// 3-point moving median of `values` for drawing. The first and last values
+/// are kept as they are, so the line still starts and ends where the header's
+/// change is measured.
+fn median3(values: &[f64]) -> Vec<f64> {
+    (0..values.len())
+        .map(|i| {
+            if i == 0 || i + 1 == values.len() {
+                return values[i];
+            }
+            let (a, b, c) = (values[i - 1], values[i], values[i + 1]);
+            a.max(b).min(a.min(b).max(c))
+        })
         .collect()
 }
 
@@ -156,141 +1449,295 @@ fn render_chart(f: &mut Frame, app: &App, area: ratatui::layout::Rect) {
     }
 
     // Candlestick path
-    if app.show_candlesticks {
-        if let Some(ref stock_data) = app.stock_data {
-            let candles = app.convert_to_candlesticks();
-            if !candles.is_empty() {
-                let title = format!(
-                    "{} - {} (Candlesticks: {})",
-                    stock_data.symbol,
-                    app.timeframe.display(),
-                    app.candle_interval.to_string()
-                );
-                let first_ts = candles.first().unwrap().timestamp.clone();
-                let last_ts  = candles.last().unwrap().timestamp.clone();
-                let x_labels = vec![
-                    Span::raw(format_timestamp(&first_ts, &app.timeframe)),
-                    Span::raw(format_timestamp(&last_ts,  &app.timeframe)),
-                ];
-                render_candlestick_chart(f, &candles, area, title, x_labels, &stock_data.symbol);
-                return;
+    if app.show_candlesticks && let Some(ref stock_data) = app.stock_data {
+        let candles = app.convert_to_candlesticks();
+        if !candles.is_empty() {
+            let mut title = format!(
+                "{} - {} (Candlesticks: {})",
+                stock_data.symbol,
+                app.timeframe.display(),
+                app.candle_interval.to_string()
+            );
+            if app.log_scale && candles.iter().all(|c| c.low > 0.0) {
+                title.push_str(" · log");
             }
+            if app.full_screen {
+                title.push_str(&full_screen_quote(stock_data));
+            }
+            let first_ts = candles.first().unwrap().timestamp;
+            let last_ts  = candles.last().unwrap().timestamp;
+            let x_labels = vec![
+                Span::raw(format_timestamp(&first_ts, &app.timeframe, app.display_zone())),
+                Span::raw(format_timestamp(&last_ts,  &app.timeframe, app.display_zone())),
+            ];
+            let overlays = indicator_overlays(app);
+            let patterns = detect_patterns(app, candles.iter());
+            let candles: Vec<&Candlestick> = candles.iter().collect();
+            render_candlestick_chart(f, area, &CandleChart {
+                title,
+                candles: &candles,
+                scroll: 0,
+                forming: false,
+                log_scale: app.log_scale,
+                overlays: &overlays,
+                patterns: Some(&patterns),
+                pivots: None,
+                cursor: app.chart_cursor,
+                braille: app.use_braille_candles(f.area().height),
+                hollow_up: app.display_mode == DisplayMode::Colorblind,
+                axes: Some(CandleAxes { cur: &stock_data.currency_prefix(), x_labels, zone: app.display_zone() }),
+            });
+            return;
         }
     }
 
+    if let (Some(main), Some(other)) = (&app.stock_data, &app.compare_data)
+        && render_compare_chart(f, app, area, main, other)
+    {
+        return;
+    }
+
     if let Some(ref stock_data) = app.stock_data {
-        let price_color = if stock_data.change >= 0.0 { Color::Green } else { Color::Red };
-
-        let chart_data: Vec<(f64, f64)>;
-        let max_price: f64;
-        let min_price: f64;
-        let max_x: f64;
-        let first_ts: DateTime<Utc>;
-        let last_ts: DateTime<Utc>;
-
-        // Regular line chart
-        chart_data = stock_data
-            .prices
+        let price_color = if stock_data.period_change >= 0.0 { Color::Green } else { Color::Red };
+
+        // Regular line chart. Braille packs two dots per cell, so anything beyond
+        // that is invisible and only slows down rendering on long ranges.
+        let range = visible_slice(app, stock_data);
+        let prices = &stock_data.prices[range.clone()];
+        let timestamps = &stock_data.timestamps[range.start.min(stock_data.timestamps.len())..range.end.min(stock_data.timestamps.len())];
+        // Everything is built on bar indices and moved through the session
+        // gaps, if any, once the line is split up
+        let plot_width = area.width.saturating_sub(2 + graph_left_offset(app, area));
+        let xmap = x_map(app, plot_width);
+        let index_bounds = visible_x_bounds(app);
+        let x_bounds = xmap.bounds(index_bounds);
+        let smoothed = app.smooth_line.then(|| median3(prices));
+        let chart_data: Vec<(f64, f64)> = smoothed.as_deref().unwrap_or(prices)
             .iter()
             .enumerate()
-            .map(|(i, &p)| (i as f64, p))
+            .map(|(i, &p)| ((range.start + i) as f64, p))
             .collect();
-        max_price = stock_data.prices.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
-        min_price = stock_data.prices.iter().cloned().fold(f64::INFINITY,     f64::min);
-        max_x     = (stock_data.prices.len() - 1) as f64;
-        first_ts  = *stock_data.timestamps.first().unwrap();
-        last_ts   = *stock_data.timestamps.last().unwrap();
+        let chart_data = downsample(chart_data, area.width as usize * 2);
+        // Running high of the drawn line over the visible window, thinned the same way
+        let peaks = app.drawdown_shading.then(|| {
+            let peaks: Vec<(f64, f64)> = crate::analytics::running_max(smoothed.as_deref().unwrap_or(prices))
+                .into_iter()
+                .enumerate()
+                .map(|(i, p)| ((range.start + i) as f64, p))
+                .collect();
+            downsample(peaks, area.width as usize * 2)
+        });
+        let first_ts: DateTime<Utc> = *timestamps.first().unwrap();
+        let last_ts: DateTime<Utc>  = *timestamps.last().unwrap();
+        // Pad by one typical bar's range so the line never hugs the frame; without
+        // enough candles for an ATR, fall back to a slice of the price range.
+        // In log mode the padding is a slice of the log range instead.
+        let (scale, y_bounds, y_labels) = line_y_axis(app, stock_data);
+        let chart_data = scale.apply_points(chart_data);
+        let peaks = peaks.map(|p| xmap.points(&scale.apply_points(p)));
 
         // Pre-compute SMA data (must outlive the datasets vec)
-        let sma20_data = if app.show_sma { compute_sma(&stock_data.prices, 20) } else { Vec::new() };
-        let sma50_data = if app.show_sma { compute_sma(&stock_data.prices, 50) } else { Vec::new() };
-
-        let mut datasets = vec![
-            Dataset::default()
-                .name(stock_data.symbol.as_str())
-                .marker(symbols::Marker::Braille)
-                .graph_type(GraphType::Line)
-                .style(Style::default().fg(price_color))
-                .data(&chart_data),
-        ];
+        let mut overlays = indicator_overlays(app);
+        for overlay in overlays.iter_mut() {
+            let points = xmap.points(visible_points(&overlay.points, index_bounds));
+            overlay.points = scale.apply_points(points);
+        }
 
-        if app.show_sma {
-            if !sma20_data.is_empty() {
-                datasets.push(
-                    Dataset::default()
-                        .name("SMA20")
-                        .marker(symbols::Marker::Braille)
-                        .graph_type(GraphType::Line)
-                        .style(Style::default().fg(Color::Cyan))
-                        .data(&sma20_data),
-                );
+        // 52-week reference lines, only when they fall inside the visible y-range
+        let reference_line = |level: Option<f64>| -> Vec<(f64, f64)> {
+            match level.filter(|&p| p > 0.0 || !scale.log).map(|p| scale.apply(p)) {
+                Some(y) if y >= y_bounds[0] && y <= y_bounds[1] => {
+                    let steps = 80;
+                    let [lo, hi] = x_bounds;
+                    (0..=steps).map(|i| (lo + (hi - lo) * i as f64 / steps as f64, y)).collect()
+                }
+                _ => Vec::new(),
+            }
+        };
+        let high_52w_data = reference_line(stock_data.fifty_two_week_high);
+        let low_52w_data  = reference_line(stock_data.fifty_two_week_low);
+        let live_data = xmap.points(&scale.apply_points(live_segment(app)));
+        let baseline = chart_baseline(app, stock_data);
+        let baseline_data = reference_line(baseline);
+        // Overnight gaps on multi-day intraday charts break the line
+        let breaks: Vec<usize> = session_breaks(timestamps, app.display_zone()).into_iter().map(|i| range.start + i).collect();
+        let break_xs: Vec<f64> = breaks.iter().map(|&i| i as f64).collect();
+        // Pre- and post-market stretches are drawn dimmed, by the exchange's clock
+        let hours = extended_hours(app);
+        let is_regular = |x: f64| hours.is_none_or(|h| {
+            stock_data.timestamps.get(x as usize).is_none_or(|ts| h.is_regular_at(ts.timestamp()))
+        });
+        let mut runs: Vec<(Color, Vec<(f64, f64)>)> = Vec::new();
+        for session in split_sessions(&chart_data, &break_xs) {
+            for (regular, piece) in hours_segments(&session, is_regular) {
+                match baseline {
+                    _ if !regular => runs.push((EXTENDED_HOURS_COLOR, piece)),
+                    Some(base) => runs.extend(
+                        baseline_segments(&piece, scale.apply(base))
+                            .into_iter()
+                            .map(|(above, run)| (if above { Color::Green } else { Color::Red }, run)),
+                    ),
+                    None => runs.push((price_color, piece)),
+                }
             }
-            if !sma50_data.is_empty() {
+        }
+        // The open and close, where the dimmed stretches meet the regular session
+        let hour_marks: Vec<usize> = match hours {
+            Some(_) => (range.start + 1..range.end)
+                .filter(|&i| is_regular(i as f64) != is_regular((i - 1) as f64) && !breaks.contains(&i))
+                .collect(),
+            None => Vec::new(),
+        };
+        let runs: Vec<(Color, Vec<(f64, f64)>)> = runs.into_iter().map(|(color, run)| (color, xmap.points(&run))).collect();
+        let chart_data = xmap.points(&chart_data);
+
+        let mut datasets = Vec::new();
+        for (line, color) in [(&high_52w_data, Color::Green), (&low_52w_data, Color::Red), (&baseline_data, Color::Gray)] {
+            if !line.is_empty() {
                 datasets.push(
                     Dataset::default()
-                        .name("SMA50")
-                        .marker(symbols::Marker::Braille)
-                        .graph_type(GraphType::Line)
-                        .style(Style::default().fg(Color::Yellow))
-                        .data(&sma50_data),
+                        .marker(symbols::Marker::Dot)
+                        .graph_type(GraphType::Scatter)
+                        .style(Style::default().fg(color).add_modifier(Modifier::DIM))
+                        .data(line),
                 );
             }
         }
+        let named = runs.iter().position(|(color, _)| *color == price_color).filter(|_| baseline.is_none());
+        for (i, (color, run)) in runs.iter().enumerate() {
+            let dataset = Dataset::default()
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(*color))
+                .data(run);
+            datasets.push(if Some(i) == named { dataset.name(stock_data.symbol.as_str()) } else { dataset });
+        }
 
-        let first_date = format_timestamp(&first_ts, &app.timeframe);
-        let last_date  = format_timestamp(&last_ts,  &app.timeframe);
-        let mut x_labels = vec![Span::raw(first_date), Span::raw(last_date)];
+        if !live_data.is_empty() {
+            let live_color = if stock_data.period_change >= 0.0 { Color::LightGreen } else { Color::LightRed };
+            datasets.push(
+                Dataset::default()
+                    .marker(symbols::Marker::Braille)
+                    .graph_type(GraphType::Line)
+                    .style(Style::default().fg(live_color).add_modifier(Modifier::BOLD))
+                    .data(&live_data),
+            );
+        }
 
-        let data_len = stock_data.timestamps.len();
-        match app.timeframe {
-            TimeFrame::OneDay | TimeFrame::OneWeek => {
-                let mid = format_timestamp(stock_data.timestamps.get(data_len / 2).unwrap(), &app.timeframe);
-                x_labels.insert(1, Span::raw(mid));
-            }
-            TimeFrame::OneMonth | TimeFrame::OneYear => {
-                let q1  = format_timestamp(stock_data.timestamps.get(data_len / 4).unwrap(),     &app.timeframe);
-                let mid = format_timestamp(stock_data.timestamps.get(data_len / 2).unwrap(),     &app.timeframe);
-                let q3  = format_timestamp(stock_data.timestamps.get(data_len * 3 / 4).unwrap(), &app.timeframe);
-                x_labels.insert(1, Span::raw(q1));
-                x_labels.insert(2, Span::raw(mid));
-                x_labels.insert(3, Span::raw(q3));
-            }
-            TimeFrame::ThreeMonths => {
-                let t1  = format_timestamp(stock_data.timestamps.get(data_len / 3).unwrap(),     &app.timeframe);
-                let t2  = format_timestamp(stock_data.timestamps.get(data_len * 2 / 3).unwrap(), &app.timeframe);
-                x_labels.insert(1, Span::raw(t1));
-                x_labels.insert(2, Span::raw(t2));
-            }
+        for overlay in &overlays {
+            let dataset = Dataset::default()
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(overlay.color))
+                .data(&overlay.points);
+            datasets.push(if overlay.name.is_empty() { dataset } else { dataset.name(overlay.name.as_str()) });
         }
 
-        let y_labels = vec![
-            Span::raw(format!("${:.2}", min_price)),
-            Span::raw(format!("${:.2}", (min_price + max_price) / 2.0)),
-            Span::raw(format!("${:.2}", max_price)),
-        ];
+        let relative_breaks: Vec<usize> = breaks.iter().map(|&i| i - range.start).collect();
+        let x_labels = x_axis_labels(timestamps, &app.timeframe, app.display_zone(), x_label_room(f, plot_width), &relative_breaks, &xmap, range.start);
 
-        let title = if app.show_sma {
-            format!("{} - {}  SMA20 SMA50", stock_data.symbol, app.timeframe.display())
-        } else {
-            format!("{} - {}", stock_data.symbol, app.timeframe.display())
-        };
+        let cur = stock_data.currency_prefix();
+        // Same widths so the layout matches graph_left_offset; the real
+        // labels go in at nice price levels once the chart is drawn
+        let y_labels: Vec<Span> = y_labels.iter().map(|label| Span::raw(" ".repeat(label.chars().count()))).collect();
+
+        let mut title = format!("{} - {} ({})", stock_data.symbol, app.timeframe.display(), app.active_interval());
+        if app.full_screen {
+            title.push_str(&full_screen_quote(stock_data));
+        }
+        if scale.log {
+            title.push_str(" · log");
+        }
+        if smoothed.is_some() {
+            title.push_str(" (smoothed)");
+        }
+        if app.live_updates_enabled {
+            title.push_str(" · live");
+        }
+        if let Some(base) = baseline {
+            title.push_str(&format!(" · vs prev close {}", format_price(base, &stock_data.currency_prefix())));
+        }
+        if range.len() < stock_data.prices.len() {
+            title.push_str(&format!(
+                " · {} – {} ({}%)",
+                format_timestamp(&first_ts, &app.timeframe, app.display_zone()),
+                format_timestamp(&last_ts, &app.timeframe, app.display_zone()),
+                ZOOM_LEVELS[app.zoom_level],
+            ));
+        }
+        if let Some(ref other) = app.compare_symbol {
+            // Either still loading or no timestamps in common
+            let state = if app.compare_data.is_some() { "no common dates" } else { "loading" };
+            title.push_str(&format!("  · vs {} ({})", other, state));
+        }
+        for overlay in overlays.iter().filter(|o| !o.name.is_empty()) {
+            title.push_str(&format!("  {}", overlay.name));
+        }
+        if let Some(ref notice) = app.interval_notice {
+            title.push_str(&format!("  · {}", notice));
+        }
 
         let chart = Chart::new(datasets)
             .block(Block::default().borders(Borders::ALL).title(title))
             .x_axis(
                 Axis::default()
                     .style(Style::default().fg(Color::Gray))
-                    .bounds([0.0, max_x])
+                    .bounds(x_bounds)
                     .labels(x_labels),
             )
             .y_axis(
                 Axis::default()
                     .style(Style::default().fg(Color::Gray))
-                    .bounds([min_price - 5.0, max_price + 5.0])
+                    .bounds(y_bounds)
                     .labels(y_labels),
             );
 
         f.render_widget(chart, area);
+
+        let plot = Rect {
+            x: area.x + 1 + graph_left_offset(app, area),
+            y: area.y + 1,
+            width: area.width.saturating_sub(2 + graph_left_offset(app, area)),
+            // Less the x-axis line and its labels
+            height: area.height.saturating_sub(4),
+        };
+        let tag = format!(" {}{:.*} ", cur, price_decimals(scale.invert(y_bounds[1]) - scale.invert(y_bounds[0])), stock_data.current_price);
+        let norm = (y_bounds[1] - scale.apply(stock_data.current_price)) / (y_bounds[1] - y_bounds[0]);
+        render_price_marker(f, plot, norm, &tag, price_color);
+        if app.area_fill {
+            render_area_fill(f, plot, &chart_data, x_bounds, y_bounds, price_color);
+        }
+        if let Some(ref peaks) = peaks {
+            render_drawdown_shading(f, plot, &chart_data, peaks, x_bounds, y_bounds);
+        }
+        let dividers: Vec<(f64, String)> = breaks.iter()
+            .map(|&i| (xmap.divider_x(i), app.display_zone().format(&stock_data.timestamps[i], "%a %m/%d")))
+            .chain(hour_marks.iter().map(|&i| (xmap.x(i as f64), app.display_zone().format(&stock_data.timestamps[i], "%H:%M"))))
+            .collect();
+        render_session_dividers(f, plot, &dividers, x_bounds);
+        render_price_labels(f, Rect { x: area.x + 1, width: plot.x.saturating_sub(area.x + 2), ..plot }, &cur, y_bounds, scale);
+        render_cross_markers(f, plot, &sma_crosses(app), &xmap, x_bounds, y_bounds, scale);
+        if matches!(app.timeframe, TimeFrame::OneDay) {
+            render_extreme_markers(f, app, plot, &xmap, x_bounds, y_bounds, scale);
+        }
+        if let Some(index) = app.chart_cursor
+            && let Some(col) = x_to_col(xmap.x(index as f64), x_bounds, plot.width)
+        {
+            highlight_column(f, Rect { x: plot.x + col, width: 1, ..plot });
+        }
+        // Worst fall in the visible window, from the data rather than the drawn line
+        if peaks.is_some()
+            && let Some(dd) = crate::analytics::max_drawdown(prices, timestamps)
+        {
+            let legend = format!(
+                " Max DD −{:.2}%  {} → {} ",
+                dd.percent,
+                format_timestamp(&dd.peak, &app.timeframe, app.display_zone()),
+                format_timestamp(&dd.trough, &app.timeframe, app.display_zone()),
+            );
+            let row = Rect { height: 1, ..plot };
+            f.render_widget(Paragraph::new(Span::styled(legend, Style::default().fg(Color::Red))), row);
+        }
     } else if let Some(ref error) = app.error_message {
         let error_text = Paragraph::new(error.as_str())
             .style(Style::default().fg(Color::Red))
@@ -319,23 +1766,34 @@ fn render_volume_bars(f: &mut Frame, app: &App, area: Rect, left_offset: u16) {
     let bar_area_width = inner_width.saturating_sub(offset);
     if bar_area_width == 0 { return; }
 
-    let n = data.prices.len().min(data.volumes.len());
+    let range = visible_slice(app, data);
+    let start = range.start.min(data.volumes.len());
+    let end = range.end.min(data.volumes.len());
+    let n = end - start;
     if n == 0 { return; }
 
-    let scale_vol = data.volumes.iter().cloned().fold(0.0f64, f64::max);
+    let scale_vol = data.volumes[start..end].iter().cloned().fold(0.0f64, f64::max);
     if scale_vol == 0.0 { return; }
 
     // Mirror ratatui's x-axis mapping: data index i → pixel i*(width-1)/(n-1)
-    // so bar at column col uses data index col*(n-1)/(width-1)
-    let bars: Vec<(f64, bool)> = (0..bar_area_width)
+    // so bar at column col uses data index col*(n-1)/(width-1). Columns in
+    // a session gap stay blank.
+    let xmap = x_map(app, bar_area_width as u16);
+    let [lo, hi] = xmap.bounds([start as f64, (end - 1) as f64]);
+    let bars: Vec<Option<(f64, bool)>> = (0..bar_area_width)
         .map(|col| {
-            let i = if bar_area_width > 1 && n > 1 {
-                (col * (n - 1) / (bar_area_width - 1)).min(n - 1)
+            let x = if bar_area_width > 1 && n > 1 {
+                lo + col as f64 * (hi - lo) / (bar_area_width - 1) as f64
             } else {
-                0
+                lo
             };
+            if xmap.in_gap(x) {
+                return None;
+            }
+            // Nudged so exact multiples don't floor one bar short
+            let i = ((xmap.index(x) + 1e-9) as usize).clamp(start, end - 1);
             let is_up = i == 0 || data.prices[i] >= data.prices[i - 1];
-            (data.volumes[i], is_up)
+            Some((data.volumes[i], is_up))
         })
         .collect();
 
@@ -349,55 +1807,304 @@ fn render_volume_bars(f: &mut Frame, app: &App, area: Rect, left_offset: u16) {
             Span::raw(pre_axis.clone()),
             Span::styled("│", Style::default().fg(Color::DarkGray)),
         ];
-        for &(vol, is_up) in &bars {
-            // Compute height in eighths for sub-row precision
-            let total_eighths = ((vol / scale_vol) * bar_height as f64 * 8.0) as usize;
-            let full_rows     = total_eighths / 8;
-            let partial       = total_eighths % 8;
+        for &bar in &bars {
+            let Some((vol, is_up)) = bar else {
+                spans.push(Span::raw(" "));
+                continue;
+            };
+            let ch = volume_glyph(vol / scale_vol, bar_height, from_bottom);
             let color = if is_up { Color::Green } else { Color::Red };
 
-            let ch: &'static str = if from_bottom < full_rows {
-                "█"
-            } else if from_bottom == full_rows && partial > 0 {
-                match partial {
-                    1 => "▁", 2 => "▂", 3 => "▃", 4 => "▄",
-                    5 => "▅", 6 => "▆", 7 => "▇", _ => " ",
-                }
+            if ch == " " {
+                spans.push(Span::raw(" "));
             } else {
-                " "
-            };
+                spans.push(Span::styled(ch, Style::default().fg(color)));
+            }
+        }
+        lines.push(Line::from(spans));
+    }
+
+    f.render_widget(Paragraph::new(lines), inner);
+}
+
+/// Volume pane lined up with `render_candlestick_chart` drawn in `chart_area`.
+fn render_historical_candle_volume(f: &mut Frame, app: &App, chart_area: Rect, area: Rect) {
+    let candles = app.convert_to_candlesticks();
+    let chart_width = chart_area.width.saturating_sub(2 + CANDLE_GUTTER as u16 + 1) as usize;
+    let (range, columns) = candle_layout(candles.len(), CANDLE_GUTTER, chart_width, 0);
+    let visible: Vec<&Candlestick> = candles[range].iter().collect();
+    render_candle_volume(f, area, &visible, &columns, false);
+}
+
+/// Block character for one row of a volume bar `fraction` of the pane tall.
+/// Heights are counted in eighths so the top cell gets sub-row precision.
+pub(super) fn volume_glyph(fraction: f64, rows: usize, from_bottom: usize) -> &'static str {
+    let total_eighths = (fraction * rows as f64 * 8.0) as usize;
+    let full_rows     = total_eighths / 8;
+    let partial       = total_eighths % 8;
+
+    if from_bottom < full_rows {
+        "█"
+    } else if from_bottom == full_rows && partial > 0 {
+        match partial {
+            1 => "▁", 2 => "▂", 3 => "▃", 4 => "▄",
+            5 => "▅", 6 => "▆", 7 => "▇", _ => " ",
+        }
+    } else {
+        " "
+    }
+}
+
+/// Where candle columns sit inside a chart's inner area.
+pub(super) struct CandleColumns {
+    /// Blank cells before the first candle (the price label gutter and any
+    /// centring).
+    pub left: usize,
+    /// Cells per candle, including `gap` leading blanks.
+    pub width: usize,
+    pub gap: usize,
+}
+
+impl CandleColumns {
+    /// Cells of a candle's body.
+    pub fn bar_width(&self) -> usize {
+        self.width.saturating_sub(self.gap).max(1)
+    }
+
+    /// First body column of the `offset`-th shown candle.
+    pub fn x(&self, offset: usize) -> usize {
+        self.left + offset * self.width + self.gap
+    }
+}
+
+/// Widest slot a candle gets, gap included.
+const MAX_CANDLE_SLOT: usize = 7;
+
+/// The candles that fit in `width` cells after a `left` gutter, ending
+/// `scroll` candles before the newest, and where they go. Slots widen to
+/// share out the space (3 to `MAX_CANDLE_SLOT` cells, the widest with a
+/// two-cell gap) and a run too short to fill the width is centred. Shared by
+/// the historical and live charts and the panes lined up under them.
+pub(super) fn candle_layout(count: usize, left: usize, width: usize, scroll: usize) -> (std::ops::Range<usize>, CandleColumns) {
+    let shown = (width / 3).min(count);
+    let end = count.saturating_sub(scroll).max(shown);
+    let slot = width.checked_div(shown).unwrap_or(3).clamp(3, MAX_CANDLE_SLOT);
+    let gap = if slot >= 6 { 2 } else { 1 };
+    let centring = width.saturating_sub(shown * slot) / 2;
+    (end - shown..end, CandleColumns { left: left + centring, width: slot, gap })
+}
+
+/// Volume bars under a candlestick chart, one per candle and scaled to the
+/// largest visible volume. With `highlight_last` the forming candle is drawn
+/// in yellow, matching the live chart.
+pub(super) fn render_candle_volume(
+    f: &mut Frame,
+    area: Rect,
+    candles: &[&Candlestick],
+    columns: &CandleColumns,
+    highlight_last: bool,
+) {
+    let block = Block::default()
+        .borders(Borders::LEFT | Borders::RIGHT | Borders::BOTTOM)
+        .title("Volume");
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let rows = inner.height as usize;
+    let max_vol = candles.iter().map(|c| c.volume).max().unwrap_or(0);
+    if rows == 0 || max_vol == 0 { return; }
+
+    let bar_width = columns.bar_width();
+    let lines: Vec<Line> = (0..rows)
+        .map(|row| {
+            let from_bottom = rows - 1 - row;
+            let mut spans = vec![Span::raw(" ".repeat(columns.left))];
+            for (i, candle) in candles.iter().enumerate() {
+                let ch = volume_glyph(candle.volume as f64 / max_vol as f64, rows, from_bottom);
+                let color = if highlight_last && i == candles.len() - 1 {
+                    Color::Yellow
+                } else if candle.close >= candle.open {
+                    Color::Green
+                } else {
+                    Color::Red
+                };
+                spans.push(Span::raw(" ".repeat(columns.gap)));
+                spans.push(Span::styled(ch.repeat(bar_width), Style::default().fg(color)));
+            }
+            Line::from(spans)
+        })
+        .collect();
+
+    f.render_widget(Paragraph::new(lines), inner);
+}
+
+/// Candles drawn with half-block characters, so each row holds two price
+/// levels. `to_norm` maps a price to 0.0 at the top of `plot` and 1.0 at the
+/// bottom. Shared by the historical and live candle charts; cells between
+/// candles are left alone. With `hollow_up`, rising candles get an outlined
+/// body so direction doesn't depend on colour.
+fn render_candle_bodies(
+    f: &mut Frame,
+    plot: Rect,
+    candles: &[&Candlestick],
+    columns: &CandleColumns,
+    to_norm: impl Fn(f64) -> f64,
+    highlight_last: bool,
+    hollow_up: bool,
+) {
+    let half_rows = plot.height as usize * 2;
+    if half_rows == 0 { return; }
+    let to_half = |price: f64| ((to_norm(price) * half_rows as f64).max(0.0) as usize).min(half_rows - 1);
+
+    let right = (plot.x + plot.width) as usize;
+    let bar_width = columns.bar_width();
+    let buf = f.buffer_mut();
+    for (i, candle) in candles.iter().enumerate() {
+        let x = plot.x as usize + columns.x(i);
+        if x >= right { break; }
+        let color = if highlight_last && i == candles.len() - 1 {
+            Color::Yellow
+        } else if candle.close >= candle.open {
+            Color::Green
+        } else {
+            Color::Red
+        };
+        let (high, low) = (to_half(candle.high), to_half(candle.low));
+        let (body_top, body_bottom) = (to_half(candle.open.max(candle.close)), to_half(candle.open.min(candle.close)));
+        let hollow = hollow_up && candle.close > candle.open;
+        for row in high / 2..=low / 2 {
+            let symbol = candle_glyph(row, [high, low], [body_top, body_bottom]);
+            for cx in x..(x + bar_width).min(right) {
+                let symbol = match symbol {
+                    "█" | "▀" | "▄" if hollow => {
+                        if bar_width == 1 { "┃" } else if cx == x || cx == x + bar_width - 1 { "│" } else { " " }
+                    }
+                    other => other,
+                };
+                buf[(cx as u16, plot.y + row as u16)].set_symbol(symbol).set_fg(color);
+            }
+        }
+    }
+}
+
+/// The same candles as `render_candle_bodies` drawn on a Braille canvas, two
+/// dots across and four down per cell, so wicks and bodies keep their detail
+/// on short terminals. Only painted cells are written.
+fn render_braille_candles(
+    f: &mut Frame,
+    plot: Rect,
+    candles: &[&Candlestick],
+    columns: &CandleColumns,
+    to_norm: impl Fn(f64) -> f64,
+    highlight_last: bool,
+    hollow_up: bool,
+) {
+    use ratatui::widgets::canvas::{Canvas, Line as CanvasLine};
+
+    if plot.width == 0 || plot.height == 0 { return; }
+    let bar_width = columns.bar_width();
+    let canvas = Canvas::default()
+        .marker(symbols::Marker::Braille)
+        // Half a cell per dot column, so x = cell + 0.5 is a cell's right dot
+        .x_bounds([0.0, plot.width as f64 - 0.5])
+        .y_bounds([0.0, 1.0])
+        .paint(|ctx| {
+            for (i, candle) in candles.iter().enumerate() {
+                let left = columns.x(i) as f64;
+                if left >= plot.width as f64 { break; }
+                let color = if highlight_last && i == candles.len() - 1 {
+                    Color::Yellow
+                } else if candle.close >= candle.open {
+                    Color::Green
+                } else {
+                    Color::Red
+                };
+                let y = |price: f64| 1.0 - to_norm(price).clamp(0.0, 1.0);
+                // The body is 2 * bar_width dots across; the wick takes the
+                // dot just left of its middle
+                let wick_x = left + (bar_width - 1) as f64 / 2.0;
+                let (top, bottom) = (y(candle.open.max(candle.close)), y(candle.open.min(candle.close)));
+                ctx.draw(&CanvasLine { x1: wick_x, y1: y(candle.low), x2: wick_x, y2: bottom, color });
+                ctx.draw(&CanvasLine { x1: wick_x, y1: top, x2: wick_x, y2: y(candle.high), color });
+                let right = left + (bar_width * 2 - 1) as f64 / 2.0;
+                if hollow_up && candle.close > candle.open {
+                    // Outline only: both sides, top and bottom
+                    for (x1, y1, x2, y2) in [(left, bottom, left, top), (right, bottom, right, top), (left, top, right, top), (left, bottom, right, bottom)] {
+                        ctx.draw(&CanvasLine { x1, y1, x2, y2, color });
+                    }
+                    continue;
+                }
+                for dot in 0..bar_width * 2 {
+                    let x = left + dot as f64 / 2.0;
+                    ctx.draw(&CanvasLine { x1: x, y1: bottom, x2: x, y2: top, color });
+                }
+            }
+        });
+    f.render_widget(canvas, plot);
+}
+
+/// Glyph for one row of a candle whose wick spans half-rows `wick` and body
+/// `body` (top, bottom; half-row 2r is the upper half of row r). A body
+/// thinner than half a row — a doji — is a tick across the wick.
+fn candle_glyph(row: usize, wick: [usize; 2], body: [usize; 2]) -> &'static str {
+    let (upper, lower) = (row * 2, row * 2 + 1);
+    if body[0] == body[1] && row == body[0] / 2 {
+        return match (wick[0] < body[0], wick[1] > body[1]) {
+            (true, true) => "┼",
+            (true, false) => "┴",
+            (false, true) => "┬",
+            (false, false) => "─",
+        };
+    }
+    let in_body = |half: usize| half >= body[0] && half <= body[1];
+    let in_wick = |half: usize| half >= wick[0] && half <= wick[1];
+    match (in_body(upper), in_body(lower)) {
+        (true, true) => "█",
+        (true, false) => "▀",
+        (false, true) => "▄",
+        (false, false) => match (in_wick(upper), in_wick(lower)) {
+            (true, true) => "│",
+            (true, false) => "╵",
+            (false, true) => "╷",
+            (false, false) => " ",
+        },
+    }
+}
 
-            if ch == " " {
-                spans.push(Span::raw(" "));
-            } else {
-                spans.push(Span::styled(ch, Style::default().fg(color)));
+/// Dots for an overlay on a candle chart at `(candle, norm)` points, with
+/// `norm` as in `render_candle_bodies`. Candle bodies, block or Braille, stay
+/// on top.
+fn render_candle_overlay(
+    f: &mut Frame,
+    plot: Rect,
+    points: impl Iterator<Item = (usize, f64)>,
+    columns: &CandleColumns,
+    color: Color,
+) {
+    let right = (plot.x + plot.width) as usize;
+    let bar_width = columns.bar_width();
+    let buf = f.buffer_mut();
+    for (i, norm) in points {
+        if !(0.0..=1.0).contains(&norm) { continue; }
+        let row = ((norm * plot.height as f64) as u16).min(plot.height.saturating_sub(1));
+        let x = plot.x as usize + columns.x(i);
+        for cx in x..(x + bar_width).min(right) {
+            let cell = &mut buf[(cx as u16, plot.y + row)];
+            let braille = cell.symbol().chars().next().is_some_and(|c| ('\u{2801}'..='\u{28FF}').contains(&c));
+            if !braille && !matches!(cell.symbol(), "█" | "▀" | "▄") {
+                cell.set_symbol("·").set_fg(color);
             }
         }
-        lines.push(Line::from(spans));
     }
-
-    f.render_widget(Paragraph::new(lines), inner);
 }
 
 fn render_footer(f: &mut Frame, app: &App, area: ratatui::layout::Rect) {
-    use ratatui::layout::{Constraint, Direction, Layout};
-
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([Constraint::Length(3), Constraint::Length(2)])
-        .split(area);
-
-    // Row 1 — shared nav bar with toggle indicators for v/i
+    // Row 1 — shared nav bar with toggle indicators for v/i/m
     let vol_style = if app.show_volume {
         Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD | Modifier::REVERSED)
     } else {
         Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
     };
-    let sma_style = if app.show_sma {
-        Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD | Modifier::REVERSED)
-    } else {
-        Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
-    };
 
     let nav = Line::from(vec![
         nav_key("←/→"), Span::raw(" Timeframe   "),
@@ -406,129 +2113,1156 @@ fn render_footer(f: &mut Frame, app: &App, area: ratatui::layout::Rect) {
         nav_key("a"),   Span::raw(" Alert   "),
         nav_key("r"),   Span::raw(" Refresh   "),
         Span::styled("v", vol_style), Span::raw(" Vol   "),
-        Span::styled("i", sma_style), Span::raw(" SMA   "),
+        nav_key("i"),   Span::raw(" Indicators   "),
+        nav_key("t"),   Span::raw(" Interval   "),
+        nav_key("x"),   Span::raw(" Export   "),
         nav_key("s"),   Span::raw(" Search   "),
         nav_key("b"),   Span::raw(" Back   "),
         nav_key("q"),   Span::raw(" Quit"),
     ]);
+
+    // A one-line footer shows whichever row matters most right now
+    if area.height < 3 {
+        let line = match (&app.flash, app.chart_cursor) {
+            (Some((message, _)), _) => Line::from(Span::styled(message.clone(), Style::default().fg(Color::Green))),
+            (None, Some(index)) => cursor_status_line(app, index),
+            (None, None) => nav.alignment(Alignment::Center),
+        };
+        f.render_widget(Paragraph::new(line), area);
+        return;
+    }
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Length(2)])
+        .split(area);
     let nav_bar = Paragraph::new(nav)
         .block(Block::default().borders(Borders::ALL))
         .alignment(Alignment::Center);
     f.render_widget(nav_bar, chunks[0]);
 
-    // Row 2 — alert status
-    let alert_line = if let Some(alert) = app.alert_for_symbol(&app.symbol) {
+    // Row 2 — flash confirmation, else alert status
+    let alert_line = if let Some((ref message, _)) = app.flash {
+        Line::from(Span::styled(format!("  {}", message), Style::default().fg(Color::Green)))
+    } else if let Some(alert) = app.alert_for_symbol(&app.symbol) {
+        let more = match app.alert_count(&app.symbol) {
+            1 => String::new(),
+            n => format!(" (+{} more)", n - 1),
+        };
         if alert.triggered {
             Line::from(Span::styled(
-                format!("  ⚡ {} crossed ${:.2} — press a to clear", alert.symbol, alert.target),
+                format!("  ⚡ {} {} reached{} — A: alerts", alert.symbol, alert.condition(), more),
                 Style::default().fg(Color::Black).bg(Color::Yellow).add_modifier(Modifier::BOLD),
             ))
         } else {
-            let direction = if alert.above { "↑" } else { "↓" };
             Line::from(Span::styled(
-                format!("  Alert: ${:.2} {}  (a: clear)", alert.target, direction),
+                format!("  Alert: {}{}  (A: alerts)", alert.condition(), more),
                 Style::default().fg(Color::Yellow),
             ))
         }
     } else {
         Line::from(Span::styled("  No alert set", Style::default().fg(Color::DarkGray)))
     };
-    f.render_widget(Paragraph::new(alert_line), chunks[1]);
+    let mut lines = vec![alert_line];
+    if let Some(index) = app.chart_cursor {
+        lines.push(cursor_status_line(app, index));
+    } else {
+        let last_bar = app.indicator_values.closes.len().checked_sub(1);
+        if let Some(readout) = last_bar.and_then(|i| indicator_readout_line(app, i)) {
+            lines.push(readout);
+        }
+    }
+    f.render_widget(Paragraph::new(lines), chunks[1]);
+}
+
+/// Date and price at the crosshair bar (full OHLC and volume in candlestick
+/// mode), followed by the enabled indicators there.
+fn cursor_status_line(app: &App, index: usize) -> Line<'static> {
+    let cur = app.stock_data.as_ref().map_or_else(|| "$".to_string(), |d| d.currency_prefix());
+    let label = |text: &str| Span::styled(text.to_string(), Style::default().fg(Color::Gray));
+    let value = |text: String, color: Color| Span::styled(text, Style::default().fg(color));
+
+    let mut spans = vec![Span::styled("  ┃ ", Style::default().fg(Color::Cyan))];
+    if let Some(ts) = app.indicator_values.timestamps.get(index) {
+        spans.push(value(app.display_zone().format(ts, "%Y-%m-%d %H:%M"), Color::Cyan));
+        spans.push(Span::raw("  "));
+    }
+    let candle = app.show_candlesticks.then(|| app.convert_to_candlesticks().get(index).cloned()).flatten();
+    if let Some(c) = candle {
+        spans.extend([
+            label("O "), value(format!("{} ", format_price(c.open, &cur)), Color::White),
+            label("H "), value(format!("{} ", format_price(c.high, &cur)), Color::Green),
+            label("L "), value(format!("{} ", format_price(c.low, &cur)), Color::Red),
+            label("C "), value(format!("{} ", format_price(c.close, &cur)), Color::Cyan),
+            label("Vol "), value(compact_number(c.volume as f64), Color::White),
+        ]);
+    } else if let Some(&price) = app.indicator_values.closes.get(index) {
+        spans.push(value(format_price(price, &cur), Color::White));
+        let volume = app.stock_data.as_ref().and_then(|d| d.volumes.get(index)).copied().unwrap_or(0.0);
+        if volume > 0.0 {
+            spans.push(label("  Vol "));
+            spans.push(value(compact_number(volume), Color::White));
+        }
+    }
+    if let Some(readout) = indicator_readout_line(app, index) {
+        spans.extend(readout.spans);
+    }
+    Line::from(spans)
+}
+
+/// "SMA20 123.45  RSI 54.2  EMA21 –" for the enabled indicators at bar `index`,
+/// or `None` with nothing enabled.
+fn indicator_readout_line(app: &App, index: usize) -> Option<Line<'static>> {
+    let readout = app.indicator_readout(index);
+    if readout.is_empty() {
+        return None;
+    }
+    let mut spans = vec![Span::raw(" ")];
+    for (name, value) in readout {
+        spans.push(Span::styled(format!(" {} ", name), Style::default().fg(Color::Gray)));
+        let text = match value {
+            Some(v) if name == "OBV" => compact_number(v),
+            Some(v) if name == "RV" => format!("{:.1}%", v),
+            Some(v) => format!("{:.2}", v),
+            None => "–".to_string(),
+        };
+        spans.push(Span::styled(text, Style::default().fg(Color::White)));
+    }
+    Some(Line::from(spans))
 }
 
-fn format_timestamp(dt: &DateTime<Utc>, timeframe: &TimeFrame) -> String {
+fn format_timestamp(dt: &DateTime<Utc>, timeframe: &TimeFrame, zone: DisplayZone) -> String {
     let fmt = match timeframe {
         TimeFrame::OneDay => "%m/%d %H:%M",
-        TimeFrame::OneWeek | TimeFrame::OneMonth | TimeFrame::ThreeMonths => "%m/%d",
+        TimeFrame::OneWeek | TimeFrame::OneMonth | TimeFrame::ThreeMonths
+        | TimeFrame::SixMonths | TimeFrame::YearToDate => "%m/%d",
         TimeFrame::OneYear => "%m/%Y",
+        TimeFrame::FiveYears | TimeFrame::Max => "%Y",
     };
-    dt.with_timezone(&Local).format(fmt).to_string()
+    zone.format(dt, fmt)
+}
+
+/// Width to lay x-axis labels out over: the plot's, or none on a narrow
+/// terminal so only the two end labels are kept.
+fn x_label_room(f: &Frame, plot_width: u16) -> u16 {
+    if f.area().width < NARROW_WIDTH { 0 } else { plot_width }
+}
+
+/// Cells budgeted per x-axis label, gap included.
+const X_LABEL_WIDTH: usize = 12;
+
+/// Evenly spaced x-axis labels for `timestamps` across a plot `width` cells
+/// wide, as many as fit without overlapping. ratatui spreads labels evenly
+/// over the x bounds, so label `k` comes from the matching fraction of the
+/// series. A series shorter than that gets one label per point. A label
+/// within half a step of a session break (`breaks`, indices into
+/// `timestamps`) shows the opening bar of that session instead. With session
+/// gaps, labels are spaced evenly over the mapped positions, `xmap` taking
+/// bar `start + i` for `timestamps[i]`, so a label in a gap names the session
+/// after it.
+fn x_axis_labels(timestamps: &[DateTime<Utc>], timeframe: &TimeFrame, zone: DisplayZone, width: u16, breaks: &[usize], xmap: &XMap, start: usize) -> Vec<Span<'static>> {
+    let count = (width as usize / X_LABEL_WIDTH).max(2).min(timestamps.len());
+    let last = timestamps.len().saturating_sub(1);
+    let half_step = last / (2 * count.saturating_sub(1).max(1));
+    let [lo, hi] = xmap.bounds([start as f64, (start + last) as f64]);
+    (0..count)
+        .map(|k| if count > 1 { lo + k as f64 * (hi - lo) / (count - 1) as f64 } else { lo })
+        .map(|x| ((xmap.index(x) - start as f64).round().max(0.0) as usize).min(last))
+        .map(|i| breaks.iter().copied().filter(|b| b.abs_diff(i) <= half_step).min_by_key(|b| b.abs_diff(i)).unwrap_or(i))
+        .map(|i| Span::raw(format_timestamp(&timestamps[i], timeframe, zone)))
+        .collect()
+}
+
+/// A candlestick chart for `render_candlestick_chart`: the candles and
+/// whatever is drawn over and around them. Shared by the historical and
+/// live views.
+pub(super) struct CandleChart<'a> {
+    pub title: String,
+    pub candles: &'a [&'a Candlestick],
+    /// Candles the view is scrolled back from the newest
+    pub scroll: usize,
+    /// The newest candle is still forming; it's drawn in yellow while in view
+    pub forming: bool,
+    pub log_scale: bool,
+    pub overlays: &'a [Overlay],
+    /// Markers for matched patterns (indices into `candles`) on a row under
+    /// the candles, or `None` for no marker row
+    pub patterns: Option<&'a [(usize, CandlePattern)]>,
+    pub pivots: Option<&'a PivotLevels>,
+    /// Index into `candles` of the crosshair
+    pub cursor: Option<usize>,
+    pub braille: bool,
+    pub hollow_up: bool,
+    /// A price gutter, time axis, last-close marker and OHLC legend, for the
+    /// historical chart; the live one has its readout in the footer
+    pub axes: Option<CandleAxes<'a>>,
 }
 
-fn render_candlestick_chart(f: &mut Frame, candles: &[Candlestick], area: Rect, title: String, x_labels: Vec<Span>, _symbol: &str) {
-    if candles.is_empty() { return; }
+pub(super) struct CandleAxes<'a> {
+    pub cur: &'a str,
+    pub x_labels: Vec<Span<'a>>,
+    pub zone: DisplayZone,
+}
 
-    let max_price  = candles.iter().map(|c| c.high).fold(f64::NEG_INFINITY, f64::max);
-    let min_price  = candles.iter().map(|c| c.low).fold(f64::INFINITY, f64::min);
-    let price_range = max_price - min_price;
-    if price_range == 0.0 { return; }
+/// Price labels take this many cells left of the candles when there are axes.
+const CANDLE_GUTTER: usize = 9;
 
-    let block = Block::default().borders(Borders::ALL).title(title);
+/// Draw `chart` in `area`. The price range is that of the candles in view,
+/// padded as the line chart's is.
+pub(super) fn render_candlestick_chart(f: &mut Frame, area: Rect, chart: &CandleChart) {
+    let block = Block::default().borders(Borders::ALL).title(chart.title.clone());
     let inner = block.inner(area);
     f.render_widget(block, area);
 
-    let chart_height  = inner.height.saturating_sub(3) as usize;
-    let chart_width   = inner.width.saturating_sub(10) as usize;
-    if chart_height == 0 || chart_width == 0 { return; }
-
-    let max_candles   = chart_width / 2;
-    let display_start = if candles.len() > max_candles { candles.len() - max_candles } else { 0 };
-    let displayed     = &candles[display_start..];
-    let candle_width  = if !displayed.is_empty() { (chart_width / displayed.len()).max(2) } else { 2 };
-
-    let price_label_rows   = [0, chart_height / 4, chart_height / 2, chart_height * 3 / 4, chart_height.saturating_sub(1)];
-    let price_label_values = [
-        format!("${:.2}", max_price),
-        format!("${:.2}", max_price - price_range * 0.25),
-        format!("${:.2}", max_price - price_range * 0.5),
-        format!("${:.2}", max_price - price_range * 0.75),
-        format!("${:.2}", min_price),
+    // Axes take the gutter, a spacer for the markers and the time row
+    let candles = chart.candles;
+    let (gutter, chart_width, chart_height) = match chart.axes {
+        Some(_) => (CANDLE_GUTTER, inner.width.saturating_sub(CANDLE_GUTTER as u16 + 1), inner.height.saturating_sub(3)),
+        None => (0, inner.width, inner.height.saturating_sub(u16::from(chart.patterns.is_some()))),
+    };
+    if candles.is_empty() || chart_width < 5 || chart_height == 0 { return; }
+
+    let (range, columns) = candle_layout(candles.len(), gutter, chart_width as usize, chart.scroll);
+    let display_start = range.start;
+    let displayed = &candles[range.clone()];
+    let live_edge = range.end == candles.len();
+
+    let max_price = displayed.iter().map(|c| c.high).fold(f64::NEG_INFINITY, f64::max);
+    let min_price = displayed.iter().map(|c| c.low).fold(f64::INFINITY, f64::min);
+    let scale = YScale::new(chart.log_scale, min_price);
+    let [y_bottom, y_top] = padded_bounds(min_price, max_price, scale, None);
+    let to_norm = |price: f64| (y_top - scale.apply(price)) / (y_top - y_bottom);
+
+    if let Some(patterns) = chart.patterns {
+        let mut spans = vec![Span::raw(" ".repeat(columns.left))];
+        for i in range.clone() {
+            spans.push(Span::raw(" ".repeat(columns.gap)));
+            spans.push(match patterns.iter().find(|(p, _)| *p == i) {
+                Some(&(_, pattern)) => {
+                    let (symbol, color) = pattern_marker(pattern);
+                    Span::styled(format!("{:^width$}", symbol, width = columns.bar_width()), Style::default().fg(color))
+                }
+                None => Span::raw(" ".repeat(columns.bar_width())),
+            });
+        }
+        let marker_row = Rect { y: inner.y + chart_height, height: 1, ..inner };
+        f.render_widget(Paragraph::new(Line::from(spans)), marker_row);
+    }
+
+    if let Some(ref axes) = chart.axes {
+        // Price labels at round levels, on the row the candles put that price in
+        let mut lines: Vec<Line> = vec![Line::from(""); chart_height as usize];
+        for (level, label) in axis_levels([y_bottom, y_top], scale, chart_height, axes.cur) {
+            let row = ((to_norm(level) * chart_height as f64) as usize).min(chart_height as usize - 1);
+            lines[row] = Line::from(Span::styled(format!("{:>8} ", label), Style::default().fg(Color::Gray)));
+        }
+        f.render_widget(Paragraph::new(lines), Rect { height: chart_height, ..inner });
+
+        // The time axis spans the candles, which may be centred in a wide chart
+        let run = (displayed.len() * columns.width).max(3);
+        let label = |i: usize| axes.x_labels.get(i).map(|s| s.content.as_ref()).unwrap_or("");
+        let time_line = Line::from(vec![
+            Span::raw(" ".repeat(columns.left)),
+            Span::styled(format!("{:width$}", label(0), width = run / 3), Style::default().fg(Color::Gray)),
+            Span::styled(format!("{:^width$}", label(axes.x_labels.len() / 2), width = run / 3), Style::default().fg(Color::Gray)),
+            Span::styled(format!("{:>width$}", label(axes.x_labels.len().saturating_sub(1)), width = run / 3), Style::default().fg(Color::Gray)),
+        ]);
+        f.render_widget(Paragraph::new(time_line), Rect { y: inner.y + chart_height + 1, height: 1, ..inner });
+    }
+
+    let plot = Rect { height: chart_height, ..inner };
+    let highlight_last = chart.forming && live_edge;
+    if chart.braille {
+        render_braille_candles(f, plot, displayed, &columns, to_norm, highlight_last, chart.hollow_up);
+    } else {
+        render_candle_bodies(f, plot, displayed, &columns, to_norm, highlight_last, chart.hollow_up);
+    }
+    // Levels outside the candle range are dropped rather than pinned to the
+    // top/bottom row
+    for overlay in chart.overlays {
+        let points = overlay.points.iter()
+            .filter(|&&(x, p)| range.contains(&(x as usize)) && p >= min_price && p <= max_price)
+            .map(|&(x, p)| (x as usize - display_start, to_norm(p)));
+        render_candle_overlay(f, plot, points, &columns, overlay.color);
+    }
+    if let Some(pivots) = chart.pivots {
+        render_pivot_lines(f, plot, pivots, [scale.invert(y_bottom), scale.invert(y_top)], to_norm);
+    }
+
+    if let Some(ref axes) = chart.axes {
+        if let Some(last) = displayed.last() {
+            let decimals = price_decimals(scale.invert(y_top) - scale.invert(y_bottom));
+            let color = if last.close >= last.open { Color::Green } else { Color::Red };
+            let candles_area = Rect { x: plot.x + gutter as u16, width: chart_width.min(plot.width.saturating_sub(gutter as u16)), ..plot };
+            render_price_marker(f, candles_area, to_norm(last.close), &format!(" {}{:.*} ", axes.cur, decimals, last.close), color);
+        }
+
+        // Legend for the selected candle, or the latest without a crosshair
+        if let Some(candle) = chart.cursor.and_then(|i| candles.get(i)).or(candles.last()) {
+            let color = if candle.close >= candle.open { Color::Green } else { Color::Red };
+            let mut spans = vec![
+                Span::styled(
+                    format!(" {} ", axes.zone.format(&candle.timestamp, "%m/%d %H:%M")),
+                    Style::default().fg(color).add_modifier(Modifier::BOLD),
+                ),
+            ];
+            spans.extend(ohlc_spans(candle, axes.cur, color));
+            spans.push(Span::styled("  Vol:", Style::default().fg(Color::Gray)));
+            spans.push(Span::styled(format!("{} ", compact_number(candle.volume as f64)), Style::default().fg(Color::White)));
+            let legend = Rect { x: inner.x + gutter as u16, y: inner.y, width: inner.width.saturating_sub(gutter as u16), height: 1 };
+            f.render_widget(Paragraph::new(Line::from(spans)), legend);
+        }
+    }
+
+    // Crosshair over the selected candle, when it's scrolled into view
+    if let Some(offset) = chart.cursor.filter(|i| range.contains(i)).map(|i| i - display_start) {
+        let x = plot.x + columns.x(offset) as u16;
+        if x < plot.x + plot.width {
+            let width = (columns.bar_width() as u16).min(plot.x + plot.width - x);
+            highlight_column(f, Rect { x, width, ..plot });
+        }
+    }
+}
+
+/// Dashed rows for the pivot levels within `[lo, hi]`, placed by `to_norm`
+/// as the candles are, drawn only over empty cells so candles stay on top.
+/// Labels sit at the left edge, away from the forming candle.
+fn render_pivot_lines(f: &mut Frame, plot: Rect, pivots: &PivotLevels, [lo, hi]: [f64; 2], to_norm: impl Fn(f64) -> f64) {
+    let buf = f.buffer_mut();
+    for (name, level) in pivots.levels() {
+        if level < lo || level > hi {
+            continue;
+        }
+        // Same row mapping as the candles: row r covers prices just below its top
+        let row = (to_norm(level) * plot.height as f64) as u16;
+        if row >= plot.height {
+            continue;
+        }
+        let y = plot.y + row;
+        let color = match name {
+            "P" => Color::Yellow,
+            n if n.starts_with('R') => Color::Red,
+            _ => Color::Green,
+        };
+        for x in plot.x..plot.x + plot.width {
+            let cell = &mut buf[(x, y)];
+            if cell.symbol() == " " {
+                cell.set_symbol("╌").set_style(Style::default().fg(Color::DarkGray));
+            }
+        }
+        let label = format!(" {} {:.2} ", name, level);
+        let width = label.chars().count() as u16;
+        if width <= plot.width {
+            buf.set_string(plot.x, y, label, Style::default().fg(color));
+        }
+    }
+}
+
+/// "O:… H:… L:… C:…" for a candle, as in the live candle footer; the close
+/// takes `close_color`.
+pub(super) fn ohlc_spans(candle: &Candlestick, cur: &str, close_color: Color) -> Vec<Span<'static>> {
+    vec![
+        Span::styled("O:", Style::default().fg(Color::Gray)),
+        Span::styled(format!("{} ", format_price(candle.open, cur)), Style::default().fg(Color::White)),
+        Span::styled("H:", Style::default().fg(Color::Gray)),
+        Span::styled(format!("{} ", format_price(candle.high, cur)), Style::default().fg(Color::Green)),
+        Span::styled("L:", Style::default().fg(Color::Gray)),
+        Span::styled(format!("{} ", format_price(candle.low, cur)), Style::default().fg(Color::Red)),
+        Span::styled("C:", Style::default().fg(Color::Gray)),
+        Span::styled(format_price(candle.close, cur), Style::default().fg(close_color)),
+    ]
+}
+
+/// Marker drawn under a candle that matched a pattern.
+fn pattern_marker(pattern: CandlePattern) -> (&'static str, Color) {
+    match pattern {
+        CandlePattern::Doji => ("+", Color::Yellow),
+        CandlePattern::Hammer => ("h", Color::Cyan),
+        CandlePattern::BullishEngulfing => ("▲", Color::Green),
+        CandlePattern::BearishEngulfing => ("▼", Color::Red),
+    }
+}
+
+/// Pattern matches over `candles`, or nothing while detection is switched off.
+pub(super) fn detect_patterns<'a>(app: &App, candles: impl Iterator<Item = &'a Candlestick> + Clone) -> Vec<(usize, CandlePattern)> {
+    if !app.show_patterns {
+        return Vec::new();
+    }
+    let opens: Vec<f64> = candles.clone().map(|c| c.open).collect();
+    let highs: Vec<f64> = candles.clone().map(|c| c.high).collect();
+    let lows: Vec<f64> = candles.clone().map(|c| c.low).collect();
+    let closes: Vec<f64> = candles.map(|c| c.close).collect();
+    indicators::candle_patterns(&opens, &highs, &lows, &closes)
+}
+
+/// Most recent pattern matches with their candle times: the historical candles
+/// on the chart, closed candles in live mode.
+pub fn render_patterns_popup(f: &mut Frame, app: &App) {
+    use ratatui::widgets::{Clear, List, ListItem};
+
+    const SHOWN: usize = 10;
+
+    let historical;
+    let candles: Vec<&Candlestick> = match app.state {
+        super::AppState::LiveCandles | super::AppState::LiveTicker => app.live_candles.iter().collect(),
+        _ => {
+            historical = app.convert_to_candlesticks();
+            historical.iter().collect()
+        }
+    };
+    let opens: Vec<f64> = candles.iter().map(|c| c.open).collect();
+    let highs: Vec<f64> = candles.iter().map(|c| c.high).collect();
+    let lows: Vec<f64> = candles.iter().map(|c| c.low).collect();
+    let closes: Vec<f64> = candles.iter().map(|c| c.close).collect();
+    let found = indicators::candle_patterns(&opens, &highs, &lows, &closes);
+
+    let area = f.area();
+    let popup_width = area.width.min(44);
+    // One row per match plus the border
+    let popup_height = area.height.min(found.len().clamp(1, SHOWN) as u16 + 2);
+    let popup_area = Rect {
+        x: (area.width.saturating_sub(popup_width)) / 2,
+        y: (area.height.saturating_sub(popup_height)) / 2,
+        width: popup_width,
+        height: popup_height,
+    };
+
+    let mut items: Vec<ListItem> = found.iter()
+        .rev()
+        .take(SHOWN)
+        .map(|&(i, pattern)| {
+            let (symbol, color) = pattern_marker(pattern);
+            let when = app.display_zone().format(&candles[i].timestamp, "%b %-d %H:%M");
+            ListItem::new(Line::from(vec![
+                Span::styled(format!(" {} ", symbol), Style::default().fg(color)),
+                Span::styled(format!("{:<18}", pattern.name()), Style::default().fg(Color::White)),
+                Span::styled(when.to_string(), Style::default().fg(Color::Gray)),
+            ]))
+        })
+        .collect();
+    if items.is_empty() {
+        items.push(ListItem::new(Line::from(Span::styled(" No patterns found", Style::default().fg(Color::Gray)))));
+    }
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Candle patterns (Esc to close)")
+                .style(Style::default().bg(Color::Black)),
+        );
+
+    f.render_widget(Clear, popup_area);
+    f.render_widget(list, popup_area);
+}
+
+pub fn render_earnings_popup(f: &mut Frame, app: &App) {
+    use ratatui::widgets::{Clear, List, ListItem};
+
+    let Some(earnings) = app.stock_data.as_ref().and_then(|d| d.earnings.as_ref()) else { return; };
+
+    let area = f.area();
+    let popup_width = area.width.min(50);
+    let popup_height = area.height.min(10);
+    let popup_x = (area.width.saturating_sub(popup_width)) / 2;
+    let popup_y = (area.height.saturating_sub(popup_height)) / 2;
+
+    let popup_area = Rect {
+        x: popup_x,
+        y: popup_y,
+        width: popup_width,
+        height: popup_height,
+    };
+
+    let fmt_eps = |v: Option<f64>| v.map(|x| format!("{:.2}", x)).unwrap_or_else(|| "--".to_string());
+
+    let mut items = vec![ListItem::new(Line::from(Span::styled(
+        format!("{:<12}{:>10}{:>10}{:>12}", "Quarter", "Estimate", "Actual", "Surprise"),
+        Style::default().fg(Color::Gray),
+    )))];
+
+    if earnings.history.is_empty() {
+        items.push(ListItem::new(Line::from(Span::styled(
+            "No reported quarters",
+            Style::default().fg(Color::Gray),
+        ))));
+    }
+
+    for q in &earnings.history {
+        let (surprise, color) = match (q.estimate, q.actual) {
+            (Some(est), Some(act)) if est != 0.0 => {
+                let pct = (act - est) / est.abs() * 100.0;
+                let color = if act >= est { Color::Green } else { Color::Red };
+                (format!("{:+.1}%", pct), color)
+            }
+            _ => ("--".to_string(), Color::Gray),
+        };
+        items.push(ListItem::new(Line::from(vec![
+            Span::styled(
+                format!("{:<12}", q.period),
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(format!("{:>10}", fmt_eps(q.estimate)), Style::default().fg(Color::White)),
+            Span::styled(format!("{:>10}", fmt_eps(q.actual)), Style::default().fg(Color::White)),
+            Span::styled(format!("{:>12}", surprise), Style::default().fg(color)),
+        ])));
+    }
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Earnings: EPS Estimate vs Actual (Press 'E' to close)")
+                .style(Style::default().bg(Color::Black))
+        );
+
+    f.render_widget(Clear, popup_area);
+    f.render_widget(list, popup_area);
+}
+
+pub fn render_stats_popup(f: &mut Frame, app: &App) {
+    use ratatui::widgets::Clear;
+
+    let Some(ref data) = app.stock_data else { return; };
+    let Some(stats) = crate::analytics::series_stats(&data.prices, &data.timestamps) else { return; };
+
+    let area = f.area();
+    let popup_width = area.width.min(48);
+    let popup_height = area.height.min(11);
+    let popup_x = (area.width.saturating_sub(popup_width)) / 2;
+    let popup_y = (area.height.saturating_sub(popup_height)) / 2;
+
+    let popup_area = Rect {
+        x: popup_x,
+        y: popup_y,
+        width: popup_width,
+        height: popup_height,
+    };
+
+    let cur = data.currency_prefix();
+    let when = |dt: &DateTime<Utc>| format_timestamp(dt, &app.timeframe, app.display_zone());
+    let row = |label: &str, value: String, color: Color| {
+        Line::from(vec![
+            Span::styled(format!("  {:<16}", label), Style::default().fg(Color::Gray)),
+            Span::styled(value, Style::default().fg(color)),
+        ])
+    };
+    let return_color = if stats.total_return >= 0.0 { Color::Green } else { Color::Red };
+
+    let lines = vec![
+        row("High", format!("{}  {}", format_price(stats.high.0, &cur), when(&stats.high.1)), Color::Green),
+        row("Low", format!("{}  {}", format_price(stats.low.0, &cur), when(&stats.low.1)), Color::Red),
+        row("Mean", format_price(stats.mean, &cur), Color::White),
+        row("Std dev", format_price(stats.std_dev, &cur), Color::White),
+        row("Volatility (ann.)", stats.annualized_volatility.map_or("--".to_string(), |v| format!("{:.2}%", v)), Color::White),
+        row("Max drawdown", stats.max_drawdown.map_or("--".to_string(), |d| {
+            format!("-{:.2}%  {} → {}", d.percent, when(&d.peak), when(&d.trough))
+        }), Color::Red),
+        row("Total return", format!("{:+.2}%", stats.total_return), return_color),
     ];
 
-    let price_to_row = |price: f64| -> usize {
-        let norm = (max_price - price) / price_range;
-        ((norm * chart_height as f64) as usize).min(chart_height - 1)
+    let popup = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("{} · {} statistics (Esc to close)", data.symbol, app.timeframe.short_label()))
+                .style(Style::default().bg(Color::Black)),
+        );
+
+    f.render_widget(Clear, popup_area);
+    f.render_widget(popup, popup_area);
+}
+
+/// Full details of the bar under the crosshair, in the historical chart or the
+/// live candles: OHLC, range, body and trade count for candles, and the change
+/// from the bar before. Line charts only have closes and volume.
+pub fn render_candle_detail_popup(f: &mut Frame, app: &App) {
+    use ratatui::widgets::Clear;
+
+    let Some(ref data) = app.stock_data else { return; };
+    let cur = data.currency_prefix();
+    let zone = app.display_zone();
+    let live = matches!(app.state, super::AppState::LiveCandles);
+
+    // The selected candle, the close before it and whether it's still forming
+    let selection: Option<(Candlestick, Option<f64>, bool)> = if live {
+        let all: Vec<&Candlestick> = app.live_candles.iter().chain(app.current_candle.as_ref()).collect();
+        app.live_cursor.and_then(|i| {
+            let forming = app.current_candle.is_some() && i + 1 == all.len();
+            all.get(i).map(|&c| (c.clone(), i.checked_sub(1).map(|j| all[j].close), forming))
+        })
+    } else if app.show_candlesticks {
+        let candles = app.convert_to_candlesticks();
+        app.chart_cursor.and_then(|i| {
+            candles.get(i).map(|c| (c.clone(), i.checked_sub(1).map(|j| candles[j].close), false))
+        })
+    } else {
+        None
     };
 
-    let mut lines: Vec<Line> = Vec::new();
-    for row in 0..chart_height {
-        let mut spans = Vec::new();
+    let row = |label: &str, value: String, color: Color| {
+        Line::from(vec![
+            Span::styled(format!("  {:<9}", label), Style::default().fg(Color::Gray)),
+            Span::styled(value, Style::default().fg(color)),
+        ])
+    };
+    let change_row = |close: f64, prev: Option<f64>| match prev.filter(|&p| p != 0.0) {
+        Some(p) => {
+            let change = close - p;
+            let color = if change >= 0.0 { Color::Green } else { Color::Red };
+            row("Change", format!("{}{} ({:+.2}%)", sign_prefix(change), format_price(change.abs(), &cur), change / p * 100.0), color)
+        }
+        None => row("Change", "--".to_string(), Color::DarkGray),
+    };
 
-        let label = price_label_rows.iter().position(|&r| r == row)
-            .and_then(|idx| price_label_values.get(idx));
-        if let Some(lbl) = label {
-            spans.push(Span::styled(format!("{:>8} ", lbl), Style::default().fg(Color::Gray)));
-        } else {
-            spans.push(Span::raw("         "));
+    let (title, lines) = match selection {
+        Some((candle, prev, forming)) => {
+            let color = if candle.close >= candle.open { Color::Green } else { Color::Red };
+            let range = candle.high - candle.low;
+            let body = if range > 0.0 {
+                format!("{:.0}% of range", (candle.close - candle.open).abs() / range * 100.0)
+            } else {
+                "--".to_string()
+            };
+            let range_pct = if candle.low > 0.0 { format!(" ({:.2}%)", range / candle.low * 100.0) } else { String::new() };
+            let lines = vec![
+                row("Time", zone.format(&candle.timestamp, "%Y-%m-%d %H:%M"), Color::Cyan),
+                row("Open", format_price(candle.open, &cur), Color::White),
+                row("High", format_price(candle.high, &cur), Color::Green),
+                row("Low", format_price(candle.low, &cur), Color::Red),
+                row("Close", format_price(candle.close, &cur), color),
+                row("Range", format!("{}{}", format_price(range, &cur), range_pct), Color::White),
+                row("Body", body, Color::White),
+                row("Volume", compact_number(candle.volume as f64), Color::White),
+                // Historical candles are built from bars rather than trades
+                row(if live { "Trades" } else { "Bars" }, candle.trade_count.to_string(), Color::White),
+                change_row(candle.close, prev),
+            ];
+            let title = if forming { " Candle (forming) · Esc to close " } else { " Candle · Esc to close " };
+            (title, lines)
         }
+        None => {
+            let Some(i) = app.chart_cursor.filter(|&i| i < data.prices.len()) else { return; };
+            let lines = vec![
+                row("Time", data.timestamps.get(i).map_or("--".to_string(), |ts| zone.format(ts, "%Y-%m-%d %H:%M")), Color::Cyan),
+                row("Price", format_price(data.prices[i], &cur), Color::White),
+                row("Volume", data.volumes.get(i).map_or("--".to_string(), |&v| compact_number(v)), Color::White),
+                change_row(data.prices[i], i.checked_sub(1).map(|j| data.prices[j])),
+            ];
+            (" Bar · Esc to close ", lines)
+        }
+    };
+
+    let area = f.area();
+    let popup_width = area.width.min(40);
+    let popup_height = area.height.min(lines.len() as u16 + 2);
+    let popup_area = Rect {
+        x: (area.width.saturating_sub(popup_width)) / 2,
+        y: (area.height.saturating_sub(popup_height)) / 2,
+        width: popup_width,
+        height: popup_height,
+    };
+
+    let popup = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title)
+                .style(Style::default().bg(Color::Black)),
+        );
+
+    f.render_widget(Clear, popup_area);
+    f.render_widget(popup, popup_area);
+}
 
-        for candle in displayed {
-            let is_bullish   = candle.close >= candle.open;
-            let color        = if is_bullish { Color::Green } else { Color::Red };
-            let body_top     = candle.open.max(candle.close);
-            let body_bottom  = candle.open.min(candle.close);
-            let high_row     = price_to_row(candle.high);
-            let low_row      = price_to_row(candle.low);
-            let body_top_row = price_to_row(body_top);
-            let body_bot_row = price_to_row(body_bottom);
+pub fn render_indicators_popup(f: &mut Frame, app: &App) {
+    use ratatui::widgets::{Clear, List, ListItem};
+    use super::Indicator;
+
+    let area = f.area();
+    let popup_width = area.width.min(40);
+    // One row per indicator plus the border
+    let popup_height = area.height.min(Indicator::ALL.len() as u16 + 2);
+    let popup_x = (area.width.saturating_sub(popup_width)) / 2;
+    let popup_y = (area.height.saturating_sub(popup_height)) / 2;
+
+    let popup_area = Rect {
+        x: popup_x,
+        y: popup_y,
+        width: popup_width,
+        height: popup_height,
+    };
 
-            let (ch, col) = if row >= high_row && row <= low_row {
-                if row >= body_top_row && row <= body_bot_row { ("█", color) } else { ("│", color) }
+    let items: Vec<ListItem> = Indicator::ALL.iter()
+        .enumerate()
+        .map(|(i, &indicator)| {
+            let check = if app.indicator_enabled(indicator) { "[x]" } else { "[ ]" };
+            let style = if i == app.indicator_cursor {
+                Style::default().fg(Color::Black).bg(Color::Cyan)
             } else {
-                (" ", Color::White)
+                Style::default().fg(Color::White)
             };
-            spans.push(Span::styled(ch.repeat(candle_width.min(3)), Style::default().fg(col)));
-        }
-        lines.push(Line::from(spans));
-    }
+            ListItem::new(Line::from(Span::styled(
+                format!(" {} {}", check, app.indicator_label(indicator)),
+                style,
+            )))
+        })
+        .collect();
 
-    let time_line = Line::from(vec![
-        Span::raw("         "),
-        Span::styled(
-            format!("{:width$}", x_labels.first().map(|s| s.content.as_ref()).unwrap_or(""), width = chart_width / 3),
-            Style::default().fg(Color::Gray),
-        ),
-        Span::styled(
-            format!("{:^width$}", x_labels.get(x_labels.len() / 2).map(|s| s.content.as_ref()).unwrap_or(""), width = chart_width / 3),
-            Style::default().fg(Color::Gray),
-        ),
-        Span::styled(
-            format!("{:>width$}", x_labels.last().map(|s| s.content.as_ref()).unwrap_or(""), width = chart_width / 3),
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Indicators (Space toggle, p periods, Esc close)")
+                .style(Style::default().bg(Color::Black)),
+        );
+
+    f.render_widget(Clear, popup_area);
+    f.render_widget(list, popup_area);
+}
+
+pub fn render_appearance_popup(f: &mut Frame, app: &App) {
+    use ratatui::widgets::{Clear, List, ListItem};
+    use super::AppearanceOption;
+
+    let area = f.area();
+    let popup_width = area.width.min(44);
+    // One row per option plus the border
+    let popup_height = area.height.min(AppearanceOption::ALL.len() as u16 + 2);
+    let popup_area = Rect {
+        x: (area.width.saturating_sub(popup_width)) / 2,
+        y: (area.height.saturating_sub(popup_height)) / 2,
+        width: popup_width,
+        height: popup_height,
+    };
+
+    let items: Vec<ListItem> = AppearanceOption::ALL.iter()
+        .enumerate()
+        .map(|(i, &option)| {
+            let check = if app.appearance_enabled(option) { "[x]" } else { "[ ]" };
+            let style = if i == app.appearance_cursor {
+                Style::default().fg(Color::Black).bg(Color::Cyan)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            ListItem::new(Line::from(Span::styled(format!(" {} {}", check, option.name()), style)))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Appearance (Space toggle, Esc close)")
+                .style(Style::default().bg(Color::Black)),
+        );
+
+    f.render_widget(Clear, popup_area);
+    f.render_widget(list, popup_area);
+}
+
+pub fn render_params_popup(f: &mut Frame, app: &App) {
+    use ratatui::widgets::Clear;
+
+    let params = app.editable_params();
+    let area = f.area();
+    let popup_width = area.width.min(50);
+    // One row per parameter, a hint row and the border
+    let popup_height = area.height.min(params.len().max(1) as u16 + 4);
+    let popup_area = Rect {
+        x: (area.width.saturating_sub(popup_width)) / 2,
+        y: (area.height.saturating_sub(popup_height)) / 2,
+        width: popup_width,
+        height: popup_height,
+    };
+
+    let mut lines: Vec<Line> = params.iter()
+        .enumerate()
+        .map(|(i, &param)| {
+            let selected = i == app.param_cursor;
+            let value = if selected && app.param_editing {
+                format!("{}_", app.param_input_buffer)
+            } else {
+                app.param_values(param).iter().map(|n| n.to_string()).collect::<Vec<_>>().join(",")
+            };
+            let style = if selected && !app.param_editing {
+                Style::default().fg(Color::Black).bg(Color::Cyan)
+            } else if selected {
+                Style::default().fg(Color::White).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            Line::from(Span::styled(format!(" {:<26}{}", param.name(), value), style))
+        })
+        .collect();
+    if lines.is_empty() {
+        lines.push(Line::from(Span::styled(
+            " Switch an indicator on to tune it",
             Style::default().fg(Color::Gray),
-        ),
-    ]);
+        )));
+    }
     lines.push(Line::from(""));
-    lines.push(time_line);
+    lines.push(match &app.param_error {
+        Some(e) => Line::from(Span::styled(format!(" {}", e), Style::default().fg(Color::Red))),
+        None if app.param_editing => Line::from(Span::styled(
+            " Comma-separated bars  Enter: apply  Esc: cancel",
+            Style::default().fg(Color::DarkGray),
+        )),
+        None => Line::from(Span::styled(" Enter: edit  Esc: back", Style::default().fg(Color::DarkGray))),
+    });
+
+    let popup = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Indicator parameters")
+                .style(Style::default().bg(Color::Black)),
+        );
+
+    f.render_widget(Clear, popup_area);
+    f.render_widget(popup, popup_area);
+}
 
-    f.render_widget(Paragraph::new(lines), inner);
+pub fn render_profile_popup(f: &mut Frame, app: &App) {
+    use ratatui::widgets::{Clear, List, ListItem};
+
+    let area = f.area();
+    let popup_width = area.width.min(60);
+    let popup_height = area.height.min(10);
+    let popup_x = (area.width.saturating_sub(popup_width)) / 2;
+    let popup_y = (area.height.saturating_sub(popup_height)) / 2;
+
+    let popup_area = Rect {
+        x: popup_x,
+        y: popup_y,
+        width: popup_width,
+        height: popup_height,
+    };
+
+    let message = |text: &str| vec![ListItem::new(Line::from(Span::styled(
+        text.to_string(),
+        Style::default().fg(Color::Gray),
+    )))];
+
+    let items: Vec<ListItem> = if app.symbol.starts_with('^') {
+        message("No profile available for index symbols")
+    } else if app.live_polling_fallback {
+        message("Company profiles need FINNHUB_API_KEY")
+    } else {
+        match app.profiles.get(&app.symbol) {
+            None => message("Loading profile..."),
+            Some(None) => message("No profile available"),
+            Some(Some(p)) => {
+                let millions = |v: Option<f64>, unit: &str| match v {
+                    Some(m) if m >= 1_000_000.0 => format!("{:.2}T {}", m / 1_000_000.0, unit),
+                    Some(m) if m >= 1_000.0 => format!("{:.2}B {}", m / 1_000.0, unit),
+                    Some(m) => format!("{:.2}M {}", m, unit),
+                    None => "--".to_string(),
+                };
+                let rows = [
+                    ("Name", p.name.clone()),
+                    ("Industry", p.industry.clone()),
+                    ("Market cap", millions(p.market_cap, &p.currency)),
+                    ("Shares out", millions(p.shares_outstanding, "shares")),
+                    ("IPO", p.ipo.clone()),
+                    ("Website", p.website.clone()),
+                ];
+                rows.into_iter()
+                    .map(|(label, value)| {
+                        ListItem::new(Line::from(vec![
+                            Span::styled(
+                                format!("{:12}", label),
+                                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+                            ),
+                            Span::styled(value, Style::default().fg(Color::White)),
+                        ]))
+                    })
+                    .collect()
+            }
+        }
+    };
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("{} Profile (Press 'p' to close)", app.symbol))
+                .style(Style::default().bg(Color::Black))
+        );
+
+    f.render_widget(Clear, popup_area);
+    f.render_widget(list, popup_area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_bounds(actual: [f64; 2], expected: [f64; 2]) {
+        assert!(
+            (actual[0] - expected[0]).abs() < 1e-9 && (actual[1] - expected[1]).abs() < 1e-9,
+            "{:?} != {:?}", actual, expected
+        );
+    }
+
+    #[test]
+    fn padded_bounds_pads_by_five_percent_of_the_range() {
+        let linear = YScale::new(false, 100.0);
+        assert_bounds(padded_bounds(100.0, 200.0, linear, None), [95.0, 205.0]);
+        // An ATR replaces the percentage
+        assert_bounds(padded_bounds(100.0, 200.0, linear, Some(12.0)), [88.0, 212.0]);
+    }
+
+    #[test]
+    fn padded_bounds_keeps_a_penny_range_in_proportion() {
+        // A $0.03 range keeps its shape instead of vanishing into a fixed dollar
+        let [lo, hi] = padded_bounds(0.10, 0.13, YScale::new(false, 0.10), None);
+        assert_bounds([lo, hi], [0.0985, 0.1315]);
+        assert!((0.13 - 0.10) / (hi - lo) > 0.9);
+    }
+
+    #[test]
+    fn padded_bounds_opens_up_a_flat_series() {
+        // A tenth of a percent of the price either side
+        assert_bounds(padded_bounds(50.0, 50.0, YScale::new(false, 50.0), None), [49.95, 50.05]);
+        // And never less than the floor, even at zero
+        let [lo, hi] = padded_bounds(0.0, 0.0, YScale::new(false, 0.0), None);
+        assert!(lo < 0.0 && hi > 0.0);
+    }
+
+    fn candle(open: f64, high: f64, low: f64, close: f64, minute: i64) -> Candlestick {
+        let timestamp = DateTime::from_timestamp(1_700_000_000 / 60 * 60 + minute * 60, 0).unwrap();
+        Candlestick { open, high, low, close, volume: 1_000 + minute as u64 * 100, timestamp, trade_count: 0 }
+    }
+
+    /// Rising, falling, a doji, a long wick and a gap up.
+    fn fixture() -> Vec<Candlestick> {
+        vec![
+            candle(10.0, 10.8, 9.8, 10.6, 0),
+            candle(10.6, 10.7, 10.0, 10.1, 1),
+            candle(10.1, 10.5, 9.7, 10.1, 2),
+            candle(10.1, 10.3, 9.2, 10.2, 3),
+            candle(10.9, 11.4, 10.8, 11.2, 4),
+        ]
+    }
+
+    fn chart<'a>(candles: &'a [&'a Candlestick]) -> CandleChart<'a> {
+        CandleChart {
+            title: "Candles".to_string(),
+            candles,
+            scroll: 0,
+            forming: false,
+            log_scale: false,
+            overlays: &[],
+            patterns: None,
+            pivots: None,
+            cursor: None,
+            braille: false,
+            hollow_up: false,
+            axes: None,
+        }
+    }
+
+    /// `chart` drawn `width` x `height`, one string per row.
+    fn draw(chart: &CandleChart, width: u16, height: u16) -> Vec<String> {
+        let mut terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(width, height)).unwrap();
+        terminal.draw(|f| render_candlestick_chart(f, f.area(), chart)).unwrap();
+        let buf = terminal.backend().buffer();
+        (0..height)
+            .map(|y| (0..width).map(|x| buf[(x, y)].symbol()).collect::<String>().trim_end().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn snapshot_block_candles() {
+        let candles = fixture();
+        let candles: Vec<&Candlestick> = candles.iter().collect();
+        let expected = [
+            "┌Candles───────────────┐",
+            "│                  │││ │",
+            "│                  ███ │",
+            "│  │││ ╷╷╷         ▀▀▀ │",
+            "│  ███ ███ │││ ╷╷╷     │",
+            "│  ███ ███ ┼┼┼ ███     │",
+            "│  │││     │││ │││     │",
+            "│              │││     │",
+            "│              │││     │",
+            "└──────────────────────┘",
+        ];
+        assert_eq!(draw(&chart(&candles), 24, 10), expected);
+    }
+
+    #[test]
+    fn snapshot_braille_candles() {
+        let candles = fixture();
+        let candles: Vec<&Candlestick> = candles.iter().collect();
+        let expected = [
+            "┌Candles───────────────┐",
+            "│                  ⣀⣆⣀ │",
+            "│                  ⣿⣿⣿ │",
+            "│  ⣀⣆⣀ ⣀⣄⣀          ⠃  │",
+            "│  ⣿⣿⣿ ⣿⣿⣿  ⡇   ⡀      │",
+            "│  ⣿⣿⣿ ⠿⡿⠿ ⠤⡧⠤ ⠿⡿⠿     │",
+            "│   ⠃       ⡇   ⡇      │",
+            "│               ⡇      │",
+            "│               ⠃      │",
+            "└──────────────────────┘",
+        ];
+        assert_eq!(draw(&CandleChart { braille: true, ..chart(&candles) }, 24, 10), expected);
+    }
+
+    #[test]
+    fn snapshot_candles_with_axes() {
+        let candles = fixture();
+        let candles: Vec<&Candlestick> = candles.iter().collect();
+        let zone = DisplayZone { offset: chrono::FixedOffset::east_opt(0) };
+        let axes = CandleAxes { cur: "$", x_labels: vec![Span::raw("22:13"), Span::raw("22:15"), Span::raw("22:17")], zone };
+        let expected = [
+            "┌Candles───────────────────────────────────────────────────┐",
+            "│          11/14 22:14 O:$10.60 H:$10.70 L:$10.00 C:$10.10 │",
+            "│         ┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈████ $11.20  │",
+            "│                 ╷╷╷╷╷                       ▀▀▀▀▀        │",
+            "│                 █████  █████  ╷╷╷╷╷                      │",
+            "│                 █████  █████  │││││  ▄▄▄▄▄               │",
+            "│  $10.00         █████  ▀▀▀▀▀  ┼┼┼┼┼  ▀▀▀▀▀               │",
+            "│                 ╵╵╵╵╵         │││││  │││││               │",
+            "│                                      │││││               │",
+            "│                                      │││││               │",
+            "│                                                          │",
+            "│               22:13         22:15         22:17          │",
+            "│                                                          │",
+            "└──────────────────────────────────────────────────────────┘",
+        ];
+        assert_eq!(draw(&CandleChart { axes: Some(axes), cursor: Some(1), ..chart(&candles) }, 60, 14), expected);
+    }
+
+    #[test]
+    fn suffixed_price_table() {
+        let table = [
+            (250_000.0, "$", 50_000.0, "$250k"),
+            (152_500.0, "$", 5_000.0, "$152.5k"),
+            (100_250.0, "", 500.0, "100.25k"),
+            (150_000_000.0, "$", 20_000_000.0, "$150M"),
+            (123_450_000.0, "€", 500_000.0, "€123.45M"),
+        ];
+        for (level, cur, span, expected) in table {
+            assert_eq!(suffixed_price(level, cur, span), expected, "{}", level);
+        }
+    }
+
+    #[test]
+    fn baseline_segments_split_at_each_crossing() {
+        let segments = baseline_segments(&[(0.0, 9.0), (1.0, 11.0), (2.0, 12.0), (3.0, 8.0)], 10.0);
+        assert_eq!(segments, [
+            (false, vec![(0.0, 9.0), (0.5, 10.0)]),
+            (true, vec![(0.5, 10.0), (1.0, 11.0), (2.0, 12.0), (2.5, 10.0)]),
+            (false, vec![(2.5, 10.0), (3.0, 8.0)]),
+        ]);
+    }
+
+    #[test]
+    fn baseline_segments_edge_cases() {
+        assert!(baseline_segments(&[], 10.0).is_empty());
+        assert_eq!(baseline_segments(&[(0.0, 10.0), (1.0, 10.0)], 10.0), [(true, vec![(0.0, 10.0), (1.0, 10.0)])]);
+        assert_eq!(baseline_segments(&[(0.0, 9.0), (1.0, 9.0)], 10.0), [(false, vec![(0.0, 9.0), (1.0, 9.0)])]);
+        // Touching the baseline keeps the run going; leaving it from there starts the next
+        assert_eq!(
+            baseline_segments(&[(0.0, 11.0), (1.0, 10.0), (2.0, 12.0)], 10.0),
+            [(true, vec![(0.0, 11.0), (1.0, 10.0), (2.0, 12.0)])],
+        );
+        assert_eq!(baseline_segments(&[(0.0, 11.0), (1.0, 10.0), (2.0, 9.0)], 10.0), [
+            (true, vec![(0.0, 11.0), (1.0, 10.0)]),
+            (false, vec![(1.0, 10.0), (2.0, 9.0)]),
+        ]);
+    }
+
+    #[test]
+    fn chart_baseline_needs_1d_and_a_previous_close() {
+        let mut app = App::new();
+        app.baseline = true;
+        app.timeframe = TimeFrame::OneDay;
+        let mut data = crate::provider::fixture::fixture_data(crate::provider::fixture::FIXTURE_SYMBOL, "5m", 10);
+        data.previous_close = Some(99.5);
+        assert_eq!(chart_baseline(&app, &data), Some(99.5));
+        data.previous_close = Some(0.0);
+        assert_eq!(chart_baseline(&app, &data), None);
+        data.previous_close = Some(99.5);
+        app.timeframe = TimeFrame::OneWeek;
+        assert_eq!(chart_baseline(&app, &data), None);
+        app.timeframe = TimeFrame::OneDay;
+        app.baseline = false;
+        assert_eq!(chart_baseline(&app, &data), None);
+    }
+
+    /// Labels for `count` one-minute bars from 11/14 22:13 UTC.
+    fn minute_labels(count: usize, width: u16) -> Vec<String> {
+        let timestamps: Vec<DateTime<Utc>> = (0..count as i64)
+            .map(|i| DateTime::from_timestamp(1_700_000_000 / 60 * 60 + i * 60, 0).unwrap())
+            .collect();
+        let zone = DisplayZone { offset: chrono::FixedOffset::east_opt(0) };
+        x_axis_labels(&timestamps, &TimeFrame::OneDay, zone, width, &[], &XMap::identity(), 0)
+            .into_iter()
+            .map(|s| s.content.into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn x_axis_labels_single_point() {
+        assert_eq!(minute_labels(1, 120), ["11/14 22:13"]);
+        assert_eq!(minute_labels(1, 0), ["11/14 22:13"]);
+        assert!(minute_labels(0, 120).is_empty());
+    }
+
+    #[test]
+    fn x_axis_labels_one_per_point_when_short() {
+        assert_eq!(minute_labels(3, 120), ["11/14 22:13", "11/14 22:14", "11/14 22:15"]);
+        // No room still keeps both ends
+        assert_eq!(minute_labels(3, 0), ["11/14 22:13", "11/14 22:15"]);
+    }
+
+    #[test]
+    fn x_axis_labels_spread_over_a_long_series() {
+        // Five fit in 60 cells, at bars 0, 125, 250, 374 and 499
+        assert_eq!(
+            minute_labels(500, 60),
+            ["11/14 22:13", "11/15 00:18", "11/15 02:23", "11/15 04:27", "11/15 06:32"],
+        );
+        assert_eq!(minute_labels(500, 0), ["11/14 22:13", "11/15 06:32"]);
+    }
+
+    #[test]
+    fn padded_bounds_pads_log_scales_in_log_units() {
+        let log = YScale::new(true, 10.0);
+        assert!(log.log);
+        let expected = (100f64.ln() - 10f64.ln()) * 0.05;
+        assert_bounds(padded_bounds(10.0, 100.0, log, None), [10f64.ln() - expected, 100f64.ln() + expected]);
+    }
+
+    #[test]
+    fn penny_stock_axis_labels_are_distinct() {
+        let mut app = App::new();
+        let mut data = crate::provider::fixture::fixture_data("PENY", "5m", 60);
+        // Around three cents, moving a few hundredths of a cent
+        for p in data.prices.iter_mut() {
+            *p = 0.03 + (*p - 100.0) * 0.0002;
+        }
+        let (first, last) = (data.prices[0], *data.prices.last().unwrap());
+        data.current_price = last;
+        data.previous_close = Some(first);
+        data.base_historical_price = first;
+        data.change = last - first;
+        data.change_percent = data.change / first * 100.0;
+        app.symbol = "PENY".to_string();
+        app.state = crate::ui::AppState::Chart;
+        app.stock_data = Some(data);
+        app.refresh_indicators();
+        let screen = crate::ui::render_to_text(&app, 120, 40).unwrap();
+
+        // The gutter between the outer border and the axis line
+        let labels: Vec<&str> = screen.lines()
+            .filter_map(|row| row.split('│').nth(1))
+            .map(str::trim)
+            .filter(|cell| cell.starts_with('$'))
+            .collect();
+        assert_eq!(labels, ["$0.0330", "$0.0320", "$0.0310", "$0.0300"], "{}", screen);
+    }
 }
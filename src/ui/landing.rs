@@ -6,7 +6,33 @@ use ratatui::{
 	Frame,
 };
 
-use super::{App, LandingPanel, render_nav};
+use super::{App, LandingPanel, NARROW_WIDTH, footer_rows, format_price, render_nav};
+
+/// Cells in a popular-stock sparkline.
+const SPARKLINE_WIDTH: usize = 12;
+pub(super) const SPARK_LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Today's prices squeezed into `SPARKLINE_WIDTH` block characters, the last
+/// price of each slice scaled between the day's low and high. Blank until the
+/// background fetch has delivered.
+fn sparkline_span(app: &App, symbol: &str) -> Span<'static> {
+    let Some(spark) = app.sparklines.get(symbol).filter(|s| !s.prices.is_empty()) else {
+        return Span::raw(" ".repeat(SPARKLINE_WIDTH));
+    };
+    let prices = &spark.prices;
+    let lo = prices.iter().copied().fold(f64::INFINITY, f64::min);
+    let hi = prices.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let cells = SPARKLINE_WIDTH.min(prices.len());
+    let line: String = (1..=cells)
+        .map(|k| {
+            let price = prices[k * prices.len() / cells - 1];
+            let level = if hi > lo { ((price - lo) / (hi - lo) * 7.0).round() as usize } else { 3 };
+            SPARK_LEVELS[level.min(7)]
+        })
+        .collect();
+    let color = if spark.change_percent >= 0.0 { Color::Green } else { Color::Red };
+    Span::styled(format!("{:<width$}", line, width = SPARKLINE_WIDTH), Style::default().fg(color))
+}
 
 
 fn quote_spans(app: &App, symbol: &str) -> Vec<Span<'static>> {
@@ -18,7 +44,7 @@ fn quote_spans(app: &App, symbol: &str) -> Vec<Span<'static>> {
 
         let mut spans = vec![
             Span::styled(
-                format!("{:>9.2}", q.price),
+                format!("{:>9}", format_price(q.price, "")),
                 Style::default().fg(Color::White),
             ),
             Span::raw("  "),
@@ -48,13 +74,21 @@ fn quote_spans(app: &App, symbol: &str) -> Vec<Span<'static>> {
             } else {
                 let direction = if alert.above { "↑" } else { "↓" };
                 spans.push(Span::styled(
-                    format!("[!${:.0}{}]", alert.target, direction),
+                    format!("[!{}{:.0}{}]", alert.prefix(), alert.target, direction),
                     Style::default().fg(Color::Yellow),
                 ));
             }
         }
 
         spans
+    } else if let Some(spark) = app.sparklines.get(symbol) {
+        // No quote yet, but the sparkline fetch knows the day's change
+        let color = if spark.change_percent >= 0.0 { Color::Green } else { Color::Red };
+        let sign = if spark.change_percent >= 0.0 { "+" } else { "" };
+        vec![
+            Span::raw(" ".repeat(11)),
+            Span::styled(format!("{}{:.2}%", sign, spark.change_percent), Style::default().fg(color)),
+        ]
     } else {
         vec![Span::styled("  --", Style::default().fg(Color::DarkGray))]
     }
@@ -66,7 +100,7 @@ pub fn render_landing(f: &mut Frame, app: &App) {
         .constraints([
             Constraint::Length(5),
             Constraint::Min(0),
-            Constraint::Length(3),
+            Constraint::Length(footer_rows(f, 3)),
         ])
         .split(f.area());
 
@@ -91,8 +125,9 @@ pub fn render_landing(f: &mut Frame, app: &App) {
     f.render_widget(header, chunks[0]);
 
     // Main content
+    // Side by side panes are too cramped on a narrow terminal
     let main_chunks = Layout::default()
-        .direction(Direction::Horizontal)
+        .direction(if f.area().width < NARROW_WIDTH { Direction::Vertical } else { Direction::Horizontal })
         .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
         .split(chunks[1]);
 
@@ -121,6 +156,8 @@ pub fn render_landing(f: &mut Frame, app: &App) {
                     Style::default().fg(Color::White),
                 ),
                 Span::raw(" "),
+                sparkline_span(app, ticker),
+                Span::raw(" "),
             ];
             spans.extend(quote_spans(app, ticker));
             ListItem::new(Line::from(spans))
@@ -167,10 +204,47 @@ pub fn render_landing(f: &mut Frame, app: &App) {
                 Style::default().fg(Color::Gray),
             )),
         ];
+
+        let search_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(8), Constraint::Min(0)])
+            .split(main_chunks[1]);
+
         let search = Paragraph::new(search_text)
             .block(Block::default().borders(Borders::ALL).title("Search"))
             .alignment(Alignment::Left);
-        f.render_widget(search, main_chunks[1]);
+        f.render_widget(search, search_chunks[0]);
+
+        if !app.search_results.is_empty() {
+            let result_items: Vec<ListItem> = app
+                .search_results
+                .iter()
+                .map(|(symbol, name, exchange)| {
+                    ListItem::new(Line::from(vec![
+                        Span::styled(
+                            format!("{:<10}", symbol),
+                            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                        ),
+                        Span::styled(
+                            format!("{:<24}", truncate(name, 24)),
+                            Style::default().fg(Color::White),
+                        ),
+                        Span::styled(exchange.clone(), Style::default().fg(Color::DarkGray)),
+                    ]))
+                })
+                .collect();
+
+            let results = List::new(result_items)
+                .block(Block::default().borders(Borders::ALL).title("Matches  (↑/↓ to pick)"))
+                .highlight_style(
+                    Style::default()
+                        .bg(Color::DarkGray)
+                        .add_modifier(Modifier::BOLD),
+                )
+                .highlight_symbol(">> ");
+
+            f.render_stateful_widget(results, search_chunks[1], &mut app.search_state.clone());
+        }
     } else if app.watchlist.is_empty() {
         let hint = vec![
             Line::from(""),
@@ -233,7 +307,7 @@ pub fn render_landing(f: &mut Frame, app: &App) {
 
     // Footer
     if app.input_mode {
-        render_nav(f, chunks[2], &[("Enter", "Confirm"), ("Esc", "Cancel")]);
+        render_nav(f, chunks[2], &[("↑/↓", "Pick Match"), ("Enter", "Confirm"), ("Esc", "Cancel")]);
     } else {
         render_nav(f, chunks[2], &[
             ("Enter", "Select"), ("Tab", "Switch Panel"), ("s", "Search"),
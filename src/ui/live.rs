@@ -6,61 +6,86 @@ use ratatui::{
 	Frame,
 };
 
-use chrono::{Utc, Local};
+use chrono::Utc;
+use std::cmp::Ordering;
+use std::collections::VecDeque;
 
-use super::{App, WebSocketStatus, Candlestick, render_nav};
+use super::{App, AppState, WebSocketStatus, Candlestick, IndicatorPane, Trade, footer_rows, format_countdown, format_price, render_nav, sign_prefix};
+use super::landing::SPARK_LEVELS;
+use super::chart::{CandleChart, CandleColumns, EMA_COLORS, EXTENDED_HOURS_COLOR, MIN_CHART_HEIGHT, Overlay, PANE_HEIGHT, VOLUME_HEIGHT, detect_patterns, ohlc_spans, candle_layout, render_candle_volume, render_candlestick_chart, render_stochastic_pane, visible_points, volume_glyph};
+use crate::config::DisplayMode;
 
 pub fn render_live_ticker(f: &mut Frame, app: &App) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(5),
+            // One more header row than the candles view, for the activity sparkline
+            Constraint::Length(7),
             Constraint::Min(0),
-            Constraint::Length(3),
+            Constraint::Length(footer_rows(f, 3)),
         ])
         .split(f.area());
 
     // Header with current price
     render_live_header(f, app, chunks[0], "LIVE TICKER");
 
-    // Trade feed
+    // Only as many rows as fit; the buffer can hold thousands
+    render_trade_feed(f, app, chunks[1], chunks[1].height as usize);
+
+    // Footer
+    render_live_footer(f, app, chunks[2]);
+}
+
+/// The newest `limit` trades at or above the tape's size filter, newest
+/// first, with time, price, tick direction and size. Also shown beside the
+/// chart while it streams.
+pub(super) fn render_trade_feed(f: &mut Frame, app: &App, area: ratatui::layout::Rect, limit: usize) {
+    let min_size = app.tape_min_size();
+    let shown = |trade: &Trade| min_size.is_none_or(|min| trade.volume.is_some_and(|v| v >= min));
     let trades: Vec<ListItem> = if app.live_trades.is_empty() {
         vec![ListItem::new(Line::from(Span::styled(
             "Waiting for trades...",
             Style::default().fg(Color::Gray),
         )))]
     } else {
+        let cur = app.stock_data.as_ref().map_or_else(|| "$".to_string(), |d| d.currency_prefix());
+        let (up, down) = if app.display_mode == DisplayMode::Colorblind { (" ▲ ", " ▼ ") } else { (" ↑ ", " ↓ ") };
+        let hours = app.stock_data.as_ref().and_then(|d| d.market_hours.as_ref());
         app.live_trades
             .iter()
-            .map(|trade| {
-                let time = trade.timestamp.with_timezone(&Local).format("%H:%M:%S").to_string();
-                let direction = if let Some(prev) = app.live_trades.get(1) {
-                    if trade.price > prev.price {
-                        Span::styled(" ↑ ", Style::default().fg(Color::Green))
-                    } else if trade.price < prev.price {
-                        Span::styled(" ↓ ", Style::default().fg(Color::Red))
-                    } else {
-                        Span::styled(" - ", Style::default().fg(Color::Gray))
-                    }
-                } else {
-                    Span::styled(" - ", Style::default().fg(Color::Gray))
+            .enumerate()
+            .filter(|&(_, trade)| shown(trade))
+            .take(limit)
+            .map(|(i, trade)| {
+                let time = app.display_zone().format(&trade.timestamp, "%H:%M:%S");
+                let (direction, tick_color) = match tick_direction(&app.live_trades, i) {
+                    Some(Ordering::Greater) => (up, Color::Green),
+                    Some(Ordering::Less) => (down, Color::Red),
+                    _ => (" - ", Color::Gray),
                 };
+                let direction = Span::styled(direction, Style::default().fg(tick_color));
 
                 let vol_str = match trade.volume {
                     Some(v) if v > 0 => format!("{:>8}", format_volume(v)),
                     _ => "        ".to_string(),
                 };
 
-                ListItem::new(Line::from(vec![
+                // Pre- and post-market prints are dimmed, by the exchange's clock
+                let extended = hours.is_some_and(|h| !h.is_regular_at(trade.timestamp.timestamp()));
+                let price_style = if extended {
+                    Style::default().fg(EXTENDED_HOURS_COLOR)
+                } else {
+                    let color = if tick_color == Color::Gray { Color::White } else { tick_color };
+                    Style::default().fg(color).add_modifier(Modifier::BOLD)
+                };
+                let line = Line::from(vec![
                     Span::styled(time, Style::default().fg(Color::DarkGray)),
                     Span::raw("  "),
-                    Span::styled(
-                        format!("${:<10.2}", trade.price),
-                        Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
-                    ),
+                    Span::styled(format!("{:<11}", format_price(trade.price, &cur)), price_style),
                     direction,
                     Span::styled(vol_str, Style::default().fg(Color::Cyan)),
-                ]))
+                ]);
+                ListItem::new(if extended { line.style(Style::default().add_modifier(Modifier::DIM)) } else { line })
             })
             .collect()
     };
@@ -68,21 +93,46 @@ pub fn render_live_ticker(f: &mut Frame, app: &App) {
     let trades_list = List::new(trades).block(
         Block::default()
             .borders(Borders::ALL)
-            .title(format!("Recent Trades ({})", app.total_trade_count)),
+            .title(format!(
+                "Recent Trades ({}) · {}/{} trades{}",
+                app.total_trade_count,
+                app.live_trades.len(),
+                app.trade_buffer,
+                min_size.map_or(String::new(), |min| format!(" · ≥{} sh", min)),
+            )),
     );
-    f.render_widget(trades_list, chunks[1]);
+    f.render_widget(trades_list, area);
+}
 
-    // Footer
-    render_live_footer(f, chunks[2]);
+/// How trade `i` of the newest-first `trades` moved from the trade before it
+/// in time, whether or not that one is shown. `None` for the oldest.
+fn tick_direction(trades: &VecDeque<Trade>, i: usize) -> Option<Ordering> {
+    let prev = trades.get(i + 1)?;
+    trades.get(i)?.price.partial_cmp(&prev.price)
 }
 
 pub fn render_live_candles(f: &mut Frame, app: &App) {
+    // Lower panes collapse on short terminals, the stochastic one first
+    let footer_height = footer_rows(f, 5);
+    let mut room = f.area().height.saturating_sub(5 + footer_height + MIN_CHART_HEIGHT);
+    let show_vol = app.show_volume && room >= VOLUME_HEIGHT;
+    if show_vol {
+        room -= VOLUME_HEIGHT;
+    }
+    let show_counts = app.show_candle_counts && room >= COUNTS_HEIGHT;
+    if show_counts {
+        room -= COUNTS_HEIGHT;
+    }
+    let show_stoch = app.indicator_panes.contains(&IndicatorPane::Stochastic) && room >= PANE_HEIGHT;
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(5),
+            Constraint::Length(6),
             Constraint::Min(0),
-            Constraint::Length(5),
+            Constraint::Length(if show_counts { COUNTS_HEIGHT } else { 0 }),
+            Constraint::Length(if show_vol { VOLUME_HEIGHT } else { 0 }),
+            Constraint::Length(if show_stoch { PANE_HEIGHT } else { 0 }),
+            Constraint::Length(footer_height),
         ])
         .split(f.area());
 
@@ -100,56 +150,183 @@ pub fn render_live_candles(f: &mut Frame, app: &App) {
     }
 
     if all_candles.is_empty() {
-        let waiting = Paragraph::new("Waiting for trades to build candles...")
+        let message = if app.candles_pending { "Loading history…" } else { "Waiting for trades to build candles..." };
+        let waiting = Paragraph::new(message)
             .style(Style::default().fg(Color::Gray))
             .alignment(Alignment::Center)
             .block(Block::default().borders(Borders::ALL).title("Candlesticks"));
         f.render_widget(waiting, chart_area);
     } else {
-        // Render candlestick chart
-        render_candlestick_chart(f, chart_area, &all_candles, app.current_candle.is_some());
+        // EMA over closed candles only, so it steps once per candle rather than
+        // wobbling with every trade in the one still forming
+        let overlays: Vec<Overlay> = if app.show_ema {
+            let closes: Vec<f64> = app.live_candles.iter().map(|c| c.close).collect();
+            app.ema_periods.iter()
+                .zip(EMA_COLORS.iter())
+                .map(|(&period, &color)| Overlay { name: format!("EMA{}", period), color, points: crate::indicators::ema(&closes, period) })
+                .collect()
+        } else {
+            Vec::new()
+        };
+        // Patterns on closed candles only; the forming one would flicker in and out
+        let patterns = app.show_patterns.then(|| detect_patterns(app, app.live_candles.iter()));
+
+        let inner_width = chart_area.width.saturating_sub(2);
+        let (visible, columns) = candle_layout(all_candles.len(), 0, inner_width as usize, app.live_scroll);
+        let back = all_candles.len() - visible.end;
+        let (kept, capacity) = (app.live_candles.len(), app.candle_buffer);
+        let title = if back > 0 {
+            format!("Candlesticks · {}/{} candles · viewing {} candle{} back (paused view, any key for live)", kept, capacity, back, if back == 1 { "" } else { "s" })
+        } else {
+            format!("Candlesticks · {}/{} candles", kept, capacity)
+        };
+        render_candlestick_chart(f, chart_area, &CandleChart {
+            title,
+            candles: &all_candles,
+            scroll: app.live_scroll,
+            forming: app.current_candle.is_some(),
+            log_scale: false,
+            overlays: &overlays,
+            patterns: patterns.as_deref(),
+            pivots: app.pivot_levels.as_ref(),
+            cursor: app.live_cursor,
+            braille: app.use_braille_candles(f.area().height),
+            hollow_up: app.display_mode == DisplayMode::Colorblind,
+            axes: None,
+        });
+
+        let start = visible.start;
+        let forming = app.current_candle.is_some() && visible.end == all_candles.len();
+        if show_counts {
+            let strip = ratatui::layout::Rect { x: chunks[2].x + 1, width: inner_width, ..chunks[2] };
+            render_candle_counts(f, strip, &all_candles[visible.clone()], &columns, forming);
+        }
+        if show_vol {
+            render_candle_volume(f, chunks[3], &all_candles[visible], &columns, forming);
+        }
+        if show_stoch {
+            let highs: Vec<f64> = all_candles.iter().map(|c| c.high).collect();
+            let lows: Vec<f64> = all_candles.iter().map(|c| c.low).collect();
+            let closes: Vec<f64> = all_candles.iter().map(|c| c.close).collect();
+            // Candle i is centred on column x(i - start) + (bar - 1) / 2, one
+            // slot per index
+            let slot = columns.width as f64;
+            let lo = start as f64 - (columns.x(0) as f64 + (columns.bar_width() - 1) as f64 / 2.0) / slot;
+            let hi = lo + inner_width.saturating_sub(1).max(1) as f64 / slot;
+            let [period, smooth_k, smooth_d] = app.stochastic_periods;
+            let stoch = crate::indicators::stochastic(&highs, &lows, &closes, period, smooth_k, smooth_d);
+            let (k, d) = (visible_points(&stoch.k, [lo, hi]), visible_points(&stoch.d, [lo, hi]));
+            render_stochastic_pane(f, chunks[4], &stoch, k, d, app.stochastic_periods, [lo, hi], 0);
+        }
     }
 
     // Footer with OHLC info
-    render_candle_footer(f, app, chunks[2]);
+    render_candle_footer(f, app, chunks[5]);
 }
 
 
 fn render_live_header(f: &mut Frame, app: &App, area: ratatui::layout::Rect, mode_name: &str) {
-    let price = app.last_live_price.unwrap_or(0.0);
+    let price = app.last_live_price
+        .or_else(|| app.stock_data.as_ref().and_then(|d| d.regular_market_price))
+        .unwrap_or(0.0);
     let (change, change_pct) = if let Some(ref data) = app.stock_data {
         (data.change, data.change_percent)
     } else {
         (0.0, 0.0)
     };
+    let (title, cur) = match app.stock_data {
+        Some(ref data) => (data.description(), data.currency_prefix()),
+        None => (app.symbol.clone(), "$".to_string()),
+    };
 
     let price_color = if change >= 0.0 { Color::Green } else { Color::Red };
     let change_symbol = if change >= 0.0 { "▲" } else { "▼" };
+    // Colour-blind mode spells the direction out as a sign too
+    let signed = |value: f64| if app.display_mode == DisplayMode::Colorblind { sign_prefix(value) } else { "" };
 
+    let provider = app.ws_manager.as_ref().map_or("", |m| m.provider().name());
     let status_span = match &app.ws_status {
         WebSocketStatus::Connected { since } => {
             let secs = Utc::now().signed_duration_since(*since).num_seconds();
-            Span::styled(format!("[● {}s]", secs), Style::default().fg(Color::Green))
+            Span::styled(format!("[● {} {}s]", provider, secs), Style::default().fg(Color::Green))
+        }
+        WebSocketStatus::Stale { last_message } => {
+            let secs = Utc::now().signed_duration_since(*last_message).num_seconds();
+            Span::styled(format!("[STALE — no data {}s]", secs), Style::default().fg(Color::Yellow))
         }
         WebSocketStatus::Connecting => {
-            Span::styled("[CONNECTING...]", Style::default().fg(Color::Yellow))
+            Span::styled(format!("[CONNECTING {}...]", provider), Style::default().fg(Color::Yellow))
         }
-        WebSocketStatus::Reconnecting { attempt, .. } => {
-            Span::styled(format!("[RECONNECTING {}/5]", attempt), Style::default().fg(Color::Yellow))
+        WebSocketStatus::Reconnecting { attempt, max_attempts, next_retry_in } => {
+            let attempt = match max_attempts {
+                Some(max) => format!("{}/{}", attempt, max),
+                None => format!("#{}", attempt),
+            };
+            Span::styled(
+                format!("[RECONNECTING {} in {}s]", attempt, next_retry_in.as_secs_f64().ceil()),
+                Style::default().fg(Color::Yellow),
+            )
+        }
+        WebSocketStatus::Polling { interval } => {
+            Span::styled(format!("[DELAYED (poll {}s)]", interval.as_secs()), Style::default().fg(Color::Yellow))
+        }
+        WebSocketStatus::Replay { speed } => {
+            Span::styled(format!("[REPLAY {}×]", speed), Style::default().fg(Color::Magenta))
         }
+        WebSocketStatus::MarketClosed { reopens_at } => {
+            let secs = reopens_at.signed_duration_since(Utc::now()).num_seconds();
+            Span::styled(format!("[MARKET CLOSED — back in {}]", format_countdown(secs)), Style::default().fg(Color::Blue))
+        }
+        WebSocketStatus::Idle => Span::styled("[IDLE]", Style::default().fg(Color::Gray)),
         _ => Span::styled("[DISCONNECTED]", Style::default().fg(Color::Gray)),
     };
 
+    let breakout_span = match app.stock_data {
+        Some(ref data) if data.above_52w_high() => Span::styled(
+            "  ▲ NEW 52w HIGH",
+            Style::default().fg(Color::Black).bg(Color::Green).add_modifier(Modifier::BOLD),
+        ),
+        _ => Span::raw(""),
+    };
+
+    let closed_span = match app.market_closed_banner() {
+        Some(banner) if matches!(app.ws_status, WebSocketStatus::Idle) => Span::styled(
+            format!("  {} (r: connect anyway)", banner),
+            Style::default().fg(Color::DarkGray),
+        ),
+        Some(banner) => Span::styled(format!("  {}", banner), Style::default().fg(Color::DarkGray)),
+        None => Span::raw(""),
+    };
+
+    let pause_span = match app.live_pause {
+        Some(ref pause) => Span::styled(
+            format!("  PAUSED — {} trades buffered (Space: resume) ", pause.trade_count),
+            Style::default().fg(Color::Black).bg(Color::Yellow).add_modifier(Modifier::BOLD),
+        ),
+        None => Span::raw(""),
+    };
+
+    let record_span = match app.recorder {
+        Some(ref recorder) => Span::styled(
+            format!("  ● REC {}", recorder.records),
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ),
+        None => Span::raw(""),
+    };
+
     let alert_line = if let Some(alert) = app.alert_for_symbol(&app.symbol) {
+        let more = match app.alert_count(&app.symbol) {
+            1 => String::new(),
+            n => format!(" (+{} more)", n - 1),
+        };
         if alert.triggered {
             Line::from(Span::styled(
-                format!("⚡ ALERT: {} crossed ${:.2} — press 'a' to clear", alert.symbol, alert.target),
+                format!("⚡ ALERT: {} {} reached{} — A: alerts", alert.symbol, alert.condition(), more),
                 Style::default().fg(Color::Black).bg(Color::Yellow).add_modifier(Modifier::BOLD),
             ))
         } else {
-            let direction = if alert.above { "↑" } else { "↓" };
             Line::from(Span::styled(
-                format!("Alert: ${:.2} {} (a: clear)", alert.target, direction),
+                format!("Alert: {}{} (a: add, A: alerts)", alert.condition(), more),
                 Style::default().fg(Color::Yellow),
             ))
         }
@@ -157,22 +334,26 @@ fn render_live_header(f: &mut Frame, app: &App, area: ratatui::layout::Rect, mod
         Line::from(Span::styled("a: Set alert", Style::default().fg(Color::DarkGray)))
     };
 
-    let header_text = vec![
+    let mut header_text = vec![
         Line::from(vec![
             Span::styled(
-                format!("{} - {} ", app.symbol, mode_name),
+                format!("{} - {} ", title, mode_name),
                 Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
             ),
             status_span,
+            Span::styled(format!(" {}", app.time_zone_label()), Style::default().fg(Color::DarkGray)),
+            closed_span,
+            record_span,
+            pause_span,
         ]),
         Line::from(vec![
             Span::styled(
-                format!("${:.2}", price),
+                format_price(price, &cur),
                 Style::default().fg(price_color).add_modifier(Modifier::BOLD),
             ),
             Span::raw("  "),
             Span::styled(
-                format!("{} ${:.2} ({:.2}%)", change_symbol, change.abs(), change_pct.abs()),
+                format!("{} {sign}{} ({sign}{:.2}%)", change_symbol, format_price(change.abs(), &cur), change_pct.abs(), sign = signed(change)),
                 Style::default().fg(price_color),
             ),
             Span::raw("  "),
@@ -180,15 +361,80 @@ fn render_live_header(f: &mut Frame, app: &App, area: ratatui::layout::Rect, mod
                 format!("Vol: {}", format_volume(app.total_live_volume)),
                 Style::default().fg(Color::Cyan),
             ),
+            Span::raw("  "),
+            delta_span("Δ ", app.cumulative_delta),
+            trade_rate_span(app),
+            bid_ask_span(app),
+            breakout_span,
         ]),
-        alert_line,
+        session_line(app, &cur),
     ];
+    if matches!(app.state, AppState::LiveTicker) {
+        header_text.push(activity_line(app));
+    }
+    header_text.push(alert_line);
 
     let header = Paragraph::new(header_text)
         .block(Block::default().borders(Borders::ALL));
     f.render_widget(header, area);
 }
 
+/// High, low and VWAP over the recent trade buffer, plus the session's trade count.
+pub fn render_session_stats_popup(f: &mut Frame, app: &App) {
+    use ratatui::widgets::Clear;
+
+    let area = f.area();
+    let popup_width = area.width.min(44);
+    let popup_height = area.height.min(8);
+    let popup_x = (area.width.saturating_sub(popup_width)) / 2;
+    let popup_y = (area.height.saturating_sub(popup_height)) / 2;
+
+    let popup_area = ratatui::layout::Rect {
+        x: popup_x,
+        y: popup_y,
+        width: popup_width,
+        height: popup_height,
+    };
+
+    let trades: Vec<(f64, Option<u64>)> = app.live_trades.iter().map(|t| (t.price, t.volume)).collect();
+    let cur = app.stock_data.as_ref().map_or_else(|| "$".to_string(), |d| d.currency_prefix());
+    let row = |label: &str, value: String, color: Color| {
+        Line::from(vec![
+            Span::styled(format!("  {:<14}", label), Style::default().fg(Color::Gray)),
+            Span::styled(value, Style::default().fg(color)),
+        ])
+    };
+
+    let lines = match crate::analytics::session_stats(&trades) {
+        Some(stats) => vec![
+            row("High", format_price(stats.high, &cur), Color::Green),
+            row("Low", format_price(stats.low, &cur), Color::Red),
+            row("VWAP", stats.vwap.map_or("--".to_string(), |v| format_price(v, &cur)), Color::Cyan),
+            row("Trades", app.total_trade_count.to_string(), Color::White),
+            Line::from(Span::styled(
+                format!("  High/low/VWAP over the last {} updates", stats.trades),
+                Style::default().fg(Color::DarkGray),
+            )),
+            Line::from(Span::styled(
+                format!("  {} trades merged into 100ms batches", app.coalesced_trades),
+                Style::default().fg(Color::DarkGray),
+            )),
+        ],
+        None => vec![Line::from(Span::styled("  No trades yet", Style::default().fg(Color::Gray)))],
+    };
+
+    let popup = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("{} session (Esc to close)", app.symbol))
+                .style(Style::default().bg(Color::Black)),
+        );
+
+    f.render_widget(Clear, popup_area);
+    f.render_widget(popup, popup_area);
+}
+
 pub fn render_alert_input(f: &mut Frame, app: &App) {
     use ratatui::widgets::Clear;
 
@@ -207,15 +453,15 @@ pub fn render_alert_input(f: &mut Frame, app: &App) {
 
     let sym = &app.alert_target_symbol;
     let current_price = app.current_price_for(sym)
-        .map(|p| format!("Current: ${:.2}", p))
+        .map(|(p, currency)| format!("Current: {}{:.2}", crate::stock::currency_prefix(currency.as_deref()), p))
         .unwrap_or_default();
 
     let text = vec![
         Line::from(""),
         Line::from(Span::styled(current_price, Style::default().fg(Color::Gray))),
-        Line::from(""),
+        Line::from(Span::styled(">= 200, <= 180, or a bare price", Style::default().fg(Color::DarkGray))),
         Line::from(vec![
-            Span::styled("> $", Style::default().fg(Color::Yellow)),
+            Span::styled("> ", Style::default().fg(Color::Yellow)),
             Span::styled(
                 format!("{}_", app.alert_input_buffer),
                 Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
@@ -236,14 +482,101 @@ pub fn render_alert_input(f: &mut Frame, app: &App) {
     f.render_widget(popup, popup_area);
 }
 
-fn render_live_footer(f: &mut Frame, area: ratatui::layout::Rect) {
-    render_nav(f, area, &[("b", "Back"), ("l", "Switch"), ("h", "Help"), ("e", "Errors"), ("q", "Quit")]);
+/// Every alert, with its state, and the alerts fired this session.
+pub fn render_alert_list(f: &mut Frame, app: &App) {
+    use ratatui::widgets::Clear;
+
+    let area = f.area();
+    let popup_width = area.width.min(60);
+    let popup_height = area.height.min(18);
+    let popup_area = ratatui::layout::Rect {
+        x: area.width.saturating_sub(popup_width) / 2,
+        y: area.height.saturating_sub(popup_height) / 2,
+        width: popup_width,
+        height: popup_height,
+    };
+    f.render_widget(Clear, popup_area);
+
+    let log_rows = if app.alert_log.is_empty() { 0 } else { app.alert_log.len() as u16 + 2 };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(log_rows)])
+        .split(popup_area);
+
+    let items: Vec<ListItem> = if app.alerts.is_empty() {
+        vec![ListItem::new(Span::styled("No alerts — 'a' adds one", Style::default().fg(Color::Gray)))]
+    } else {
+        app.alerts.iter().map(|alert| {
+            let state = match alert.fired_at {
+                Some(at) if alert.triggered => Span::styled(
+                    format!("fired {}", app.display_zone().format(&at, "%m-%d %H:%M")),
+                    Style::default().fg(Color::Black).bg(Color::Yellow),
+                ),
+                _ => Span::styled("armed", Style::default().fg(Color::Green)),
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{:<10}", alert.symbol), Style::default().fg(Color::White).add_modifier(Modifier::BOLD)),
+                Span::styled(format!("{:<14}", alert.condition()), Style::default().fg(Color::Yellow)),
+                Span::styled(
+                    if alert.repeat { "repeat  " } else { "once    " },
+                    Style::default().fg(Color::Gray),
+                ),
+                state,
+            ]))
+        }).collect()
+    };
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Alerts (d: delete | r: once/repeat | Esc: close)")
+                .style(Style::default().bg(Color::Black)),
+        )
+        .highlight_style(Style::default().bg(Color::DarkGray));
+    f.render_stateful_widget(list, chunks[0], &mut app.alert_list_state.clone());
+
+    if log_rows > 0 {
+        let lines: Vec<Line> = app.alert_log.iter()
+            .map(|entry| Line::from(Span::styled(entry.clone(), Style::default().fg(Color::Yellow))))
+            .collect();
+        let log = Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Fired this session")
+                .style(Style::default().bg(Color::Black)),
+        );
+        f.render_widget(log, chunks[1]);
+    }
+}
+
+fn render_live_footer(f: &mut Frame, app: &App, area: ratatui::layout::Rect) {
+    render_flash_or_nav(f, app, area, &[("b", "Back"), ("l", "Switch"), ("x", "Export"), ("h", "Help"), ("e", "Errors"), ("q", "Quit")]);
 }
 
-pub fn render_live_mode_select(f: &mut Frame) {
+/// Nav bar, temporarily replaced by the flash message after an export. Leads
+/// with the reconnect key once the feed has given up.
+fn render_flash_or_nav(f: &mut Frame, app: &App, area: ratatui::layout::Rect, items: &[(&'static str, &'static str)]) {
+    match app.flash {
+        Some((ref message, _)) => {
+            let mut bar = Paragraph::new(Span::styled(message.as_str(), Style::default().fg(Color::Green)))
+                .alignment(Alignment::Center);
+            if area.height >= 3 {
+                bar = bar.block(Block::default().borders(Borders::ALL));
+            }
+            f.render_widget(bar, area);
+        }
+        None if app.can_reconnect() => {
+            let items: Vec<_> = std::iter::once(("r", "Reconnect")).chain(items.iter().copied()).collect();
+            render_nav(f, area, &items);
+        }
+        None => render_nav(f, area, items),
+    }
+}
+
+pub fn render_live_mode_select(f: &mut Frame, app: &App) {
     let area = f.area();
-    let popup_width = 40;
-    let popup_height = 9;
+    let popup_width = 44;
+    let popup_height = if app.live_polling_fallback { 12 } else { 10 };
     let popup_x = (area.width.saturating_sub(popup_width)) / 2;
     let popup_y = (area.height.saturating_sub(popup_height)) / 2;
 
@@ -254,7 +587,7 @@ pub fn render_live_mode_select(f: &mut Frame) {
         height: popup_height,
     };
 
-    let text = vec![
+    let mut text = vec![
         Line::from(""),
         Line::from(Span::styled(
             "Select Live Mode",
@@ -269,12 +602,35 @@ pub fn render_live_mode_select(f: &mut Frame) {
             Span::styled(" [2] ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
             Span::raw("Live Candles (1min OHLC)"),
         ]),
+        Line::from(vec![
+            Span::styled(" [3] ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+            Span::raw(if app.live_updates_enabled { "Stop live ticks on the chart" } else { "Live ticks on the chart" }),
+        ]),
         Line::from(""),
-        Line::from(Span::styled(
-            "Press ESC to cancel",
-            Style::default().fg(Color::Gray),
-        )),
     ];
+    if app.live_polling_fallback && crate::websocket::Provider::for_symbol(&app.symbol) == crate::websocket::Provider::Binance {
+        text.push(Line::from(Span::styled(
+            "Streaming from Binance —",
+            Style::default().fg(Color::Green),
+        )));
+        text.push(Line::from(Span::styled(
+            "no API key needed",
+            Style::default().fg(Color::Green),
+        )));
+    } else if app.live_polling_fallback {
+        text.push(Line::from(Span::styled(
+            "No FINNHUB_API_KEY set —",
+            Style::default().fg(Color::Yellow),
+        )));
+        text.push(Line::from(Span::styled(
+            "using delayed Yahoo polling",
+            Style::default().fg(Color::Yellow),
+        )));
+    }
+    text.push(Line::from(Span::styled(
+        "Press ESC to cancel",
+        Style::default().fg(Color::Gray),
+    )));
 
     let popup = Paragraph::new(text)
         .alignment(Alignment::Center)
@@ -288,114 +644,137 @@ pub fn render_live_mode_select(f: &mut Frame) {
     f.render_widget(popup, popup_area);
 }
 
+/// "RSI 54.3" over the closed candles plus the forming one, colored at 70/30.
+/// The historical seed from entering live mode usually covers the warm-up.
+fn live_rsi_spans(app: &App) -> Vec<Span<'static>> {
+    let closes: Vec<f64> = app.live_candles.iter()
+        .chain(app.current_candle.as_ref())
+        .map(|c| c.close)
+        .collect();
+    let label = Span::styled("   RSI ", Style::default().fg(Color::Gray));
+    match crate::indicators::rsi(&closes, app.rsi_period).last() {
+        Some(&(_, value)) => {
+            let color = if value >= 70.0 {
+                Color::Red
+            } else if value <= 30.0 {
+                Color::Green
+            } else {
+                Color::White
+            };
+            vec![label, Span::styled(format!("{:.1}", value), Style::default().fg(color).add_modifier(Modifier::BOLD))]
+        }
+        None => vec![label, Span::styled("—", Style::default().fg(Color::DarkGray))],
+    }
+}
+
 fn render_candle_footer(f: &mut Frame, app: &App, area: ratatui::layout::Rect) {
+    let cur = app.stock_data.as_ref().map_or_else(|| "$".to_string(), |d| d.currency_prefix());
+    // The crosshair's candle when there is one, otherwise the forming one
+    let selected = app.live_cursor.and_then(|i| app.live_candles.get(i)).map(|c| (c, true));
+    let mut ohlc_line = if let Some((candle, closed)) = selected.or(app.current_candle.as_ref().map(|c| (c, false))) {
+        let mut spans = vec![Span::raw("  ")];
+        if closed {
+            spans.push(Span::styled(
+                format!("{} ", app.display_zone().format(&candle.timestamp, "%H:%M")),
+                Style::default().fg(Color::Cyan),
+            ));
+        }
+        spans.extend(ohlc_spans(candle, &cur, Color::Cyan));
+        Line::from(spans)
+    } else {
+        Line::from(Span::styled("  Waiting for candle data...", Style::default().fg(Color::Gray)))
+    };
+    if let Some(left) = app.candle_secs_left(app.live_clock()) {
+        ohlc_line.spans.push(Span::styled(
+            format!("  {}:{:02} left in {} candle", left / 60, left % 60, app.candle_interval.to_string()),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    ohlc_line.spans.extend(live_rsi_spans(app));
+    if app.current_candle.is_some() {
+        ohlc_line.spans.push(Span::raw("  "));
+        ohlc_line.spans.push(delta_span("Δ candle ", app.candle_delta));
+    }
+
+    // A one-line footer keeps the candle readout and drops the nav bar
+    if area.height < 3 {
+        match app.flash {
+            Some((ref message, _)) => f.render_widget(Paragraph::new(Span::styled(message.as_str(), Style::default().fg(Color::Green))), area),
+            None => f.render_widget(Paragraph::new(ohlc_line), area),
+        }
+        return;
+    }
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Length(3), Constraint::Length(2)])
         .split(area);
-
-    render_nav(f, chunks[0], &[
-        ("←/→", "Interval"), ("b", "Back"), ("l", "Switch"), ("h", "Help"), ("e", "Errors"), ("q", "Quit")
+    render_flash_or_nav(f, app, chunks[0], &[
+        ("←/→", "Interval"), ("m", "EMA"), ("v", "Volume"), ("b", "Back"), ("l", "Switch"), ("x", "Export"), ("h", "Help"), ("e", "Errors"), ("q", "Quit")
     ]);
-
-    let ohlc_line = if let Some(ref candle) = app.current_candle {
-        Line::from(vec![
-            Span::styled("  O:", Style::default().fg(Color::Gray)),
-            Span::styled(format!("{:.2} ", candle.open), Style::default().fg(Color::White)),
-            Span::styled("H:", Style::default().fg(Color::Gray)),
-            Span::styled(format!("{:.2} ", candle.high), Style::default().fg(Color::Green)),
-            Span::styled("L:", Style::default().fg(Color::Gray)),
-            Span::styled(format!("{:.2} ", candle.low), Style::default().fg(Color::Red)),
-            Span::styled("C:", Style::default().fg(Color::Gray)),
-            Span::styled(format!("{:.2}", candle.close), Style::default().fg(Color::Cyan)),
-        ])
-    } else {
-        Line::from(Span::styled("  Waiting for candle data...", Style::default().fg(Color::Gray)))
-    };
     f.render_widget(Paragraph::new(ohlc_line), chunks[1]);
 }
 
-fn render_candlestick_chart(f: &mut Frame, area: ratatui::layout::Rect, candles: &[&Candlestick], has_current: bool) {
-    let inner = Block::default().borders(Borders::ALL).title("Candlesticks");
-    let inner_area = inner.inner(area);
-    f.render_widget(inner, area);
-
-    if candles.is_empty() || inner_area.width < 5 || inner_area.height < 3 {
-        return;
-    }
-
-    // Find price range
-    let mut min_price = f64::INFINITY;
-    let mut max_price = f64::NEG_INFINITY;
-    for candle in candles {
-        min_price = min_price.min(candle.low);
-        max_price = max_price.max(candle.high);
-    }
-
-    // Add some padding to price range
-    let price_range = max_price - min_price;
-    let padding = if price_range > 0.0 { price_range * 0.1 } else { 1.0 };
-    min_price -= padding;
-    max_price += padding;
-
-    let height = inner_area.height as f64;
-    let width = inner_area.width as usize;
-
-    // Calculate how many candles we can show (2 chars per candle + 1 space)
-    let candle_width = 3;
-    let max_candles = width / candle_width;
-    let candles_to_show = candles.len().min(max_candles);
-    let start_idx = candles.len().saturating_sub(candles_to_show);
-    let visible_candles = &candles[start_idx..];
-
-    // Render each row
-    for row in 0..inner_area.height {
-        let y = inner_area.y + row;
-        let price_at_row = max_price - ((row as f64 / height) * (max_price - min_price));
-
-        let mut spans = Vec::new();
-
-        for (i, candle) in visible_candles.iter().enumerate() {
-            let is_current = has_current && i == visible_candles.len() - 1;
-            let is_bullish = candle.close >= candle.open;
-
-            let body_top = candle.open.max(candle.close);
-            let body_bottom = candle.open.min(candle.close);
+pub fn render_pivots_popup(f: &mut Frame, app: &App) {
+    use ratatui::widgets::Clear;
 
-            let char_str = if price_at_row >= candle.low && price_at_row <= candle.high {
-                if price_at_row >= body_bottom && price_at_row <= body_top {
-                    // Body
-                    "█"
-                } else {
-                    // Wick
-                    "│"
-                }
-            } else {
-                " "
-            };
+    let area = f.area();
+    let popup_width = area.width.min(40);
+    let popup_height = area.height.min(9);
+    let popup_x = (area.width.saturating_sub(popup_width)) / 2;
+    let popup_y = (area.height.saturating_sub(popup_height)) / 2;
 
-            let color = if is_current {
-                Color::Yellow
-            } else if is_bullish {
-                Color::Green
-            } else {
-                Color::Red
-            };
+    let popup_area = ratatui::layout::Rect {
+        x: popup_x,
+        y: popup_y,
+        width: popup_width,
+        height: popup_height,
+    };
 
-            spans.push(Span::styled(format!(" {}", char_str), Style::default().fg(color)));
+    let cur = app.stock_data.as_ref().map_or_else(|| "$".to_string(), |d| d.currency_prefix());
+    let lines: Vec<Line> = match app.pivot_levels {
+        Some(pivots) => {
+            let price = app.live_trades.front().map(|t| t.price);
+            pivots.levels().iter()
+                .map(|&(name, level)| {
+                    let color = match name {
+                        "P" => Color::Yellow,
+                        n if n.starts_with('R') => Color::Red,
+                        _ => Color::Green,
+                    };
+                    let mut spans = vec![
+                        Span::styled(format!("  {:<6}", name), Style::default().fg(Color::Gray)),
+                        Span::styled(format_price(level, &cur), Style::default().fg(color)),
+                    ];
+                    if let Some(price) = price {
+                        spans.push(Span::styled(
+                            format!("  {:+.2}%", (level - price) / price * 100.0),
+                            Style::default().fg(Color::DarkGray),
+                        ));
+                    }
+                    Line::from(spans)
+                })
+                .chain(std::iter::once(Line::from(Span::styled(
+                    "  From the previous session's H/L/C",
+                    Style::default().fg(Color::DarkGray),
+                ))))
+                .collect()
+        }
+        None if app.pivots_pending => {
+            vec![Line::from(Span::styled("  Loading previous session...", Style::default().fg(Color::Gray)))]
         }
+        None => vec![Line::from(Span::styled("  No previous session available", Style::default().fg(Color::Gray)))],
+    };
 
-        let line = Line::from(spans);
-        f.render_widget(
-            Paragraph::new(vec![line]),
-            ratatui::layout::Rect {
-                x: inner_area.x,
-                y,
-                width: inner_area.width,
-                height: 1,
-            },
+    let popup = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("{} pivots (Esc to close)", app.symbol))
+                .style(Style::default().bg(Color::Black)),
         );
-    }
+
+    f.render_widget(Clear, popup_area);
+    f.render_widget(popup, popup_area);
 }
 
 
@@ -443,6 +822,132 @@ pub fn render_error_log(f: &mut Frame, app: &App) {
 }
 
 
+fn trade_rate_span(app: &App) -> Span<'static> {
+    match app.trade_rate(app.live_clock()) {
+        Some((rate, true)) => Span::styled(
+            format!("  {:.0} trades/min", rate),
+            Style::default().fg(Color::Black).bg(Color::Yellow).add_modifier(Modifier::BOLD),
+        ),
+        Some((rate, false)) => Span::styled(format!("  {:.0} trades/min", rate), Style::default().fg(Color::Gray)),
+        None => Span::raw(""),
+    }
+}
+
+/// Session high, low, VWAP and average trade size. A high or low a trade
+/// has just set flashes.
+fn session_line(app: &App, cur: &str) -> Line<'static> {
+    let (Some(high), Some(low)) = (app.session_high, app.session_low) else {
+        return Line::from(Span::styled("Session: no trades yet", Style::default().fg(Color::DarkGray)));
+    };
+    let flashing = app.session_extreme_flash.map(|(_, is_high)| is_high);
+    let extreme = |label: &str, value: f64, color: Color, is_high: bool| {
+        let style = if flashing == Some(is_high) {
+            Style::default().fg(Color::Black).bg(color).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(color)
+        };
+        Span::styled(format!("{} {}", label, format_price(value, cur)), style)
+    };
+    let mut spans = vec![
+        Span::styled("Session  ", Style::default().fg(Color::Gray)),
+        extreme("H", high, Color::Green, true),
+        Span::raw("  "),
+        extreme("L", low, Color::Red, false),
+    ];
+    if let Some(vwap) = app.session_vwap() {
+        spans.push(Span::styled(format!("  VWAP {}", format_price(vwap, cur)), Style::default().fg(Color::Cyan)));
+    }
+    if let Some(size) = app.average_trade_size() {
+        spans.push(Span::styled(format!("  Avg trade {}", format_volume(size.round() as u64)), Style::default().fg(Color::Gray)));
+    }
+    Line::from(spans)
+}
+
+/// Trades per second over the last minute as a sparkline, scaled to the
+/// busiest second, so a burst shows even when the tape scrolls too fast to
+/// read. Quiet seconds are blank.
+fn activity_line(app: &App) -> Line<'static> {
+    let counts = app.trade_activity(app.live_clock());
+    let peak = counts.iter().copied().max().unwrap_or(0);
+    let spark: String = counts.iter()
+        .map(|&n| match n {
+            0 => ' ',
+            n => SPARK_LEVELS[((n as usize * SPARK_LEVELS.len()).div_ceil(peak as usize) - 1).min(SPARK_LEVELS.len() - 1)],
+        })
+        .collect();
+    Line::from(vec![
+        Span::styled("Trades/s ", Style::default().fg(Color::Gray)),
+        Span::styled(spark, Style::default().fg(Color::Cyan)),
+        Span::styled(format!(" peak {}/s", peak), Style::default().fg(Color::DarkGray)),
+    ])
+}
+
+/// "B 197.41 × A 197.44 (0.03)" from the last bid/ask poll, if there was one.
+fn bid_ask_span(app: &App) -> Span<'static> {
+    let Some(quote) = app.bid_ask else {
+        return Span::raw("");
+    };
+    let factor = app.stock_data.as_ref().map_or(1.0, |d| d.live_price_factor());
+    let (bid, ask) = (quote.bid * factor, quote.ask * factor);
+    Span::styled(
+        format!("  B {:.2} × A {:.2} ({:.2})", bid, ask, ask - bid),
+        Style::default().fg(Color::Gray),
+    )
+}
+
+/// Signed buy-minus-sell volume, green when buyers lead and red when sellers do.
+fn delta_span(label: &str, delta: i64) -> Span<'static> {
+    let sign = if delta > 0 { "+" } else if delta < 0 { "-" } else { "" };
+    let color = if delta > 0 { Color::Green } else if delta < 0 { Color::Red } else { Color::Gray };
+    Span::styled(format!("{}{}{}", label, sign, format_volume(delta.unsigned_abs())), Style::default().fg(color))
+}
+
+/// Rows of the per-candle volume and trade count strip.
+const COUNTS_HEIGHT: u16 = 2;
+
+/// Each candle's volume above its trade count, right-aligned in the candle's
+/// column. When a value doesn't fit the columns, its row falls back to a
+/// one-cell-high bar scaled to the largest shown.
+fn render_candle_counts(f: &mut Frame, area: ratatui::layout::Rect, candles: &[&Candlestick], columns: &CandleColumns, highlight_last: bool) {
+    let volumes: Vec<String> = candles.iter().map(|c| format_volume(c.volume)).collect();
+    let trades: Vec<String> = candles.iter().map(|c| c.trade_count.to_string()).collect();
+    let max_volume = candles.iter().map(|c| c.volume).max().unwrap_or(0).max(1) as f64;
+    let max_trades = candles.iter().map(|c| c.trade_count).max().unwrap_or(0).max(1) as f64;
+    let color = |i: usize, candle: &Candlestick| {
+        if highlight_last && i == candles.len() - 1 {
+            Color::Yellow
+        } else if candle.close >= candle.open {
+            Color::Green
+        } else {
+            Color::Red
+        }
+    };
+
+    let row = |labels: &[String], fraction: &dyn Fn(&Candlestick) -> f64, dim: bool| {
+        // One blank at least between neighbouring values
+        let fits = labels.iter().all(|l| l.chars().count() < columns.width);
+        let mut spans = vec![Span::raw(" ".repeat(columns.left))];
+        for (i, candle) in candles.iter().enumerate() {
+            let mut style = Style::default().fg(color(i, candle));
+            if dim {
+                style = style.add_modifier(Modifier::DIM);
+            }
+            if fits {
+                spans.push(Span::styled(format!("{:>w$}", labels[i], w = columns.width), style));
+            } else {
+                spans.push(Span::raw(" ".repeat(columns.gap)));
+                spans.push(Span::styled(volume_glyph(fraction(candle), 1, 0).repeat(columns.bar_width()), style));
+            }
+        }
+        Line::from(spans)
+    };
+    let lines = vec![
+        row(&volumes, &|c| c.volume as f64 / max_volume, false),
+        row(&trades, &|c| c.trade_count as f64 / max_trades, true),
+    ];
+    f.render_widget(Paragraph::new(lines), area);
+}
+
 fn format_volume(vol: u64) -> String {
     if vol >= 1_000_000 {
         format!("{:.1}M", vol as f64 / 1_000_000.0)
@@ -451,4 +956,51 @@ fn format_volume(vol: u64) -> String {
     } else {
         format!("{}", vol)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn each_row_ticks_against_the_trade_before_it() {
+        // Pushed to the front as they arrive, so the newest is first
+        let mut trades = VecDeque::new();
+        for (s, price) in [10.0, 11.0, 11.0, 10.5, 12.0].into_iter().enumerate() {
+            let timestamp = chrono::DateTime::from_timestamp(1_700_000_000 + s as i64, 0).unwrap();
+            trades.push_front(Trade { price, timestamp, volume: Some(100) });
+        }
+        let directions: Vec<_> = (0..trades.len()).map(|i| tick_direction(&trades, i)).collect();
+        assert_eq!(
+            directions,
+            [Some(Ordering::Greater), Some(Ordering::Less), Some(Ordering::Equal), Some(Ordering::Greater), None],
+        );
+        assert_eq!(tick_direction(&trades, trades.len()), None);
+    }
+
+    #[test]
+    fn pivot_distance_is_from_the_newest_trade() {
+        let mut app = App::new();
+        app.pivot_levels = Some(crate::indicators::pivot_points(110.0, 90.0, 100.0));
+        for (s, price) in [50.0, 80.0].into_iter().enumerate() {
+            let timestamp = chrono::DateTime::from_timestamp(1_700_000_000 + s as i64, 0).unwrap();
+            app.live_trades.push_front(Trade { price, timestamp, volume: Some(1) });
+        }
+        let mut terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(40, 9)).unwrap();
+        terminal.draw(|f| render_pivots_popup(f, &app)).unwrap();
+        let buf = terminal.backend().buffer();
+        let row = |y: u16| (0..40).map(|x| buf[(x, y)].symbol()).collect::<String>();
+
+        // P = 100 is 25% above the newest trade at 80, not 100% above the oldest at 50
+        let pivot = (0..9).map(row).find(|r| r.contains(" P ")).unwrap();
+        assert!(pivot.contains("$100.00  +25.00%"), "{}", pivot);
+    }
+
+    #[test]
+    fn format_volume_table() {
+        let table = [(0, "0"), (999, "999"), (1_000, "1.0K"), (1_540, "1.5K"), (250_000, "250.0K"), (2_500_000, "2.5M")];
+        for (volume, expected) in table {
+            assert_eq!(format_volume(volume), expected);
+        }
+    }
+}
@@ -6,7 +6,7 @@ use ratatui::{
     Frame,
 };
 
-use super::{App, MarketPanel, render_nav};
+use super::{App, MarketPanel, footer_rows, render_nav};
 use crate::stock::MarketMover;
 
 fn format_volume(vol: u64) -> String {
@@ -79,7 +79,7 @@ pub fn render_market_view(f: &mut Frame, app: &App) {
         .constraints([
             Constraint::Length(3),
             Constraint::Min(0),
-            Constraint::Length(3),
+            Constraint::Length(footer_rows(f, 3)),
         ])
         .split(f.area());
 
@@ -5,25 +5,43 @@ use ratatui::{
     Frame,
 };
 
+use crate::provider::DataProvider;
 use crate::stock::StockData;
+use crate::websocket::LivePrice;
 use std::sync::Arc;
 use std::time::Instant;
 use std::time::Duration;
 use std::collections::{VecDeque, HashMap};
 use tokio::sync::Mutex;
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use ratatui::text::{Line, Span};
 use ratatui::style::{Style, Color, Modifier};
 use ratatui::widgets::{Block, Borders, List, Clear};
 
+/// Below this many columns the landing panes stack and chart x-axes keep
+/// only their end labels.
+pub(super) const NARROW_WIDTH: u16 = 70;
+/// Below this many rows footers shrink to one line without a border.
+const SHORT_HEIGHT: u16 = 24;
+/// Smallest terminal that gets drawn at all; anything less shows a notice.
+const MIN_WIDTH: u16 = 40;
+const MIN_HEIGHT: u16 = 10;
+
 // ── Shared navigation bar ────────────────────────────────────────────────────
 
 pub fn nav_key(k: &'static str) -> Span<'static> {
     Span::styled(k, Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
 }
 
+/// Rows for a footer that is normally `rows` tall, one on a short terminal.
+pub(super) fn footer_rows(f: &Frame, rows: u16) -> u16 {
+    if f.area().height < SHORT_HEIGHT { 1 } else { rows }
+}
+
 /// Renders a one-line navigation bar with styled key bindings.
-/// `items` is a slice of (key, description) pairs.
+/// `items` is a slice of (key, description) pairs. The bar is bordered, or a
+/// bare line when `area` is too short for a border.
 pub fn render_nav(f: &mut Frame, area: Rect, items: &[(&'static str, &'static str)]) {
     let spans: Vec<Span> = items
         .iter()
@@ -39,9 +57,10 @@ pub fn render_nav(f: &mut Frame, area: Rect, items: &[(&'static str, &'static st
         })
         .collect();
 
-    let bar = Paragraph::new(Line::from(spans))
-        .block(Block::default().borders(Borders::ALL))
-        .alignment(Alignment::Center);
+    let mut bar = Paragraph::new(Line::from(spans)).alignment(Alignment::Center);
+    if area.height >= 3 {
+        bar = bar.block(Block::default().borders(Borders::ALL));
+    }
     f.render_widget(bar, area);
 }
 
@@ -49,23 +68,68 @@ mod landing;
 use landing::render_landing;
 
 mod chart;
-use chart::render_chart_view;
+use chart::{render_appearance_popup, render_candle_detail_popup, render_chart_view, render_compare_input, render_earnings_popup, render_indicators_popup, render_params_popup, render_patterns_popup, render_profile_popup, render_stats_popup};
 
 mod live;
-use live::{render_live_ticker, render_live_candles, render_live_mode_select, render_error_log, render_alert_input};
+use live::{render_live_ticker, render_live_candles, render_live_mode_select, render_error_log, render_alert_input, render_alert_list, render_pivots_popup, render_session_stats_popup};
 
 mod market;
 use market::render_market_view;
 
+#[cfg(feature = "png")]
+mod png;
+
+/// Below this many terminal rows candles default to the Braille renderer
+const BRAILLE_CANDLE_ROWS: u16 = 30;
+/// How long landing-page sparklines are kept before they're fetched again
+const SPARKLINE_TTL_SECS: u64 = 300;
+/// How long footer confirmations stay visible
+const FLASH_SECS: u64 = 4;
+/// How long the session high/low marker flashes after a live price moves it
+const EXTREME_FLASH_SECS: u64 = 2;
+/// Sliding window for the live trades-per-minute rate
+const TRADE_RATE_WINDOW_SECS: i64 = 60;
+/// How far back live batches are kept for rebuilding candles at a new interval
+const REBUCKET_WINDOW_SECS: i64 = 2 * 3600;
+/// An exchange timestamp further than this from when the trade arrived is
+/// taken as a bad clock (or a stale delayed quote) and the arrival time used
+const MAX_FEED_SKEW_SECS: i64 = 300;
+/// A rate this many times the session average is highlighted as a surge
+const TRADE_RATE_SPIKE: f64 = 3.0;
+/// Seconds of per-second trade counts behind the live ticker's activity sparkline
+pub const ACTIVITY_SECS: usize = 60;
+/// Fired alerts kept for the alert list
+const ALERT_LOG_LEN: usize = 10;
+/// How often the live header's bid/ask is polled
+const BID_ASK_POLL_SECS: u64 = 5;
+/// Lookback for the ATR readout and chart padding
+const ATR_PERIOD: usize = 14;
+/// Share of the series the line chart shows at each zoom step, in percent
+pub const ZOOM_LEVELS: [usize; 3] = [100, 50, 25];
+/// Fewest bars zooming in narrows the chart to
+const MIN_ZOOM_BARS: usize = 10;
+
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
 pub enum WebSocketStatus {
     Idle,
     Connecting,
     Connected { since: DateTime<Utc> },
-    Reconnecting { attempt: u32, next_retry_in: Duration },
+    /// Waiting to retry; `max_attempts` is `None` when it never gives up
+    Reconnecting { attempt: u32, max_attempts: Option<u32>, next_retry_in: Duration },
+    /// Delayed price polling, used when no websocket feed is configured
+    Polling { interval: Duration },
+    /// Connected, but nothing has arrived since `last_message`
+    Stale { last_message: DateTime<Utc> },
+    /// Playing back a recording made with 'R'
+    Replay { speed: f64 },
+    /// Dropped while the market was shut; reconnects shortly before `reopens_at`
+    MarketClosed { reopens_at: DateTime<Utc> },
     Error { message: String, recoverable: bool },
     Disconnected,
+    /// Something for the error log that leaves the connection as it was;
+    /// never stored as the status
+    Warning(String),
 }
 
 pub struct UpdateThrottle {
@@ -81,6 +145,10 @@ impl UpdateThrottle {
         }
     }
 
+    pub fn min_interval(&self) -> Duration {
+        self.min_interval
+    }
+
     pub fn should_update(&mut self) -> bool {
         let now = Instant::now();
         if now.duration_since(self.last_update) >= self.min_interval {
@@ -113,7 +181,234 @@ pub enum LandingPanel {
     Watchlist,
 }
 
+/// Optional indicator panes stacked under the price chart.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IndicatorPane {
+    Rsi,
+    Macd,
+    Stochastic,
+    Obv,
+    Volatility,
+}
+
+/// Rows of the indicators popup, in display order.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Indicator {
+    Sma,
+    Ema,
+    Trend,
+    Patterns,
+    Volume,
+    Pane(IndicatorPane),
+}
+
+impl Indicator {
+    pub const ALL: [Indicator; 10] = [
+        Indicator::Sma,
+        Indicator::Ema,
+        Indicator::Trend,
+        Indicator::Patterns,
+        Indicator::Volume,
+        Indicator::Pane(IndicatorPane::Rsi),
+        Indicator::Pane(IndicatorPane::Macd),
+        Indicator::Pane(IndicatorPane::Stochastic),
+        Indicator::Pane(IndicatorPane::Obv),
+        Indicator::Pane(IndicatorPane::Volatility),
+    ];
+}
+
+/// Clock that chart labels and trade times are shown in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TimeZoneMode {
+    Local,
+    Utc,
+    /// The listing's exchange, from the chart meta; local time without it
+    Exchange,
+}
+
+impl TimeZoneMode {
+    fn from_config(value: Option<&str>) -> Self {
+        match value.map(|v| v.trim().to_ascii_lowercase()).as_deref() {
+            Some("utc") => TimeZoneMode::Utc,
+            Some("exchange") => TimeZoneMode::Exchange,
+            _ => TimeZoneMode::Local,
+        }
+    }
+
+    fn config_value(self) -> &'static str {
+        match self {
+            TimeZoneMode::Local => "local",
+            TimeZoneMode::Utc => "utc",
+            TimeZoneMode::Exchange => "exchange",
+        }
+    }
+
+    fn next(self) -> Self {
+        match self {
+            TimeZoneMode::Local => TimeZoneMode::Utc,
+            TimeZoneMode::Utc => TimeZoneMode::Exchange,
+            TimeZoneMode::Exchange => TimeZoneMode::Local,
+        }
+    }
+}
+
+/// The active clock resolved against the loaded listing, cheap to pass to
+/// renderers that don't see the `App`.
 #[derive(Debug, Clone, Copy)]
+pub struct DisplayZone {
+    /// Offset east of UTC when showing exchange or UTC time; `None` is local
+    offset: Option<chrono::FixedOffset>,
+}
+
+impl DisplayZone {
+    pub fn format(&self, dt: &DateTime<Utc>, fmt: &str) -> String {
+        match self.offset {
+            Some(offset) => dt.with_timezone(&offset).format(fmt).to_string(),
+            None => dt.with_timezone(&chrono::Local).format(fmt).to_string(),
+        }
+    }
+
+    pub fn date(&self, dt: &DateTime<Utc>) -> chrono::NaiveDate {
+        match self.offset {
+            Some(offset) => dt.with_timezone(&offset).date_naive(),
+            None => dt.with_timezone(&chrono::Local).date_naive(),
+        }
+    }
+}
+
+/// Rows of the appearance popup.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AppearanceOption {
+    Candlesticks,
+    AreaFill,
+    Baseline,
+    LogScale,
+    BrailleCandles,
+    Smoothing,
+    Drawdown,
+    SessionGaps,
+}
+
+impl AppearanceOption {
+    pub const ALL: [AppearanceOption; 8] = [
+        AppearanceOption::Candlesticks,
+        AppearanceOption::AreaFill,
+        AppearanceOption::Baseline,
+        AppearanceOption::LogScale,
+        AppearanceOption::BrailleCandles,
+        AppearanceOption::Smoothing,
+        AppearanceOption::Drawdown,
+        AppearanceOption::SessionGaps,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            AppearanceOption::Candlesticks => "Candlesticks instead of the line",
+            AppearanceOption::AreaFill => "Shade the area under the line",
+            AppearanceOption::Baseline => "Baseline at previous close (1D)",
+            AppearanceOption::LogScale => "Log price scale",
+            AppearanceOption::BrailleCandles => "Braille candles",
+            AppearanceOption::Smoothing => "Smooth the line (display only)",
+            AppearanceOption::Drawdown => "Shade drawdowns from the high",
+            AppearanceOption::SessionGaps => "Gaps between sessions",
+        }
+    }
+}
+
+/// Editable rows of the indicator parameters popup.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IndicatorParam {
+    Sma,
+    Ema,
+    Rsi,
+    Macd,
+    Stochastic,
+    Volatility,
+}
+
+/// Longest period any indicator accepts from the parameters popup
+pub const MAX_INDICATOR_PERIOD: usize = 500;
+
+impl IndicatorParam {
+    pub const ALL: [IndicatorParam; 6] = [
+        IndicatorParam::Sma,
+        IndicatorParam::Ema,
+        IndicatorParam::Rsi,
+        IndicatorParam::Macd,
+        IndicatorParam::Stochastic,
+        IndicatorParam::Volatility,
+    ];
+
+    /// The indicator that has to be on for this row to be listed.
+    pub fn indicator(self) -> Indicator {
+        match self {
+            IndicatorParam::Sma => Indicator::Sma,
+            IndicatorParam::Ema => Indicator::Ema,
+            IndicatorParam::Rsi => Indicator::Pane(IndicatorPane::Rsi),
+            IndicatorParam::Macd => Indicator::Pane(IndicatorPane::Macd),
+            IndicatorParam::Stochastic => Indicator::Pane(IndicatorPane::Stochastic),
+            IndicatorParam::Volatility => Indicator::Pane(IndicatorPane::Volatility),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            IndicatorParam::Sma => "SMA periods",
+            IndicatorParam::Ema => "EMA periods",
+            IndicatorParam::Rsi => "RSI period",
+            IndicatorParam::Macd => "MACD fast,slow,signal",
+            IndicatorParam::Stochastic => "Stochastic %K,smooth,%D",
+            IndicatorParam::Volatility => "Realized vol period",
+        }
+    }
+
+    /// Key the value is saved under in config.json.
+    fn config_key(self) -> &'static str {
+        match self {
+            IndicatorParam::Sma => "sma_periods",
+            IndicatorParam::Ema => "ema_periods",
+            IndicatorParam::Rsi => "rsi_period",
+            IndicatorParam::Macd => "macd_periods",
+            IndicatorParam::Stochastic => "stochastic_periods",
+            IndicatorParam::Volatility => "volatility_period",
+        }
+    }
+
+    /// Parse a comma-separated entry like "20,50", checking the count and that
+    /// every value is a whole number of bars within bounds. Lookback windows
+    /// need at least two bars; smoothing and signal periods may be 1.
+    pub fn parse(self, input: &str) -> Result<Vec<usize>, String> {
+        let values = input.split(',')
+            .map(|s| s.trim())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.parse::<usize>().map_err(|_| format!("\"{}\" is not a whole number", s)))
+            .collect::<Result<Vec<usize>, String>>()?;
+        let (min_count, max_count) = match self {
+            IndicatorParam::Sma | IndicatorParam::Ema => (1, 4),
+            IndicatorParam::Rsi | IndicatorParam::Volatility => (1, 1),
+            IndicatorParam::Macd | IndicatorParam::Stochastic => (3, 3),
+        };
+        if values.len() < min_count || values.len() > max_count {
+            return Err(match (min_count, max_count) {
+                (1, 1) => "Enter one period".to_string(),
+                (a, b) if a == b => format!("Enter {} periods", a),
+                (a, b) => format!("Enter {} to {} periods", a, b),
+            });
+        }
+        for (i, &v) in values.iter().enumerate() {
+            let min = if matches!(self, IndicatorParam::Macd | IndicatorParam::Stochastic) && i > 0 { 1 } else { 2 };
+            if v < min || v > MAX_INDICATOR_PERIOD {
+                return Err(format!("Periods must be {}–{}", min, MAX_INDICATOR_PERIOD));
+            }
+        }
+        if self == IndicatorParam::Macd && values[0] >= values[1] {
+            return Err("The fast period must be shorter than the slow one".to_string());
+        }
+        Ok(values)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum CandleInterval {
     OneMinute,
     FiveMinutes,
@@ -123,7 +418,7 @@ pub enum CandleInterval {
 }
 
 impl CandleInterval {
-    pub fn to_secs(&self) -> u64 {
+    pub fn to_secs(self) -> u64 {
         match self {
             CandleInterval::OneMinute => 60,
             CandleInterval::FiveMinutes => 300,
@@ -133,7 +428,7 @@ impl CandleInterval {
         }
     }
 
-    pub fn to_string(&self) -> &'static str {
+    pub fn to_string(self) -> &'static str {
         match self {
             CandleInterval::OneMinute => "1m",
             CandleInterval::FiveMinutes => "5m",
@@ -164,14 +459,45 @@ impl CandleInterval {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Trade {
     pub price: f64,
     pub timestamp: DateTime<Utc>,
     pub volume: Option<u64>,
 }
 
+/// What the live feed brings in while the display is paused: the candles
+/// it keeps aggregating and the trades the frozen tape hasn't shown.
+pub struct LivePause {
+    candles: VecDeque<Candlestick>,
+    current: Option<Candlestick>,
+    /// Newest first, like `App::live_trades`
+    trades: VecDeque<Trade>,
+    /// Trades buffered in all, counting each one merged into a batch
+    pub trade_count: u32,
+}
+
+/// One update from the live feed as it went into the candles, kept so they
+/// can be rebuilt when the candle interval changes.
+struct LiveBatch {
+    timestamp: DateTime<Utc>,
+    /// Open, high, low and last price, already in display currency
+    prices: [f64; 4],
+    /// Unrounded, as `LivePrice::size`
+    quantity: f64,
+    trades: u32,
+    /// Volume signed by the tick rule, for the forming candle's delta
+    signed_volume: i64,
+}
+
+/// Today's closes and change for a landing-page sparkline.
 #[derive(Debug, Clone)]
+pub struct Sparkline {
+    pub prices: Vec<f64>,
+    pub change_percent: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Candlestick {
     pub open: f64,
     pub high: f64,
@@ -182,20 +508,69 @@ pub struct Candlestick {
     pub trade_count: u32,
 }
 
+/// Indicator series for the chart, kept on the app so the panes, overlays and
+/// readout share one computation instead of each rendering pass redoing it.
+/// Every series is indexed by bar of `closes`: candle closes in candlestick
+/// mode, the raw series otherwise. `App::refresh_indicators` rebuilds it when
+/// the data, its currency or a period changes.
+#[derive(Debug, Clone, Default)]
+pub struct IndicatorValues {
+    pub closes: Vec<f64>,
+    pub timestamps: Vec<DateTime<Utc>>,
+    /// One series per entry of `sma_periods`, in the same order
+    pub sma: Vec<Vec<(f64, f64)>>,
+    /// One series per entry of `ema_periods`, in the same order
+    pub ema: Vec<Vec<(f64, f64)>>,
+    pub rsi: Vec<(f64, f64)>,
+    pub macd: crate::indicators::Macd,
+    pub stochastic: crate::indicators::Stochastic,
+    pub obv: Vec<(f64, f64)>,
+    /// Rolling realized volatility, annualized %
+    pub volatility: Vec<(f64, f64)>,
+    pub trend: Option<crate::indicators::Regression>,
+    /// Latest ATR over the synthesized candles
+    pub atr: Option<f64>,
+}
+
+/// Value of `points` at bar `index`, if the series is defined there.
+fn value_at(points: &[(f64, f64)], index: usize) -> Option<f64> {
+    points.binary_search_by(|(x, _)| x.total_cmp(&(index as f64)))
+        .ok()
+        .map(|i| points[i].1)
+}
+
 pub struct App {
     pub state: AppState,
     pub symbol: String,
     pub timeframe: crate::stock::TimeFrame,
+    /// Chart interval chosen with 't'; None uses the timeframe's default
+    pub interval_override: Option<&'static str>,
+    /// Why the last interval override was dropped, shown in the chart title
+    pub interval_notice: Option<String>,
     pub stock_data: Option<StockData>,
+    /// Show foreign listings in the display currency (toggled with '$')
+    pub convert_currency: bool,
+    /// Set while showing a CSV file from `--csv`; network features are off
+    pub csv_path: Option<std::path::PathBuf>,
+    /// Where historical charts and candles come from
+    pub provider: Arc<dyn DataProvider>,
     pub input_mode: bool,
     pub input_buffer: String,
+    // Symbol search autocomplete
+    pub search_results: Vec<crate::stock::SymbolMatch>,
+    pub search_state: ListState,
+    pub search_edited_at: Option<Instant>,
     pub error_message: Option<String>,
+    /// Short confirmation shown in the chart footer, e.g. after an export
+    pub flash: Option<(String, Instant)>,
     pub loading: bool,
     pub live_updates_enabled: bool,
     pub last_live_price: Option<f64>,
     pub popular_list_state: ListState,
     pub popular_stocks: Vec<(&'static str, &'static str)>,
 	pub ws_should_stop: Arc<Mutex<bool>>,
+    /// The running Finnhub connection, for changing its subscriptions
+    pub ws_manager: Option<Arc<dyn crate::websocket::LiveFeed>>,
     pub ws_status: WebSocketStatus,
     pub ws_last_update: Option<DateTime<Utc>>,
     pub ws_error_log: VecDeque<String>,
@@ -203,21 +578,181 @@ pub struct App {
     pub show_error_log: bool,
     pub show_candlesticks: bool,
     pub show_volume: bool,
+    /// Each live candle's volume and trade count printed under its column
+    pub show_candle_counts: bool,
     pub show_sma: bool,
+    pub sma_periods: Vec<usize>,
+    pub show_ema: bool,
+    pub ema_periods: Vec<usize>,
+    /// Bars in the rolling realized-volatility window
+    pub volatility_period: usize,
+    pub rsi_period: usize,
+    /// MACD fast, slow and signal periods
+    pub macd_periods: [usize; 3],
+    /// Stochastic %K period, %K smoothing and %D period
+    pub stochastic_periods: [usize; 3],
+    pub indicator_values: IndicatorValues,
+    /// Crosshair bar on the historical chart, indexed like `indicator_values`
+    pub chart_cursor: Option<usize>,
+    /// Index into `ZOOM_LEVELS`; 0 shows the whole series
+    pub zoom_level: usize,
+    /// Bars between the zoomed window and the latest bar; 0 follows the latest
+    pub pan_offset: usize,
+    /// Least-squares trendline over the loaded series
+    pub show_trend: bool,
+    /// Plot prices on a log y-axis
+    pub log_scale: bool,
+    /// Fill under the line chart in a dim shade of the trend colour
+    pub area_fill: bool,
+    /// On 1D, green above the previous close and red below it
+    pub baseline: bool,
+    /// Braille candles when set; `None` leaves it to the terminal height
+    pub braille_candles: Option<bool>,
+    /// Draw the line chart through a 3-point moving median
+    pub smooth_line: bool,
+    /// Shade where the line is below its running high in the visible window
+    pub drawdown_shading: bool,
+    /// Open a gap of `session_gap` columns between sessions of a
+    /// multi-day intraday line chart
+    pub session_gaps: bool,
+    pub session_gap: u16,
+    /// Recent trades beside the chart while it streams live ticks
+    pub show_trade_feed: bool,
+    /// Chart fills the terminal, without the header and footer
+    pub full_screen: bool,
+    /// Colour, colour plus shapes, or no colour at all
+    pub display_mode: crate::config::DisplayMode,
+    pub time_zone: TimeZoneMode,
+    pub show_appearance: bool,
+    /// Highlighted row in the appearance popup
+    pub appearance_cursor: usize,
+    /// Mark doji / hammer / engulfing candles
+    pub show_patterns: bool,
+    pub show_pattern_list: bool,
+    /// Pivot levels from the previous session, for `pivot_symbol`
+    pub pivot_levels: Option<crate::indicators::PivotLevels>,
+    /// Symbol the pivot levels were requested for
+    pub pivot_symbol: Option<String>,
+    /// Pivot fetch in flight
+    pub pivots_pending: bool,
+    /// History fetch for the live candles in flight
+    pub candles_pending: bool,
+    pub show_pivots: bool,
+    /// When a live price last set a new session high or low
+    pub extreme_flash: Option<Instant>,
+    /// Enabled panes in the order they were switched on
+    pub indicator_panes: Vec<IndicatorPane>,
+    pub show_indicators: bool,
+    /// Statistics popup: the loaded series on the chart, the trade session in live modes
+    pub show_stats: bool,
+    /// Details of the candle under the crosshair
+    pub show_candle_detail: bool,
+    /// Second symbol overlaid on the chart, rebased to 100 alongside the main one
+    pub compare_symbol: Option<String>,
+    pub compare_data: Option<StockData>,
+    pub show_compare_input: bool,
+    pub compare_input_buffer: String,
+    /// Set when the main chart changed and the comparison needs refetching
+    compare_stale: bool,
+    /// Highlighted row in the indicators popup
+    pub indicator_cursor: usize,
+    /// Parameters popup opened from the indicators popup
+    pub show_params: bool,
+    /// Highlighted row among `editable_params()`
+    pub param_cursor: usize,
+    /// The highlighted row is being edited in `param_input_buffer`
+    pub param_editing: bool,
+    pub param_input_buffer: String,
+    /// Why the last entry was rejected, shown until the next edit
+    pub param_error: Option<String>,
     // Live mode fields
     pub show_live_mode_select: bool,
+    pub show_earnings: bool,
+    pub show_profile: bool,
+    /// Session cache of company profiles; `None` means Finnhub has none for the symbol
+    pub profiles: HashMap<String, Option<crate::stock::CompanyProfile>>,
+    /// Symbol whose earnings have been requested, so we ask Finnhub only once
+    pub earnings_requested_for: Option<String>,
+    /// Session hours for the symbol on screen, refreshed with each chart fetch
+    pub market_hours: Option<crate::stock::MarketHours>,
+    /// No Finnhub key configured, so live modes fall back to polling Yahoo
+    pub live_polling_fallback: bool,
     pub live_trades: VecDeque<Trade>,
     pub live_candles: VecDeque<Candlestick>,
     pub current_candle: Option<Candlestick>,
+    /// Candles the live chart is scrolled back from the newest; 0 follows it
+    pub live_scroll: usize,
+    /// Crosshair over the live candles, indexing the closed candles with the
+    /// forming one last
+    pub live_cursor: Option<usize>,
     pub candle_interval: CandleInterval,
     pub total_live_volume: u64,
     pub total_trade_count: u32,
+    /// Trades that arrived merged into another's update, for the stats popup
+    pub coalesced_trades: u32,
+    /// Minimum sizes the trade tape can be filtered to, ascending
+    pub tape_sizes: Vec<u64>,
+    /// 0 shows every trade on the tape, `i` only those of `tape_sizes[i - 1]` or more
+    pub tape_filter: usize,
+    /// Caps on the live buffers: `live_trades`, `live_ticks` on the stock
+    /// data and the finished `live_candles`
+    pub trade_buffer: usize,
+    pub tick_buffer: usize,
+    pub candle_buffer: usize,
+    /// Set while the live display is frozen with Space
+    pub live_pause: Option<LivePause>,
+    /// Open while 'R' is recording the feed to a file
+    pub recorder: Option<crate::session::Recorder>,
+    /// Set while a recording is playing back
+    pub replay_speed: Option<f64>,
+    /// The replay's notion of now: the time of the last record played
+    replay_clock: Option<DateTime<Utc>>,
+    /// Recording loaded from the command line, waiting for the main loop to play it
+    pending_replay: Option<Vec<crate::session::Record>>,
+    /// Buy minus sell volume over the session, sides assigned by the tick rule
+    pub cumulative_delta: i64,
+    /// The same delta for the candle still forming
+    pub candle_delta: i64,
+    /// Highest and lowest live price of the session, `None` before the first trade
+    pub session_high: Option<f64>,
+    pub session_low: Option<f64>,
+    /// Sum of price × volume over the session, for the VWAP
+    session_notional: f64,
+    /// Unrounded volume over the session; `total_live_volume` is it rounded
+    session_quantity: f64,
+    /// What the forming candle's whole `volume` is off from its unrounded
+    /// total, so fractional trades add up instead of each rounding away
+    candle_volume_carry: f64,
+    /// When a trade last set a new session high (`true`) or low
+    pub session_extreme_flash: Option<(Instant, bool)>,
+    /// Last polled bid and ask for the live symbol, `None` when there's no
+    /// two-sided quote or the endpoint isn't available
+    pub bid_ask: Option<crate::stock::BidAsk>,
+    /// When the bid/ask poll last went out, and whether it's still out
+    bid_ask_requested_at: Option<Instant>,
+    bid_ask_in_flight: bool,
+    /// The Finnhub key's plan has no bid/ask; stop asking
+    bid_ask_denied: bool,
+    /// Side of the last trade that moved the price: 1 buy, -1 sell, 0 unknown
+    last_tick_side: i64,
+    /// Arrival times of the trades in the last `TRADE_RATE_WINDOW_SECS`, oldest first
+    recent_trade_times: VecDeque<DateTime<Utc>>,
+    /// Trades per second, a ring indexed by epoch second mod `ACTIVITY_SECS`
+    trade_activity: [u32; ACTIVITY_SECS],
+    /// The latest second counted into `trade_activity`
+    activity_second: Option<i64>,
+    /// Feed updates of the last `REBUCKET_WINDOW_SECS`, oldest first
+    live_batches: VecDeque<LiveBatch>,
+    /// First trade of the live session, for the session-average trade rate
+    live_session_start: Option<DateTime<Utc>>,
     pub show_help: bool,
     pub watchlist: Vec<String>,
     pub watchlist_state: ListState,
     pub landing_panel: LandingPanel,
     // Landing quotes
     pub landing_quotes: HashMap<String, crate::stock::QuoteSnapshot>,
+    pub sparklines: HashMap<String, Sparkline>,
+    sparklines_requested_at: Option<Instant>,
     // Market overview
     pub market_gainers: Vec<crate::stock::MarketMover>,
     pub market_losers: Vec<crate::stock::MarketMover>,
@@ -233,6 +768,12 @@ pub struct App {
     pub show_alert_input: bool,
     pub alert_input_buffer: String,
     pub alert_target_symbol: String,
+    pub show_alert_list: bool,
+    pub alert_list_state: ListState,
+    /// Messages for alerts that fired since main last rang for them
+    fired_alerts: Vec<String>,
+    /// The session's fired alerts, timestamped, newest last
+    pub alert_log: VecDeque<String>,
 }
 
 impl App {
@@ -244,10 +785,19 @@ impl App {
             state: AppState::Landing,
             symbol: String::new(),
             timeframe: crate::stock::TimeFrame::OneMonth,
+            interval_override: None,
+            interval_notice: None,
             stock_data: None,
+            csv_path: None,
+            convert_currency: false,
+            provider: crate::provider::default_provider(),
             input_mode: false,
             input_buffer: String::new(),
+            search_results: Vec::new(),
+            search_state: ListState::default(),
+            search_edited_at: None,
             error_message: None,
+            flash: None,
             loading: false,
             live_updates_enabled: false,
             last_live_price: None,
@@ -267,6 +817,7 @@ impl App {
                 ("META", "Meta Platforms Inc."),
             ],
 			ws_should_stop: Arc::new(Mutex::new(false)),
+            ws_manager: None,
             ws_status: WebSocketStatus::Idle,
             ws_last_update: None,
             ws_error_log: VecDeque::new(),
@@ -274,20 +825,109 @@ impl App {
             show_error_log: false,
             show_candlesticks: false,
             show_volume: false,
+            show_candle_counts: false,
             show_sma: false,
+            sma_periods: crate::config::sma_periods(),
+            show_ema: false,
+            ema_periods: crate::config::ema_periods(),
+            volatility_period: crate::config::volatility_period(),
+            rsi_period: crate::config::rsi_period(),
+            macd_periods: crate::config::macd_periods(),
+            stochastic_periods: crate::config::stochastic_periods(),
+            indicator_values: IndicatorValues::default(),
+            chart_cursor: None,
+            zoom_level: 0,
+            pan_offset: 0,
+            show_trend: false,
+            log_scale: false,
+            area_fill: crate::config::area_fill(),
+            baseline: crate::config::baseline(),
+            braille_candles: crate::config::braille_candles(),
+            smooth_line: crate::config::smooth_line(),
+            drawdown_shading: crate::config::drawdown_shading(),
+            session_gaps: crate::config::session_gaps(),
+            session_gap: crate::config::session_gap(),
+            show_trade_feed: false,
+            full_screen: false,
+            display_mode: crate::config::display_mode(),
+            time_zone: TimeZoneMode::from_config(crate::config::time_zone().as_deref()),
+            show_appearance: false,
+            appearance_cursor: 0,
+            show_patterns: false,
+            show_pattern_list: false,
+            pivot_levels: None,
+            pivot_symbol: None,
+            pivots_pending: false,
+            candles_pending: false,
+            show_pivots: false,
+            extreme_flash: None,
+            indicator_panes: Vec::new(),
+            show_indicators: false,
+            show_stats: false,
+            show_candle_detail: false,
+            compare_symbol: None,
+            compare_data: None,
+            show_compare_input: false,
+            compare_input_buffer: String::new(),
+            compare_stale: false,
+            indicator_cursor: 0,
+            show_params: false,
+            param_cursor: 0,
+            param_editing: false,
+            param_input_buffer: String::new(),
+            param_error: None,
             // Live mode fields
             show_live_mode_select: false,
+            show_earnings: false,
+            show_profile: false,
+            profiles: HashMap::new(),
+            earnings_requested_for: None,
+            market_hours: None,
+            live_polling_fallback: crate::websocket::finnhub_api_key().is_none(),
             live_trades: VecDeque::new(),
             live_candles: VecDeque::new(),
             current_candle: None,
+            live_scroll: 0,
+            live_cursor: None,
             candle_interval: CandleInterval::OneMinute,
             total_live_volume: 0,
             total_trade_count: 0,
+            coalesced_trades: 0,
+            tape_sizes: crate::config::tape_min_sizes(),
+            tape_filter: 0,
+            trade_buffer: crate::config::live_trades(),
+            tick_buffer: crate::config::live_ticks(),
+            candle_buffer: crate::config::live_candles(),
+            live_pause: None,
+            recorder: None,
+            replay_speed: None,
+            replay_clock: None,
+            pending_replay: None,
+            cumulative_delta: 0,
+            candle_delta: 0,
+            session_high: None,
+            session_low: None,
+            session_notional: 0.0,
+            session_quantity: 0.0,
+            candle_volume_carry: 0.0,
+            session_extreme_flash: None,
+            bid_ask: None,
+            bid_ask_requested_at: None,
+            bid_ask_in_flight: false,
+            bid_ask_denied: false,
+            last_tick_side: 0,
+            recent_trade_times: VecDeque::new(),
+            trade_activity: [0; ACTIVITY_SECS],
+            activity_second: None,
+            live_batches: VecDeque::new(),
+            live_session_start: None,
             show_help: false,
             watchlist: crate::watchlist::load(),
             watchlist_state: ListState::default(),
             landing_panel: LandingPanel::Popular,
             landing_quotes: HashMap::new(),
+            sparklines: HashMap::new(),
+            sparklines_requested_at: None,
             market_gainers: Vec::new(),
             market_losers: Vec::new(),
             market_active: Vec::new(),
@@ -301,6 +941,10 @@ impl App {
             show_alert_input: false,
             alert_input_buffer: String::new(),
             alert_target_symbol: String::new(),
+            show_alert_list: false,
+            alert_list_state: ListState::default(),
+            fired_alerts: Vec::new(),
+            alert_log: VecDeque::new(),
         }
     }
 
@@ -309,89 +953,1221 @@ impl App {
         self.state = AppState::Chart;
     }
 
-    pub fn apply_stock_data(&mut self, symbol: &str, result: Result<StockData, String>) {
-        self.loading = false;
-        if self.symbol != symbol {
-            return; // stale response for a symbol we've since navigated away from
+    /// Show a CSV file in the chart view instead of fetching a symbol.
+    /// Bad rows go to the error log; the rest still chart.
+    pub fn load_csv(&mut self, path: std::path::PathBuf) {
+        self.state = AppState::Chart;
+        self.loading = false;
+        self.live_updates_enabled = false;
+        match crate::stock::load_from_csv(&path) {
+            Ok((data, warnings)) => {
+                for warning in warnings {
+                    self.add_error_to_log(warning);
+                }
+                self.symbol = data.symbol.clone();
+                self.stock_data = Some(data);
+                self.error_message = None;
+                self.chart_cursor = None;
+                self.zoom_level = 0;
+                self.pan_offset = 0;
+                self.refresh_indicators();
+            }
+            Err(e) => {
+                self.add_error_to_log(e.to_string());
+                self.stock_data = None;
+                self.error_message = Some(format!(
+                    "Could not load \"{}\"\n\n{}\n\n'b' to go back  'e' for error details",
+                    path.display(), e
+                ));
+            }
+        }
+        self.csv_path = Some(path);
+    }
+
+    pub fn set_flash(&mut self, message: String) {
+        self.flash = Some((message, Instant::now()));
+    }
+
+    /// Drop the flash message and high/low marker flash once they have been up
+    /// long enough. Returns true when either was cleared, so the caller knows to redraw.
+    pub fn expire_flash(&mut self) -> bool {
+        let mut cleared = false;
+        if self.flash.as_ref().is_some_and(|(_, shown)| shown.elapsed() >= Duration::from_secs(FLASH_SECS)) {
+            self.flash = None;
+            cleared = true;
+        }
+        if self.extreme_flash.is_some_and(|at| at.elapsed() >= Duration::from_secs(EXTREME_FLASH_SECS)) {
+            self.extreme_flash = None;
+            cleared = true;
+        }
+        if self.session_extreme_flash.is_some_and(|(at, _)| at.elapsed() >= Duration::from_secs(EXTREME_FLASH_SECS)) {
+            self.session_extreme_flash = None;
+            cleared = true;
+        }
+        cleared
+    }
+
+    /// Save the loaded chart series as CSV in the export directory.
+    pub fn export_chart_csv(&mut self) {
+        let Some(ref data) = self.stock_data else { return; };
+        let dir = crate::config::export_dir();
+        match crate::stock::export_csv(data, self.timeframe.short_label(), &dir) {
+            Ok(path) => self.set_flash(format!("Exported {}", path.display())),
+            Err(e) => {
+                self.add_error_to_log(format!("Export failed: {}", e));
+                self.set_flash("Export failed — press 'e' for details".to_string());
+            }
+        }
+    }
+
+    /// Draw the chart as shown, line or candles with its SMA/EMA overlays, to
+    /// a PNG in the export directory. Needs the `png` feature.
+    pub fn export_chart_png(&mut self) {
+        #[cfg(feature = "png")]
+        {
+            let Some(ref data) = self.stock_data else { return; };
+            let candles = if self.show_candlesticks { Some(self.convert_to_candlesticks()) } else { None };
+            // The overlays are indexed by candle only when there are candles to index
+            let candles = candles.filter(|c| !c.is_empty());
+            let values = &self.indicator_values;
+            let mut overlays = Vec::new();
+            if self.show_sma {
+                overlays.extend(self.sma_periods.iter().zip(&values.sma)
+                    .map(|(p, points)| png::Overlay { label: format!("SMA {}", p), points }));
+            }
+            if self.show_ema {
+                overlays.extend(self.ema_periods.iter().zip(&values.ema)
+                    .map(|(p, points)| png::Overlay { label: format!("EMA {}", p), points }));
+            }
+            let dir = crate::config::export_dir();
+            let result = png::export_png(data, candles.as_deref(), self.timeframe, self.active_interval(), &overlays, &dir);
+            match result {
+                Ok(path) => self.set_flash(format!("Saved {}", path.display())),
+                Err(e) => {
+                    self.add_error_to_log(format!("PNG export failed: {}", e));
+                    self.set_flash("PNG export failed — press 'e' for details".to_string());
+                }
+            }
+        }
+        #[cfg(not(feature = "png"))]
+        self.set_flash("PNG export needs a build with --features png".to_string());
+    }
+
+    /// Write the current screen, at the terminal's size, as a text file in
+    /// the export directory, for pasting an ASCII chart elsewhere.
+    pub fn save_snapshot(&mut self, width: u16, height: u16) {
+        use std::io::Write;
+
+        let result = render_to_text(self, width, height).and_then(|text| {
+            let dir = crate::config::export_dir();
+            let label = match self.state {
+                AppState::LiveTicker => "live",
+                AppState::LiveCandles => self.candle_interval.to_string(),
+                _ => self.timeframe.short_label(),
+            };
+            let stem = format!(
+                "charty_{}_{}_{}",
+                crate::stock::file_safe_symbol(&self.symbol),
+                label,
+                chrono::Local::now().format("%Y%m%d_%H%M%S"),
+            );
+            let mut path = dir.join(format!("{}.txt", stem));
+            let mut counter = 2;
+            while path.exists() {
+                path = dir.join(format!("{}_{}.txt", stem, counter));
+                counter += 1;
+            }
+            let mut file = std::fs::OpenOptions::new().write(true).create_new(true).open(&path)
+                .map_err(|e| format!("Could not create {}: {}", path.display(), e))?;
+            file.write_all(text.as_bytes())?;
+            Ok(path)
+        });
+        match result {
+            Ok(path) => self.set_flash(format!("Snapshot saved to {}", path.display())),
+            Err(e) => {
+                self.add_error_to_log(format!("Snapshot failed: {}", e));
+                self.set_flash("Snapshot failed — press 'e' for details".to_string());
+            }
+        }
+    }
+
+    /// Save the live trades and candles to a JSON file in the export directory.
+    pub fn export_live_session(&mut self) {
+        let session = crate::session::LiveSession {
+            symbol: self.symbol.clone(),
+            candle_interval: self.candle_interval,
+            exported_at: Utc::now(),
+            trades: self.live_trades.iter().cloned().collect(),
+            candles: self.live_candles.iter().cloned().collect(),
+            current_candle: self.current_candle.clone(),
+        };
+        match crate::session::export(&session, &crate::config::export_dir()) {
+            Ok(path) => self.set_flash(format!("Saved {}", path.display())),
+            Err(e) => {
+                self.add_error_to_log(format!("Live export failed: {}", e));
+                self.set_flash("Export failed — press 'e' for details".to_string());
+            }
+        }
+    }
+
+    /// Rebuild live state from an exported session by feeding its trades back
+    /// through `update_live_price_at`. Candles older than the first trade are
+    /// copied over as-is since their trades have already rolled off.
+    pub fn replay_live_session(&mut self, path: &std::path::Path) {
+        let session = match crate::session::load(path) {
+            Ok(s) => s,
+            Err(e) => {
+                self.add_error_to_log(e.to_string());
+                self.error_message = Some(format!("Could not replay session\n\n{}", e));
+                self.state = AppState::Chart;
+                return;
+            }
+        };
+        self.symbol = session.symbol;
+        self.candle_interval = session.candle_interval;
+        self.clear_live_data();
+        self.state = AppState::LiveCandles;
+
+        let interval_secs = self.candle_interval.to_secs() as i64;
+        let first_bucket = session.trades.last()
+            .map(|t| t.timestamp.timestamp() / interval_secs * interval_secs);
+        self.live_candles.extend(
+            session.candles.into_iter()
+                .filter(|c| first_bucket.is_none_or(|b| c.timestamp.timestamp() < b)),
+        );
+        for trade in session.trades.iter().rev() {
+            self.update_live_price_at(trade.price, trade.volume, trade.timestamp);
+        }
+    }
+
+    /// Load a recording made with 'R' and queue it to play back through the
+    /// live channel at `speed` times its recorded pace.
+    pub fn replay_recording(&mut self, path: &std::path::Path, speed: f64) {
+        let records = match crate::session::load_recording(path) {
+            Ok(records) if !records.is_empty() => records,
+            Ok(_) => {
+                self.error_message = Some(format!("Could not replay recording\n\n{} is empty", path.display()));
+                self.state = AppState::Chart;
+                return;
+            }
+            Err(e) => {
+                self.add_error_to_log(e.to_string());
+                self.error_message = Some(format!("Could not replay recording\n\n{}", e));
+                self.state = AppState::Chart;
+                return;
+            }
+        };
+        self.symbol = records[0].price.symbol.clone();
+        self.clear_live_data();
+        self.state = AppState::LiveTicker;
+        self.live_updates_enabled = true;
+        self.replay_speed = Some(speed);
+        self.replay_clock = None;
+        self.pending_replay = Some(records);
+    }
+
+    pub fn take_replay_request(&mut self) -> Option<(Vec<crate::session::Record>, f64)> {
+        let records = self.pending_replay.take()?;
+        Some((records, self.replay_speed.unwrap_or(1.0)))
+    }
+
+    /// Back to the wall clock once a real feed takes over from a replay.
+    pub fn end_replay(&mut self) {
+        self.replay_speed = None;
+        self.replay_clock = None;
+        self.pending_replay = None;
+    }
+
+    /// Start or stop recording the live feed to a file in the export directory.
+    pub fn toggle_recording(&mut self) {
+        if let Some(recorder) = self.recorder.take() {
+            self.set_flash(format!("Recorded {} updates to {}", recorder.records, recorder.path.display()));
+            return;
+        }
+        match crate::session::Recorder::start(&self.symbol, &crate::config::export_dir()) {
+            Ok(recorder) => {
+                self.set_flash(format!("Recording to {}", recorder.path.display()));
+                self.recorder = Some(recorder);
+            }
+            Err(e) => {
+                self.add_error_to_log(format!("Recording failed: {}", e));
+                self.set_flash("Recording failed — press 'e' for details".to_string());
+            }
+        }
+    }
+
+    /// Append an update to the recording, if one is running. A failed write
+    /// stops the recording rather than retrying every batch.
+    pub fn record_live_price(&mut self, price: &LivePrice) {
+        let Some(recorder) = self.recorder.as_mut() else { return };
+        if let Err(e) = recorder.record(price) {
+            self.recorder = None;
+            self.add_error_to_log(format!("Recording stopped: {}", e));
+            self.set_flash("Recording stopped — press 'e' for details".to_string());
+        }
+    }
+
+    /// Take every trade batch waiting on `rx` into the candles and volume.
+    /// A running recording gets every batch; only the active symbol's trades
+    /// reach the chart, others have no home yet, and with live updates off
+    /// the backlog is dropped. Returns whether any reached the chart.
+    pub fn drain_live_prices(&mut self, rx: &mut tokio::sync::mpsc::UnboundedReceiver<LivePrice>) -> bool {
+        let mut traded = false;
+        while let Ok(live_price) = rx.try_recv() {
+            self.record_live_price(&live_price);
+            if self.live_updates_enabled && live_price.symbol == self.symbol {
+                self.update_live_batch(&live_price);
+                traded = true;
+            }
+        }
+        traded
+    }
+
+    pub fn toggle_pane(&mut self, pane: IndicatorPane) {
+        if let Some(pos) = self.indicator_panes.iter().position(|&p| p == pane) {
+            self.indicator_panes.remove(pos);
+        } else {
+            self.indicator_panes.push(pane);
+        }
+    }
+
+    pub fn indicator_enabled(&self, indicator: Indicator) -> bool {
+        match indicator {
+            Indicator::Sma => self.show_sma,
+            Indicator::Ema => self.show_ema,
+            Indicator::Trend => self.show_trend,
+            Indicator::Patterns => self.show_patterns,
+            Indicator::Volume => self.show_volume,
+            Indicator::Pane(pane) => self.indicator_panes.contains(&pane),
+        }
+    }
+
+    pub fn toggle_indicator(&mut self, indicator: Indicator) {
+        match indicator {
+            Indicator::Sma => self.show_sma = !self.show_sma,
+            Indicator::Ema => self.show_ema = !self.show_ema,
+            Indicator::Trend => self.show_trend = !self.show_trend,
+            Indicator::Patterns => self.show_patterns = !self.show_patterns,
+            Indicator::Volume => self.show_volume = !self.show_volume,
+            Indicator::Pane(pane) => self.toggle_pane(pane),
+        }
+    }
+
+    pub fn appearance_enabled(&self, option: AppearanceOption) -> bool {
+        match option {
+            AppearanceOption::Candlesticks => self.show_candlesticks,
+            AppearanceOption::AreaFill => self.area_fill,
+            AppearanceOption::Baseline => self.baseline,
+            AppearanceOption::LogScale => self.log_scale,
+            AppearanceOption::BrailleCandles => self.braille_candles == Some(true),
+            AppearanceOption::Smoothing => self.smooth_line,
+            AppearanceOption::Drawdown => self.drawdown_shading,
+            AppearanceOption::SessionGaps => self.session_gaps,
+        }
+    }
+
+    /// Clock for displayed times. Exchange time falls back to local for data
+    /// without a known exchange zone, such as CSV files.
+    pub fn display_zone(&self) -> DisplayZone {
+        let offset = match self.time_zone {
+            TimeZoneMode::Local => None,
+            TimeZoneMode::Utc => chrono::FixedOffset::east_opt(0),
+            TimeZoneMode::Exchange => self.stock_data.as_ref()
+                .and_then(|d| d.exchange_timezone.as_ref())
+                .and_then(|(_, secs)| chrono::FixedOffset::east_opt(*secs as i32)),
+        };
+        DisplayZone { offset }
+    }
+
+    /// Short name of the active clock for the header: "local", "UTC" or the
+    /// exchange's zone abbreviation.
+    pub fn time_zone_label(&self) -> String {
+        match self.time_zone {
+            TimeZoneMode::Utc => "UTC".to_string(),
+            TimeZoneMode::Exchange => match self.stock_data.as_ref().and_then(|d| d.exchange_timezone.as_ref()) {
+                Some((name, _)) => name.clone(),
+                None => "local".to_string(),
+            },
+            TimeZoneMode::Local => "local".to_string(),
+        }
+    }
+
+    /// Step to the next clock and remember it in config.json.
+    pub fn cycle_time_zone(&mut self) {
+        self.time_zone = self.time_zone.next();
+        let value = serde_json::Value::String(self.time_zone.config_value().to_string());
+        if let Err(e) = crate::config::save_settings(&[("time_zone", value)]) {
+            self.add_error_to_log(format!("Could not save time_zone to config.json: {}", e));
+        }
+        self.set_flash(format!("Times shown in {}", self.time_zone_label()));
+    }
+
+    /// Smallest trade the tape lists, `None` when it lists them all. Only
+    /// the display is filtered; candles, totals and stats see every trade.
+    pub fn tape_min_size(&self) -> Option<u64> {
+        self.tape_filter.checked_sub(1).and_then(|i| self.tape_sizes.get(i).copied())
+    }
+
+    pub fn cycle_tape_filter(&mut self) {
+        self.tape_filter = (self.tape_filter + 1) % (self.tape_sizes.len() + 1);
+        self.set_flash(match self.tape_min_size() {
+            Some(min) => format!("Tape: trades of {} shares or more", min),
+            None => "Tape: all trades".to_string(),
+        });
+    }
+
+    /// Whether candles on a terminal `rows` high use the Braille renderer.
+    pub fn use_braille_candles(&self, rows: u16) -> bool {
+        self.braille_candles.unwrap_or(rows < BRAILLE_CANDLE_ROWS)
+    }
+
+    /// Flip an appearance option. The line styles are remembered in config.json.
+    pub fn toggle_appearance(&mut self, option: AppearanceOption) {
+        let (key, value) = match option {
+            AppearanceOption::Candlesticks => {
+                self.show_candlesticks = !self.show_candlesticks;
+                // Indicators and the crosshair index bars, which are now candles or points
+                self.chart_cursor = None;
+                self.refresh_indicators();
+                return;
+            }
+            AppearanceOption::AreaFill => {
+                self.area_fill = !self.area_fill;
+                ("area_fill", self.area_fill)
+            }
+            AppearanceOption::Baseline => {
+                self.baseline = !self.baseline;
+                ("baseline", self.baseline)
+            }
+            AppearanceOption::LogScale => {
+                self.log_scale = !self.log_scale;
+                return;
+            }
+            AppearanceOption::BrailleCandles => {
+                let braille = self.braille_candles != Some(true);
+                self.braille_candles = Some(braille);
+                ("braille_candles", braille)
+            }
+            AppearanceOption::Smoothing => {
+                self.smooth_line = !self.smooth_line;
+                ("smooth_line", self.smooth_line)
+            }
+            AppearanceOption::Drawdown => {
+                self.drawdown_shading = !self.drawdown_shading;
+                ("drawdown_shading", self.drawdown_shading)
+            }
+            AppearanceOption::SessionGaps => {
+                self.session_gaps = !self.session_gaps;
+                ("session_gaps", self.session_gaps)
+            }
+        };
+        if let Err(e) = crate::config::save_settings(&[(key, serde_json::Value::Bool(value))]) {
+            self.add_error_to_log(format!("Could not save {} to config.json: {}", key, e));
+        }
+    }
+
+    /// Popup label, with the configured periods where they apply.
+    pub fn indicator_label(&self, indicator: Indicator) -> String {
+        let periods = |p: &[usize]| p.iter().map(|n| n.to_string()).collect::<Vec<_>>().join("/");
+        match indicator {
+            Indicator::Sma => format!("SMA {} (overlay)", periods(&self.sma_periods)),
+            Indicator::Ema => format!("EMA {} (overlay)", periods(&self.ema_periods)),
+            Indicator::Trend => "Trend line ±1σ (overlay)".to_string(),
+            Indicator::Patterns => "Candle patterns (markers)".to_string(),
+            Indicator::Volume => "Volume (pane)".to_string(),
+            Indicator::Pane(IndicatorPane::Rsi) => format!("RSI {} (pane)", self.rsi_period),
+            Indicator::Pane(IndicatorPane::Macd) => format!("MACD {} (pane)", periods(&self.macd_periods)),
+            Indicator::Pane(IndicatorPane::Stochastic) => format!("Stochastic {} (pane)", periods(&self.stochastic_periods)),
+            Indicator::Pane(IndicatorPane::Obv) => "On-balance volume (pane)".to_string(),
+            Indicator::Pane(IndicatorPane::Volatility) => format!("Realized vol {} (pane)", self.volatility_period),
+        }
+    }
+
+    /// Recompute `indicator_values` from the loaded data and current periods.
+    pub fn refresh_indicators(&mut self) {
+        use crate::indicators;
+
+        let Some(data) = self.stock_data.as_ref() else {
+            self.indicator_values = IndicatorValues::default();
+            self.chart_cursor = None;
+            return;
+        };
+        let synthesized = self.convert_to_candlesticks();
+        // The line chart has no OHLC, so every point stands in for all three there
+        let (closes, highs, lows, volumes, timestamps) = if self.show_candlesticks && !synthesized.is_empty() {
+            (
+                synthesized.iter().map(|c| c.close).collect(),
+                synthesized.iter().map(|c| c.high).collect(),
+                synthesized.iter().map(|c| c.low).collect(),
+                synthesized.iter().map(|c| c.volume as f64).collect(),
+                synthesized.iter().map(|c| c.timestamp).collect(),
+            )
+        } else {
+            (data.prices.clone(), data.prices.clone(), data.prices.clone(), data.volumes.clone(), data.timestamps.clone())
+        };
+
+        let [fast, slow, signal] = self.macd_periods;
+        let [period, smooth_k, smooth_d] = self.stochastic_periods;
+        let volatility = crate::analytics::periods_per_year(&timestamps)
+            .map(|per_year| indicators::realized_volatility(&closes, self.volatility_period, per_year))
+            .unwrap_or_default();
+        let atr = {
+            let highs: Vec<f64> = synthesized.iter().map(|c| c.high).collect();
+            let lows: Vec<f64> = synthesized.iter().map(|c| c.low).collect();
+            let closes: Vec<f64> = synthesized.iter().map(|c| c.close).collect();
+            indicators::atr(&highs, &lows, &closes, ATR_PERIOD).last().map(|&(_, v)| v)
+        };
+        self.indicator_values = IndicatorValues {
+            sma: self.sma_periods.iter().map(|&p| indicators::sma(&closes, p)).collect(),
+            ema: self.ema_periods.iter().map(|&p| indicators::ema(&closes, p)).collect(),
+            rsi: indicators::rsi(&closes, self.rsi_period),
+            macd: indicators::macd(&closes, fast, slow, signal),
+            stochastic: indicators::stochastic(&highs, &lows, &closes, period, smooth_k, smooth_d),
+            obv: indicators::obv(&closes, &volumes),
+            volatility,
+            trend: indicators::linear_regression(&closes),
+            atr,
+            closes,
+            timestamps,
+        };
+        let last = self.indicator_values.closes.len().checked_sub(1);
+        self.chart_cursor = self.chart_cursor.and_then(|i| last.map(|last| i.min(last)));
+        self.clamp_pan();
+    }
+
+    /// Bars of the series the line chart shows at the current zoom. The
+    /// candlestick renderer fits candles to its width by itself, so it always
+    /// gets the full range.
+    pub fn visible_range(&self) -> std::ops::Range<usize> {
+        let len = self.indicator_values.closes.len();
+        let count = self.zoom_bars();
+        let end = len - self.pan_offset.min(len - count);
+        end - count..end
+    }
+
+    /// Number of bars in the zoomed window.
+    fn zoom_bars(&self) -> usize {
+        let len = self.indicator_values.closes.len();
+        if self.show_candlesticks || self.compare_data.is_some() || self.zoom_level == 0 {
+            return len;
+        }
+        (len * ZOOM_LEVELS[self.zoom_level] / 100).max(MIN_ZOOM_BARS).min(len)
+    }
+
+    /// Step the line chart's zoom in or out, without refetching. The right
+    /// edge of the window stays put.
+    pub fn zoom(&mut self, zoom_in: bool) {
+        self.zoom_level = if zoom_in {
+            (self.zoom_level + 1).min(ZOOM_LEVELS.len() - 1)
+        } else {
+            self.zoom_level.saturating_sub(1)
+        };
+        self.clamp_pan();
+    }
+
+    /// Shift the zoomed window `delta` bars, negative for earlier. Stops at
+    /// either end of the loaded series.
+    pub fn pan(&mut self, delta: isize) {
+        self.pan_offset = self.pan_offset.saturating_add_signed(-delta);
+        self.clamp_pan();
+    }
+
+    /// Keep the pan offset inside the series and the crosshair on screen.
+    fn clamp_pan(&mut self) {
+        let len = self.indicator_values.closes.len();
+        self.pan_offset = self.pan_offset.min(len - self.zoom_bars());
+        let range = self.visible_range();
+        if let Some(i) = self.chart_cursor
+            && let Some(last) = range.end.checked_sub(1)
+        {
+            self.chart_cursor = Some(i.clamp(range.start, last));
+        }
+    }
+
+    /// Show the crosshair on the latest visible bar, or hide it.
+    pub fn toggle_chart_cursor(&mut self) {
+        self.chart_cursor = match self.chart_cursor {
+            Some(_) => None,
+            None => self.visible_range().end.checked_sub(1),
+        };
+    }
+
+    /// Step the crosshair `delta` bars, starting it on the latest visible bar
+    /// if it's hidden. Stepping past the edge of the zoomed window pans it.
+    pub fn move_chart_cursor(&mut self, delta: isize) {
+        let range = self.visible_range();
+        let Some(last) = range.end.checked_sub(1) else { return };
+        let Some(i) = self.chart_cursor else {
+            self.chart_cursor = Some(last);
+            return;
+        };
+        let len = self.indicator_values.closes.len();
+        let i = i.saturating_add_signed(delta).min(len - 1);
+        if i < range.start {
+            self.pan(i as isize - range.start as isize);
+        } else if i > last {
+            self.pan((i - last) as isize);
+        }
+        self.chart_cursor = Some(i);
+    }
+
+    /// Jump the crosshair to the first or last visible bar.
+    pub fn jump_chart_cursor(&mut self, to_end: bool) {
+        let range = self.visible_range();
+        let Some(last) = range.end.checked_sub(1) else { return };
+        self.chart_cursor = Some(if to_end { last } else { range.start });
+    }
+
+    /// Values of the enabled indicators at bar `index` of `indicator_values`,
+    /// `None` where an indicator is still warming up.
+    pub fn indicator_readout(&self, index: usize) -> Vec<(String, Option<f64>)> {
+        let values = &self.indicator_values;
+        let mut readout = Vec::new();
+        if self.show_sma {
+            for (period, points) in self.sma_periods.iter().zip(&values.sma) {
+                readout.push((format!("SMA{}", period), value_at(points, index)));
+            }
+        }
+        if self.show_ema {
+            for (period, points) in self.ema_periods.iter().zip(&values.ema) {
+                readout.push((format!("EMA{}", period), value_at(points, index)));
+            }
+        }
+        if self.show_trend {
+            let trend = values.trend.filter(|_| index < values.closes.len()).map(|fit| fit.at(index as f64));
+            readout.push(("Trend".to_string(), trend));
+        }
+        for &pane in &self.indicator_panes {
+            match pane {
+                IndicatorPane::Rsi => readout.push(("RSI".to_string(), value_at(&values.rsi, index))),
+                IndicatorPane::Macd => {
+                    readout.push(("MACD".to_string(), value_at(&values.macd.macd, index)));
+                    readout.push(("Signal".to_string(), value_at(&values.macd.signal, index)));
+                }
+                IndicatorPane::Stochastic => {
+                    readout.push(("%K".to_string(), value_at(&values.stochastic.k, index)));
+                    readout.push(("%D".to_string(), value_at(&values.stochastic.d, index)));
+                }
+                IndicatorPane::Obv => readout.push(("OBV".to_string(), value_at(&values.obv, index))),
+                IndicatorPane::Volatility => readout.push(("RV".to_string(), value_at(&values.volatility, index))),
+            }
+        }
+        readout
+    }
+
+    /// Parameter rows for the indicators that are switched on.
+    pub fn editable_params(&self) -> Vec<IndicatorParam> {
+        IndicatorParam::ALL.into_iter()
+            .filter(|p| self.indicator_enabled(p.indicator()))
+            .collect()
+    }
+
+    pub fn param_values(&self, param: IndicatorParam) -> Vec<usize> {
+        match param {
+            IndicatorParam::Sma => self.sma_periods.clone(),
+            IndicatorParam::Ema => self.ema_periods.clone(),
+            IndicatorParam::Rsi => vec![self.rsi_period],
+            IndicatorParam::Macd => self.macd_periods.to_vec(),
+            IndicatorParam::Stochastic => self.stochastic_periods.to_vec(),
+            IndicatorParam::Volatility => vec![self.volatility_period],
+        }
+    }
+
+    /// Start editing the highlighted parameter with its current value.
+    pub fn begin_param_edit(&mut self) {
+        let Some(&param) = self.editable_params().get(self.param_cursor) else { return };
+        self.param_input_buffer = self.param_values(param).iter()
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        self.param_editing = true;
+        self.param_error = None;
+    }
+
+    /// Validate and apply the entry being edited. Panes and overlays pick the
+    /// new periods up on the next frame; the value is also written to
+    /// config.json so it survives a restart.
+    pub fn commit_param_edit(&mut self) {
+        let Some(&param) = self.editable_params().get(self.param_cursor) else {
+            self.param_editing = false;
+            return;
+        };
+        let values = match param.parse(&self.param_input_buffer) {
+            Ok(v) => v,
+            Err(e) => {
+                self.param_error = Some(e);
+                return;
+            }
+        };
+        match param {
+            IndicatorParam::Sma => self.sma_periods = values.clone(),
+            IndicatorParam::Ema => self.ema_periods = values.clone(),
+            IndicatorParam::Rsi => self.rsi_period = values[0],
+            IndicatorParam::Macd => self.macd_periods = [values[0], values[1], values[2]],
+            IndicatorParam::Stochastic => self.stochastic_periods = [values[0], values[1], values[2]],
+            IndicatorParam::Volatility => self.volatility_period = values[0],
+        }
+        self.param_editing = false;
+        self.param_error = None;
+        self.param_input_buffer.clear();
+        self.refresh_indicators();
+
+        let value = match param {
+            IndicatorParam::Rsi | IndicatorParam::Volatility => serde_json::json!(values[0]),
+            _ => serde_json::json!(values),
+        };
+        if let Err(e) = crate::config::save_settings(&[(param.config_key(), value)]) {
+            self.add_error_to_log(format!("Could not save {} to config.json: {}", param.config_key(), e));
+        }
+    }
+
+    /// Flip between native and display-currency prices for the loaded chart.
+    pub fn toggle_currency(&mut self) {
+        let Some(ref mut data) = self.stock_data else { return; };
+        let Some(fx) = data.fx.clone() else {
+            let message = match data.fx_error {
+                Some(_) => "No exchange rate — press 'e' for details".to_string(),
+                None => format!("Prices are already in {}", data.currency.as_deref().unwrap_or("USD")),
+            };
+            self.set_flash(message);
+            return;
+        };
+        self.convert_currency = !data.converted;
+        data.set_converted(self.convert_currency);
+        self.refresh_indicators();
+        let message = if self.convert_currency {
+            format!("Showing {} (1 {} = {:.4} {})", fx.display_currency, fx.native_currency, fx.rate, fx.display_currency)
+        } else {
+            format!("Showing native {}", fx.native_currency)
+        };
+        self.set_flash(message);
+    }
+
+    /// Interval the chart is currently requested at.
+    pub fn active_interval(&self) -> &'static str {
+        self.interval_override.unwrap_or(self.timeframe.to_interval())
+    }
+
+    /// Step to the next interval Yahoo supports for the current timeframe;
+    /// wraps back to the timeframe's default.
+    pub fn cycle_interval(&mut self) {
+        self.interval_notice = None;
+        let default = self.timeframe.to_interval();
+        let options: Vec<&'static str> = self.timeframe.supported_intervals()
+            .iter()
+            .copied()
+            .filter(|&i| i != default)
+            .collect();
+        self.interval_override = match self.interval_override {
+            None => options.first().copied(),
+            Some(current) => options.iter()
+                .position(|&i| i == current)
+                .and_then(|pos| options.get(pos + 1))
+                .copied(),
+        };
+    }
+
+    /// Switch timeframe, dropping an interval override the new range can't serve.
+    pub fn set_timeframe(&mut self, timeframe: crate::stock::TimeFrame) {
+        self.timeframe = timeframe;
+        self.interval_notice = None;
+        self.zoom_level = 0;
+        self.pan_offset = 0;
+        if let Some(interval) = self.interval_override
+            && let Err(reason) = crate::stock::check_interval(timeframe, interval)
+        {
+            self.interval_override = None;
+            self.interval_notice = Some(format!("{}, using {}", reason, timeframe.to_interval()));
+        }
+    }
+
+    pub fn apply_stock_data(&mut self, symbol: &str, result: Result<StockData, String>) {
+        self.loading = false;
+        if self.symbol != symbol {
+            return; // stale response for a symbol we've since navigated away from
+        }
+        match result {
+            Ok(mut data) => {
+                // Prefer the market state from the live quote API (more reliable than chart meta)
+                crate::stock::log_debug(&format!("[apply_stock_data] {} chart market_state={:?}, landing_quotes has entry={}", symbol, data.market_state, self.landing_quotes.contains_key(symbol)));
+                if let Some(q) = self.landing_quotes.get(symbol) {
+                    crate::stock::log_debug(&format!("[apply_stock_data] {} overriding with quote market_state={:?}", symbol, q.market_state));
+                    data.market_state = q.market_state.clone();
+                }
+                self.market_hours = data.market_hours.clone();
+                // Earnings don't change with the timeframe; keep them across refetches
+                if let Some(ref old) = self.stock_data
+                    && old.symbol == data.symbol
+                {
+                    data.earnings = old.earnings.clone();
+                    // A refresh that appends bars leaves a panned view where it was
+                    if self.pan_offset > 0 {
+                        self.pan_offset += data.prices.len().saturating_sub(old.prices.len());
+                    }
+                } else {
+                    self.zoom_level = 0;
+                    self.pan_offset = 0;
+                }
+                if let Some(ref e) = data.fx_error {
+                    self.add_error_to_log(format!("{}: {}", symbol, e));
+                    if self.convert_currency {
+                        self.set_flash("Showing native prices — no exchange rate, press 'e' for details".to_string());
+                    }
+                }
+                data.set_converted(self.convert_currency);
+                self.stock_data = Some(data);
+                self.error_message = None;
+                // The old bar index points at a different date in the new series
+                self.chart_cursor = None;
+                self.refresh_indicators();
+                // The comparison has to match the new range; drop it until it's refetched
+                if self.compare_symbol.is_some() {
+                    self.compare_data = None;
+                    self.compare_stale = true;
+                }
+            }
+            Err(e) => {
+                let full_error = format!("Error fetching {}: {}", symbol, e);
+                self.add_error_to_log(full_error);
+                let hint = if e.starts_with(crate::stock::TIMEOUT_MESSAGE) {
+                    crate::stock::TIMEOUT_MESSAGE
+                } else {
+                    "Check the symbol and try again"
+                };
+                self.error_message = Some(format!(
+                    "Could not load data for \"{}\"\n\n{}\n\n's' to search  'b' to go back  'e' for error details",
+                    symbol, hint
+                ));
+            }
+        }
+    }
+
+    /// Start comparing against `symbol`; the caller fetches it.
+    pub fn set_compare(&mut self, symbol: String) {
+        self.compare_symbol = Some(symbol);
+        self.compare_data = None;
+        self.compare_stale = false;
+    }
+
+    pub fn clear_compare(&mut self) {
+        self.compare_symbol = None;
+        self.compare_data = None;
+        self.compare_stale = false;
+    }
+
+    /// Comparison symbol to refetch after the main chart reloaded.
+    pub fn take_compare_request(&mut self) -> Option<String> {
+        if !std::mem::take(&mut self.compare_stale) {
+            return None;
+        }
+        self.compare_symbol.clone()
+    }
+
+    pub fn apply_compare_data(&mut self, symbol: &str, timeframe: crate::stock::TimeFrame, result: Result<StockData, String>) {
+        if self.compare_symbol.as_deref() != Some(symbol) || self.timeframe != timeframe {
+            return; // comparison was changed or the timeframe moved on
+        }
+        match result {
+            Ok(data) => self.compare_data = Some(data),
+            Err(e) => {
+                self.add_error_to_log(format!("Error fetching {} for comparison: {}", symbol, e));
+                self.set_flash(format!("Could not load {} to compare", symbol));
+                self.clear_compare();
+            }
+        }
+    }
+
+    /// Popular symbols to fetch sparklines for, once the landing page is up
+    /// and the last batch is older than `SPARKLINE_TTL_SECS`.
+    pub fn take_sparkline_request(&mut self) -> Option<Vec<String>> {
+        if !matches!(self.state, AppState::Landing)
+            || self.sparklines_requested_at.is_some_and(|t| t.elapsed().as_secs() < SPARKLINE_TTL_SECS)
+        {
+            return None;
+        }
+        self.sparklines_requested_at = Some(Instant::now());
+        Some(self.popular_stocks.iter().map(|(t, _)| t.to_string()).collect())
+    }
+
+    /// Store fetched sparklines; a symbol that failed is left without one.
+    pub fn apply_sparklines(&mut self, results: Vec<(String, Result<Sparkline, String>)>) {
+        for (symbol, result) in results {
+            match result {
+                Ok(sparkline) => { self.sparklines.insert(symbol, sparkline); }
+                Err(e) => {
+                    crate::stock::log_debug(&format!("[sparkline] {}: {}", symbol, e));
+                    self.sparklines.remove(&symbol);
+                }
+            }
+        }
+    }
+
+    /// Symbol to fetch earnings for, if the loaded chart doesn't have them yet.
+    pub fn take_earnings_request(&mut self) -> Option<String> {
+        if self.live_polling_fallback {
+            return None; // Finnhub key required
+        }
+        let data = self.stock_data.as_ref()?;
+        if data.earnings.is_some() || self.earnings_requested_for.as_deref() == Some(data.symbol.as_str()) {
+            return None;
+        }
+        self.earnings_requested_for = Some(data.symbol.clone());
+        Some(data.symbol.clone())
+    }
+
+    pub fn apply_earnings(&mut self, symbol: &str, earnings: Option<crate::stock::EarningsInfo>) {
+        if let Some(ref mut data) = self.stock_data
+            && data.symbol == symbol
+        {
+            data.earnings = earnings;
+        }
+    }
+
+    /// Symbol to fetch pivot levels for, when the live symbol has changed since
+    /// the last request.
+    pub fn take_pivot_request(&mut self) -> Option<String> {
+        if self.pivot_symbol.as_deref() == Some(self.symbol.as_str()) {
+            return None;
+        }
+        self.pivot_levels = None;
+        self.pivot_symbol = Some(self.symbol.clone());
+        self.pivots_pending = true;
+        Some(self.symbol.clone())
+    }
+
+    /// Symbol to poll bid/ask for: the live symbol of a Finnhub feed, every
+    /// `BID_ASK_POLL_SECS` with one request out at a time, which keeps well
+    /// inside Finnhub's free-tier 60 calls a minute.
+    pub fn take_bid_ask_request(&mut self) -> Option<String> {
+        let live = matches!(self.state, AppState::LiveTicker | AppState::LiveCandles)
+            && !matches!(self.ws_status, WebSocketStatus::Idle | WebSocketStatus::Replay { .. });
+        if !live
+            || self.live_polling_fallback
+            || self.bid_ask_denied
+            || self.bid_ask_in_flight
+            || crate::websocket::Provider::for_symbol(&self.symbol) != crate::websocket::Provider::Finnhub
+            || self.bid_ask_requested_at.is_some_and(|t| t.elapsed() < Duration::from_secs(BID_ASK_POLL_SECS))
+        {
+            return None;
+        }
+        self.bid_ask_requested_at = Some(Instant::now());
+        self.bid_ask_in_flight = true;
+        Some(self.symbol.clone())
+    }
+
+    pub fn apply_bid_ask(&mut self, symbol: &str, result: Result<Option<crate::stock::BidAsk>, String>) {
+        self.bid_ask_in_flight = false;
+        if symbol != self.symbol {
+            return;
+        }
+        match result {
+            Ok(quote) => self.bid_ask = quote,
+            Err(e) => {
+                self.bid_ask = None;
+                if crate::stock::is_access_denied(&e) {
+                    self.bid_ask_denied = true;
+                    self.add_error_to_log("Bid/ask isn't included in this Finnhub plan; not showing the spread".to_string());
+                }
+            }
+        }
+    }
+
+    pub fn apply_pivot_levels(&mut self, symbol: &str, result: Result<Option<crate::indicators::PivotLevels>, String>) {
+        if self.pivot_symbol.as_deref() != Some(symbol) {
+            return; // symbol changed while the fetch was in flight
+        }
+        self.pivots_pending = false;
+        match result {
+            Ok(levels) => self.pivot_levels = levels,
+            Err(e) => {
+                self.add_error_to_log(format!("Error fetching daily candles for {} pivots: {}", symbol, e));
+                // Let the next visit to live mode try again
+                self.pivot_symbol = None;
+            }
+        }
+    }
+
+    /// Open the profile popup. Returns true when the profile still needs fetching.
+    pub fn open_profile(&mut self) -> bool {
+        self.show_profile = true;
+        !self.live_polling_fallback
+            && !self.symbol.starts_with('^')
+            && !self.profiles.contains_key(&self.symbol)
+    }
+
+    pub fn apply_profile(&mut self, symbol: String, result: Result<Option<crate::stock::CompanyProfile>, String>) {
+        match result {
+            Ok(profile) => { self.profiles.insert(symbol, profile); }
+            Err(e) => {
+                self.add_error_to_log(format!("Profile for {}: {}", symbol, e));
+                self.profiles.insert(symbol, None);
+            }
+        }
+    }
+
+    /// Record a keystroke in the search box; the query fires once typing pauses.
+    pub fn mark_search_edited(&mut self) {
+        self.search_edited_at = Some(Instant::now());
+        self.search_state.select(None);
+        if self.input_buffer.is_empty() {
+            self.search_results.clear();
+        }
+    }
+
+    /// Returns the query to send once the input has been idle for `debounce`.
+    pub fn take_due_search(&mut self, debounce: Duration) -> Option<String> {
+        let edited = self.search_edited_at?;
+        if !self.input_mode || edited.elapsed() < debounce {
+            return None;
+        }
+        self.search_edited_at = None;
+        let query = self.input_buffer.trim();
+        if query.is_empty() { None } else { Some(query.to_string()) }
+    }
+
+    pub fn apply_search_results(&mut self, query: &str, results: Vec<crate::stock::SymbolMatch>) {
+        if !self.input_mode || self.input_buffer.trim() != query {
+            return; // user kept typing; a newer query is on its way
+        }
+        self.search_results = results;
+        self.search_state.select(None);
+    }
+
+    pub fn close_search(&mut self) {
+        self.input_buffer.clear();
+        self.input_mode = false;
+        self.search_results.clear();
+        self.search_state.select(None);
+        self.search_edited_at = None;
+    }
+
+    pub fn next_search_result(&mut self) {
+        if self.search_results.is_empty() { return; }
+        let i = match self.search_state.selected() {
+            Some(i) if i + 1 < self.search_results.len() => i + 1,
+            Some(_) => 0,
+            None => 0,
+        };
+        self.search_state.select(Some(i));
+    }
+
+    pub fn previous_search_result(&mut self) {
+        if self.search_results.is_empty() { return; }
+        let i = match self.search_state.selected() {
+            Some(0) | None => self.search_results.len() - 1,
+            Some(i) => i - 1,
+        };
+        self.search_state.select(Some(i));
+    }
+
+    /// Symbol to open from the search box: the highlighted result, else the typed text.
+    pub fn search_selection(&self) -> Option<String> {
+        if let Some(i) = self.search_state.selected()
+            && let Some((sym, _, _)) = self.search_results.get(i)
+        {
+            return Some(sym.clone());
+        }
+        let typed = self.input_buffer.trim();
+        if typed.is_empty() { None } else { Some(typed.to_uppercase()) }
+    }
+
+    /// Whether entering a live mode should connect straight away: not for a
+    /// symbol the websocket can't carry, nor while its market is shut. Without
+    /// known hours we assume open rather than block the feed.
+    pub fn should_auto_start_live_mode(&self) -> bool {
+        self.live_stream_unavailable().is_none()
+            && self.market_hours
+                .as_ref()
+                .map(|h| h.is_open_at(Utc::now().timestamp()))
+                .unwrap_or(true)
+    }
+
+    /// Why the live websocket has no stream for the symbol, if it hasn't.
+    /// Delayed polling covers anything Yahoo quotes.
+    pub fn live_stream_unavailable(&self) -> Option<String> {
+        let provider = crate::websocket::Provider::for_symbol(&self.symbol);
+        if self.live_polling_fallback && provider == crate::websocket::Provider::Finnhub {
+            return None;
+        }
+        crate::websocket::yahoo_to_finnhub_symbol(&self.symbol).err()
+    }
+
+    /// Status for a live mode entered without connecting.
+    pub fn idle_live_status(&self) -> WebSocketStatus {
+        match self.live_stream_unavailable() {
+            Some(message) => WebSocketStatus::Error { message, recoverable: false },
+            None => WebSocketStatus::Idle,
         }
-        match result {
-            Ok(mut data) => {
-                // Prefer the market state from the live quote API (more reliable than chart meta)
-                crate::stock::log_debug(&format!("[apply_stock_data] {} chart market_state={:?}, landing_quotes has entry={}", symbol, data.market_state, self.landing_quotes.contains_key(symbol)));
-                if let Some(q) = self.landing_quotes.get(symbol) {
-                    crate::stock::log_debug(&format!("[apply_stock_data] {} overriding with quote market_state={:?}", symbol, q.market_state));
-                    data.market_state = q.market_state.clone();
-                }
-                self.stock_data = Some(data);
-                self.error_message = None;
-            }
-            Err(e) => {
-                let full_error = format!("Error fetching {}: {}", symbol, e);
-                self.add_error_to_log(full_error);
-                self.error_message = Some(format!(
-                    "Could not load data for \"{}\"\n\nCheck the symbol and try again\n\n's' to search  'b' to go back  'e' for error details",
-                    symbol
-                ));
-            }
+    }
+
+    /// "MARKET CLOSED — next open in 6h 12m" when the symbol's market is shut.
+    pub fn market_closed_banner(&self) -> Option<String> {
+        let hours = self.market_hours.as_ref()?;
+        let now = Utc::now().timestamp();
+        if hours.is_open_at(now) {
+            return None;
         }
+        Some(match hours.next_open_after(now) {
+            Some(open) => format!("MARKET CLOSED — next open in {}", format_countdown(open - now)),
+            None => "MARKET CLOSED".to_string(),
+        })
     }
 
-    pub fn set_price_alert(&mut self, symbol: String, target: f64, current_price: f64) {
-        self.alerts.retain(|a| a.symbol != symbol);
+    /// Add an alert for `symbol` at `target`, firing on the way up when
+    /// `above`. A symbol can have any number; an exact repeat is ignored.
+    pub fn set_price_alert(&mut self, symbol: String, target: f64, above: bool) {
+        if self.alerts.iter().any(|a| a.symbol == symbol && a.target == target && a.above == above) {
+            return;
+        }
+        let currency = self.current_price_for(&symbol).and_then(|(_, currency)| currency);
         self.alerts.push(crate::alerts::PriceAlert {
             symbol,
             target,
-            above: current_price < target,
+            above,
             triggered: false,
+            repeat: crate::config::repeat_alerts(),
+            fired_at: None,
+            currency,
         });
         crate::alerts::save(&self.alerts);
     }
 
-    pub fn clear_price_alert(&mut self, symbol: &str) {
-        self.alerts.retain(|a| a.symbol != symbol);
-        crate::alerts::save(&self.alerts);
+    /// The alert to show for `symbol`: one that has fired, else the first.
+    pub fn alert_for_symbol(&self, symbol: &str) -> Option<&crate::alerts::PriceAlert> {
+        self.alerts.iter()
+            .find(|a| a.symbol == symbol && a.triggered)
+            .or_else(|| self.alerts.iter().find(|a| a.symbol == symbol))
     }
 
-    pub fn alert_for_symbol(&self, symbol: &str) -> Option<&crate::alerts::PriceAlert> {
-        self.alerts.iter().find(|a| a.symbol == symbol)
+    pub fn alert_count(&self, symbol: &str) -> usize {
+        self.alerts.iter().filter(|a| a.symbol == symbol).count()
+    }
+
+    pub fn open_alert_input(&mut self, symbol: String) {
+        self.alert_target_symbol = symbol;
+        self.alert_input_buffer.clear();
+        self.show_alert_input = true;
+    }
+
+    /// Add the alert typed into the input popup, if it reads as one.
+    pub fn confirm_alert_input(&mut self) {
+        let symbol = self.alert_target_symbol.clone();
+        let current = self.current_price_for(&symbol).map(|(price, _)| price);
+        if let Some((target, above)) = crate::alerts::parse_condition(&self.alert_input_buffer, current) {
+            self.set_price_alert(symbol, target, above);
+        }
+        self.alert_input_buffer.clear();
+        self.show_alert_input = false;
+    }
+
+    pub fn toggle_alert_list(&mut self) {
+        self.show_alert_list = !self.show_alert_list;
+        if self.alerts.is_empty() {
+            self.alert_list_state.select(None);
+        } else if self.alert_list_state.selected().is_none_or(|i| i >= self.alerts.len()) {
+            self.alert_list_state.select(Some(0));
+        }
+    }
+
+    pub fn select_next_alert(&mut self) {
+        if self.alerts.is_empty() { return; }
+        let i = self.alert_list_state.selected().map_or(0, |i| (i + 1) % self.alerts.len());
+        self.alert_list_state.select(Some(i));
+    }
+
+    pub fn select_previous_alert(&mut self) {
+        if self.alerts.is_empty() { return; }
+        let i = match self.alert_list_state.selected() {
+            Some(0) | None => self.alerts.len() - 1,
+            Some(i) => i - 1,
+        };
+        self.alert_list_state.select(Some(i));
     }
 
-    /// Check quotes against all pending alerts. Returns (symbol, target) pairs that just triggered.
-    pub fn check_alerts(&mut self, quotes: &HashMap<String, crate::stock::QuoteSnapshot>) -> Vec<(String, f64)> {
-        let mut triggered = Vec::new();
-        let mut save_needed = false;
+    pub fn delete_selected_alert(&mut self) {
+        let Some(i) = self.alert_list_state.selected().filter(|&i| i < self.alerts.len()) else { return };
+        self.alerts.remove(i);
+        crate::alerts::save(&self.alerts);
+        self.alert_list_state.select(if self.alerts.is_empty() { None } else { Some(i.min(self.alerts.len() - 1)) });
+    }
+
+    /// Flip the selected alert between firing once and re-arming; a fired
+    /// one-shot alert made repeating is armed again.
+    pub fn toggle_selected_alert_repeat(&mut self) {
+        let Some(alert) = self.alert_list_state.selected().and_then(|i| self.alerts.get_mut(i)) else { return };
+        alert.repeat = !alert.repeat;
+        if alert.repeat {
+            alert.triggered = false;
+        }
+        crate::alerts::save(&self.alerts);
+    }
 
+    /// Check quotes against all pending alerts; ones that fire are queued
+    /// for `take_fired_alerts`.
+    pub fn check_alerts(&mut self, quotes: &HashMap<String, crate::stock::QuoteSnapshot>) {
+        let mut fired = Vec::new();
         for alert in self.alerts.iter_mut() {
-            if alert.triggered { continue; }
-            if let Some(quote) = quotes.get(&alert.symbol) {
-                let crossed = if alert.above {
-                    quote.price >= alert.target
-                } else {
-                    quote.price <= alert.target
-                };
-                if crossed {
-                    alert.triggered = true;
-                    triggered.push((alert.symbol.clone(), alert.target));
-                    save_needed = true;
-                }
+            if let Some(quote) = quotes.get(&alert.symbol)
+                && alert.check(quote.price, quote.price)
+            {
+                fired.push(format!("{} {} reached", alert.symbol, alert.condition()));
+            }
+        }
+        self.alerts_fired(fired);
+    }
+
+    /// Check the current symbol's alerts against the range of a live batch,
+    /// so a spike between redraws still counts. A replay is history and
+    /// leaves them alone.
+    fn check_live_alerts(&mut self, low: f64, high: f64) {
+        if matches!(self.ws_status, WebSocketStatus::Replay { .. }) {
+            return;
+        }
+        let mut fired = Vec::new();
+        for alert in self.alerts.iter_mut().filter(|a| a.symbol == self.symbol) {
+            if alert.check(low, high) {
+                fired.push(format!("{} {} reached", alert.symbol, alert.condition()));
             }
         }
+        if let Some(message) = fired.last() {
+            self.flash = Some((format!("⚡ {}", message), Instant::now()));
+        }
+        self.alerts_fired(fired);
+    }
 
-        if save_needed {
+    fn alerts_fired(&mut self, fired: Vec<String>) {
+        // Re-arming changes the stored state as well, but only a fire is
+        // worth the write
+        if !fired.is_empty() {
             crate::alerts::save(&self.alerts);
         }
-        triggered
+        let time = self.display_zone().format(&Utc::now(), "%H:%M:%S");
+        for message in &fired {
+            self.alert_log.push_back(format!("[{}] {}", time, message));
+            if self.alert_log.len() > ALERT_LOG_LEN {
+                self.alert_log.pop_front();
+            }
+        }
+        self.fired_alerts.extend(fired);
+    }
+
+    /// Alerts that fired since the last call, for main to ring and notify.
+    pub fn take_fired_alerts(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.fired_alerts)
     }
 
-    /// Best available price for a symbol (landing quotes → stock data fallback).
-    pub fn current_price_for(&self, symbol: &str) -> Option<f64> {
+    /// Best available price for a symbol (landing quotes → stock data fallback)
+    /// and the code of its currency, the native one that alerts are set in.
+    pub fn current_price_for(&self, symbol: &str) -> Option<(f64, Option<String>)> {
         if let Some(q) = self.landing_quotes.get(symbol) {
-            return Some(q.price);
+            return Some((q.price, q.currency.clone()));
         }
-        if let Some(ref data) = self.stock_data {
-            if data.symbol == symbol {
-                return Some(data.current_price);
-            }
+        if let Some(ref data) = self.stock_data
+            && data.symbol == symbol
+        {
+            // Undo a currency conversion; alerts are checked against native prices
+            let price = data.current_price / data.live_price_factor();
+            return Some((price, data.native_currency().map(String::from)));
         }
         None
     }
@@ -410,29 +2186,192 @@ impl App {
         }
     }
 
-    pub fn update_live_price(&mut self, price: f64, volume: Option<u64>) {
-        let now = Utc::now();
+    /// Apply an update from the live feed, which may be several trades
+    /// coalesced into one.
+    pub fn update_live_batch(&mut self, batch: &LivePrice) {
+        // A replay runs on the recording's clock so candles bucket as they did live
+        let now = match self.replay_speed {
+            Some(_) => {
+                let at = DateTime::from_timestamp(batch.timestamp, 0).unwrap_or_else(Utc::now);
+                self.replay_clock = Some(at);
+                at
+            }
+            None => Utc::now(),
+        };
+        self.update_live_batch_at(batch, now);
+    }
+
+    /// Volume-weighted average price of the session's trades.
+    pub fn session_vwap(&self) -> Option<f64> {
+        (self.session_quantity > 0.0).then(|| self.session_notional / self.session_quantity)
+    }
+
+    /// Shares per trade over the session.
+    pub fn average_trade_size(&self) -> Option<f64> {
+        (self.total_trade_count > 0 && self.session_quantity > 0.0)
+            .then(|| self.session_quantity / self.total_trade_count as f64)
+    }
+
+    /// The feed has stopped for good (retries exhausted, a fatal error or
+    /// the end of a replay) and only 'r' will bring it back, or is waiting
+    /// out a closed market and 'r' connects anyway.
+    pub fn can_reconnect(&self) -> bool {
+        matches!(
+            self.ws_status,
+            WebSocketStatus::Error { recoverable: false, .. } | WebSocketStatus::Disconnected | WebSocketStatus::MarketClosed { .. }
+        )
+    }
+
+    /// Add `trades` to the count for `second`, zeroing the slots of any
+    /// seconds skipped since the last trade.
+    fn count_activity(&mut self, second: i64, trades: u32) {
+        let slot = |s: i64| s.rem_euclid(ACTIVITY_SECS as i64) as usize;
+        match self.activity_second {
+            Some(last) if second <= last => {
+                // A late batch still counts, if its second is in the window
+                if last - second < ACTIVITY_SECS as i64 {
+                    self.trade_activity[slot(second)] += trades;
+                }
+                return;
+            }
+            Some(last) => {
+                for s in (last + 1..second).take(ACTIVITY_SECS) {
+                    self.trade_activity[slot(s)] = 0;
+                }
+            }
+            None => {}
+        }
+        self.trade_activity[slot(second)] = trades;
+        self.activity_second = Some(second);
+    }
+
+    /// Trades in each of the `ACTIVITY_SECS` seconds up to `now`, oldest
+    /// first; seconds after the last trade count as quiet.
+    pub fn trade_activity(&self, now: DateTime<Utc>) -> Vec<u32> {
+        let now = now.timestamp();
+        (now - ACTIVITY_SECS as i64 + 1..=now)
+            .map(|s| match self.activity_second {
+                Some(last) if s <= last && last - s < ACTIVITY_SECS as i64 => {
+                    self.trade_activity[s.rem_euclid(ACTIVITY_SECS as i64) as usize]
+                }
+                _ => 0,
+            })
+            .collect()
+    }
+
+    /// Now, as far as the live views are concerned: the wall clock, or the
+    /// time of the last record while a recording plays back.
+    pub fn live_clock(&self) -> DateTime<Utc> {
+        match self.replay_speed {
+            Some(_) => self.replay_clock.unwrap_or_else(Utc::now),
+            None => Utc::now(),
+        }
+    }
+
+    /// A single trade at an explicit time, used when replaying a session.
+    pub fn update_live_price_at(&mut self, price: f64, volume: Option<u64>, now: DateTime<Utc>) {
+        let trade = LivePrice::trade(self.symbol.clone(), price, now.timestamp(), volume);
+        self.update_live_batch_at(&trade, now);
+    }
+
+    /// When `batch` traded: the exchange's own timestamp when it's within
+    /// `MAX_FEED_SKEW_SECS` of `received`, else `received`. Never before the
+    /// batch ahead of it, so a late print can't reopen a finished candle.
+    fn trade_time(&self, batch: &LivePrice, received: DateTime<Utc>) -> DateTime<Utc> {
+        let at = DateTime::from_timestamp(batch.timestamp, 0)
+            .filter(|t| (received - *t).num_seconds().abs() <= MAX_FEED_SKEW_SECS)
+            .unwrap_or(received);
+        self.live_batches.back().map_or(at, |b| at.max(b.timestamp))
+    }
 
+    /// Apply `batch`, received at `now`. Candles, the tape and the rebucket
+    /// history go by when it traded; staleness and trade rates by arrival.
+    pub(crate) fn update_live_batch_at(&mut self, batch: &LivePrice, now: DateTime<Utc>) {
+        let traded_at = self.trade_time(batch, now);
+        let factor = self.stock_data.as_ref().map_or(1.0, |d| d.live_price_factor());
+        let (open, price, low, high) = (batch.open * factor, batch.price * factor, batch.low * factor, batch.high * factor);
+        let (volume, quantity, trades) = (batch.volume, batch.size(), batch.trades);
+        let new_extreme = matches!(self.timeframe, crate::stock::TimeFrame::OneDay)
+            && self.stock_data.as_ref()
+                .and_then(|d| d.session_extremes())
+                .is_some_and(|((_, session_high), (_, session_low))| high > session_high || low < session_low);
+        if new_extreme {
+            self.extreme_flash = Some(Instant::now());
+        }
+        // The session's own range, which starts with the first live trade
+        if self.session_high.is_some_and(|h| high > h) {
+            self.session_extreme_flash = Some((Instant::now(), true));
+        } else if self.session_low.is_some_and(|l| low < l) {
+            self.session_extreme_flash = Some((Instant::now(), false));
+        }
+        self.session_high = Some(self.session_high.map_or(high, |h| h.max(high)));
+        self.session_low = Some(self.session_low.map_or(low, |l| l.min(low)));
+        // Alerts are set in the quote's currency
+        self.check_live_alerts(batch.low, batch.high);
+        let notional = if batch.notional > 0.0 { batch.notional } else { batch.price * quantity };
+        self.session_notional += notional * factor;
+        // Tick rule: an uptick is buyer-initiated, a downtick seller-initiated,
+        // and an unchanged price keeps the side of the last move
+        self.last_tick_side = match self.last_live_price {
+            Some(prev) if price > prev => 1,
+            Some(prev) if price < prev => -1,
+            _ => self.last_tick_side,
+        };
+        let signed_volume = match volume {
+            Some(v) if v > 0 => self.last_tick_side * v as i64,
+            _ => 0,
+        };
+        self.cumulative_delta += signed_volume;
+        self.live_session_start.get_or_insert(now);
+        self.recent_trade_times.extend(std::iter::repeat_n(now, trades as usize));
+        self.count_activity(now.timestamp(), trades);
+        let cutoff = now - chrono::Duration::seconds(TRADE_RATE_WINDOW_SECS);
+        while self.recent_trade_times.front().is_some_and(|&t| t < cutoff) {
+            self.recent_trade_times.pop_front();
+        }
         self.last_live_price = Some(price);
         self.ws_last_update = Some(now);
-        self.total_trade_count += 1;
-        if let Some(v) = volume {
-            self.total_live_volume += v;
-        }
+        self.total_trade_count += trades;
+        self.coalesced_trades += trades.saturating_sub(1);
+        self.session_quantity += quantity;
+        self.total_live_volume = self.session_quantity.round() as u64;
 
         // Add to trade history for ticker view
         let trade = Trade {
             price,
-            timestamp: now,
+            timestamp: traded_at,
             volume,
         };
-        self.live_trades.push_front(trade);
-        if self.live_trades.len() > 100 {
-            self.live_trades.pop_back();
+        let trade_buffer = self.trade_buffer;
+        let tape = match self.live_pause {
+            Some(ref mut pause) => {
+                pause.trade_count += trades;
+                &mut pause.trades
+            }
+            None => &mut self.live_trades,
+        };
+        tape.push_front(trade);
+        tape.truncate(trade_buffer);
+
+        self.live_batches.push_back(LiveBatch {
+            timestamp: traded_at,
+            prices: [open, high, low, price],
+            quantity,
+            trades,
+            signed_volume,
+        });
+        let cutoff = traded_at - chrono::Duration::seconds(REBUCKET_WINDOW_SECS);
+        while self.live_batches.front().is_some_and(|b| b.timestamp < cutoff) {
+            self.live_batches.pop_front();
         }
 
         // Aggregate into candlesticks
-        self.aggregate_into_candle(price, volume.unwrap_or(0), now);
+        let started = self.on_feed_candles(|app| app.aggregate_into_candle([open, high, low, price], quantity, trades, traded_at));
+        if started {
+            self.candle_delta = signed_volume;
+        } else {
+            self.candle_delta += signed_volume;
+        }
 
         // Update stock data for header display
         if let Some(ref mut data) = self.stock_data {
@@ -441,10 +2380,10 @@ impl App {
 
             data.live_ticks.push_back(crate::stock::LiveTick {
                 price,
-                timestamp: now,
+                timestamp: traded_at,
             });
 
-            if data.live_ticks.len() > 100 {
+            while data.live_ticks.len() > self.tick_buffer {
                 data.live_ticks.pop_front();
             }
 
@@ -453,61 +2392,240 @@ impl App {
         }
     }
 
-    fn aggregate_into_candle(&mut self, price: f64, volume: u64, timestamp: DateTime<Utc>) {
+    /// Returns whether the trades started a new candle.
+    fn aggregate_into_candle(&mut self, [open, high, low, price]: [f64; 4], quantity: f64, trades: u32, timestamp: DateTime<Utc>) -> bool {
         let interval_secs = self.candle_interval.to_secs() as i64;
         let candle_start = timestamp.timestamp() / interval_secs * interval_secs;
 
-        match &mut self.current_candle {
-            Some(candle) => {
-                let current_start = candle.timestamp.timestamp() / interval_secs * interval_secs;
-
-                if candle_start == current_start {
-                    // Same candle - update OHLC
-                    candle.high = candle.high.max(price);
-                    candle.low = candle.low.min(price);
-                    candle.close = price;
-                    candle.volume += volume;
-                    candle.trade_count += 1;
-                } else {
-                    // New candle - finalize current and start new
-                    let finished_candle = candle.clone();
-                    self.live_candles.push_back(finished_candle);
-                    if self.live_candles.len() > 60 {
-                        self.live_candles.pop_front();
-                    }
+        if let Some(candle) = self.current_candle.as_mut()
+            && candle.timestamp.timestamp() / interval_secs * interval_secs == candle_start
+        {
+            // Same candle - update OHLC
+            candle.high = candle.high.max(high);
+            candle.low = candle.low.min(low);
+            candle.close = price;
+            let total = candle.volume as f64 + self.candle_volume_carry + quantity;
+            candle.volume = total.round() as u64;
+            self.candle_volume_carry = total - candle.volume as f64;
+            candle.trade_count += trades;
+            return false;
+        }
 
-                    *candle = Candlestick {
-                        open: price,
-                        high: price,
-                        low: price,
-                        close: price,
-                        volume,
-                        timestamp,
-                        trade_count: 1,
-                    };
-                }
+        // New candle - finalize current and start new
+        if let Some(finished) = self.current_candle.take() {
+            self.push_closed_candle(finished);
+        }
+        let volume = quantity.round() as u64;
+        self.candle_volume_carry = quantity - volume as f64;
+        self.current_candle = Some(Candlestick {
+            open,
+            high,
+            low,
+            close: price,
+            volume,
+            timestamp,
+            trade_count: trades,
+        });
+        true
+    }
+
+    fn push_closed_candle(&mut self, candle: Candlestick) {
+        self.live_candles.push_back(candle);
+        if self.live_candles.len() > self.candle_buffer {
+            self.live_candles.pop_front();
+            // The crosshair stays on its candle as the buffer shifts
+            self.live_cursor = self.live_cursor.map(|i| i.saturating_sub(1));
+        }
+        // A scrolled-back view stays on the candles it was showing
+        if self.live_scroll > 0 {
+            self.live_scroll = (self.live_scroll + 1).min(self.live_candles.len());
+        }
+    }
+
+    /// Switch the live candles to `interval`, rebuilding the ones the kept
+    /// batches cover, and the forming one, at the new bucket size. Older
+    /// candles come back with the history fetch for the new interval.
+    pub fn set_candle_interval(&mut self, interval: CandleInterval) {
+        self.candle_interval = interval;
+        self.candles_pending = true;
+        self.live_candles.clear();
+        self.current_candle = None;
+        self.candle_delta = 0;
+        let batches = std::mem::take(&mut self.live_batches);
+        for batch in &batches {
+            if self.aggregate_into_candle(batch.prices, batch.quantity, batch.trades, batch.timestamp) {
+                self.candle_delta = 0;
+            }
+            self.candle_delta += batch.signed_volume;
+        }
+        self.live_batches = batches;
+        self.live_scroll = 0;
+        self.live_cursor = None;
+        // A paused display shows the rebuilt candles too
+        if let Some(ref mut pause) = self.live_pause {
+            pause.candles = self.live_candles.clone();
+            pause.current = self.current_candle.clone();
+        }
+    }
+
+    /// Seconds until the forming candle's bucket closes, or `None` without one.
+    pub fn candle_secs_left(&self, now: DateTime<Utc>) -> Option<i64> {
+        let interval_secs = self.candle_interval.to_secs() as i64;
+        let candle = self.current_candle.as_ref()?;
+        let end = candle.timestamp.timestamp() / interval_secs * interval_secs + interval_secs;
+        Some((end - now.timestamp()).max(0))
+    }
+
+    /// Close the forming candle once its bucket has ended, so a quiet symbol
+    /// doesn't sit on a finished candle until the next trade arrives. Returns
+    /// whether a candle was closed.
+    pub fn finalize_idle_candle(&mut self, now: DateTime<Utc>) -> bool {
+        let closed = self.on_feed_candles(|app| {
+            if app.candle_secs_left(now) != Some(0) {
+                return false;
+            }
+            if let Some(finished) = app.current_candle.take() {
+                app.push_closed_candle(finished);
             }
+            true
+        });
+        if closed {
+            self.candle_delta = 0;
+        }
+        closed
+    }
+
+    /// Run `update` on the candles the feed aggregates into: the visible
+    /// ones, or while paused the background copy, leaving the frozen view
+    /// and its crosshair and scroll position alone.
+    fn on_feed_candles<R>(&mut self, update: impl FnOnce(&mut Self) -> R) -> R {
+        let Some(mut pause) = self.live_pause.take() else { return update(self); };
+        let (cursor, scroll) = (self.live_cursor, self.live_scroll);
+        std::mem::swap(&mut self.live_candles, &mut pause.candles);
+        std::mem::swap(&mut self.current_candle, &mut pause.current);
+        let result = update(self);
+        std::mem::swap(&mut self.live_candles, &mut pause.candles);
+        std::mem::swap(&mut self.current_candle, &mut pause.current);
+        (self.live_cursor, self.live_scroll) = (cursor, scroll);
+        self.live_pause = Some(pause);
+        result
+    }
+
+    /// Freeze the live tape and candles on screen, or bring them up to date
+    /// with everything that arrived meanwhile. The feed keeps running either way.
+    pub fn toggle_live_pause(&mut self) {
+        match self.live_pause.take() {
             None => {
-                // Start first candle
-                self.current_candle = Some(Candlestick {
-                    open: price,
-                    high: price,
-                    low: price,
-                    close: price,
-                    volume,
-                    timestamp,
-                    trade_count: 1,
+                self.live_pause = Some(LivePause {
+                    candles: self.live_candles.clone(),
+                    current: self.current_candle.clone(),
+                    trades: VecDeque::new(),
+                    trade_count: 0,
                 });
             }
+            Some(pause) => {
+                self.live_candles = pause.candles;
+                self.current_candle = pause.current;
+                let mut trades = pause.trades;
+                trades.extend(self.live_trades.drain(..));
+                trades.truncate(self.trade_buffer);
+                self.live_trades = trades;
+                // Candles may have rolled off the front meanwhile
+                let last = self.live_candles.len().checked_sub(1);
+                self.live_cursor = self.live_cursor.and_then(|i| last.map(|last| i.min(last)));
+                self.live_scroll = self.live_scroll.min(self.live_candles.len());
+            }
+        }
+    }
+
+    /// Trades per minute over the last `TRADE_RATE_WINDOW_SECS`, and whether
+    /// that is a spike against the session average. Until the window has
+    /// filled the rate is extrapolated from the time elapsed so far and never
+    /// flagged, since there's no average yet to compare with.
+    pub fn trade_rate(&self, now: DateTime<Utc>) -> Option<(f64, bool)> {
+        let start = self.live_session_start?;
+        let elapsed = (now - start).num_milliseconds() as f64 / 1000.0;
+        let window = TRADE_RATE_WINDOW_SECS as f64;
+        // The deque is pruned on each trade, so count from the back for a
+        // window that ends now rather than at the last trade
+        let cutoff = now - chrono::Duration::seconds(TRADE_RATE_WINDOW_SECS);
+        let in_window = self.recent_trade_times.iter().rev().take_while(|&&t| t >= cutoff).count() as f64;
+        if elapsed < window {
+            return Some((in_window * 60.0 / elapsed.max(1.0), false));
+        }
+        let per_minute = in_window * 60.0 / window;
+        let average = self.total_trade_count as f64 * 60.0 / elapsed;
+        Some((per_minute, per_minute > TRADE_RATE_SPIKE * average))
+    }
+
+    /// Scroll the live candle chart `delta` candles back (negative towards
+    /// the live edge), within the buffer.
+    pub fn scroll_live_candles(&mut self, delta: isize) {
+        let count = self.live_candles.len() + usize::from(self.current_candle.is_some());
+        self.live_scroll = self.live_scroll.saturating_add_signed(delta).min(count.saturating_sub(1));
+    }
+
+    /// Show the live crosshair on the newest candle in view, or hide it.
+    pub fn toggle_live_cursor(&mut self) {
+        let count = self.live_candles.len() + usize::from(self.current_candle.is_some());
+        self.live_cursor = match self.live_cursor {
+            Some(_) => None,
+            None => count.checked_sub(1 + self.live_scroll),
+        };
+    }
+
+    /// Step the live crosshair `delta` candles, scrolling the view when it
+    /// leaves a chart `width` cells wide.
+    pub fn move_live_cursor(&mut self, delta: isize, width: u16) {
+        let count = self.live_candles.len() + usize::from(self.current_candle.is_some());
+        let Some(last) = count.checked_sub(1) else { return };
+        let i = match self.live_cursor {
+            Some(i) => i.saturating_add_signed(delta).min(last),
+            None => last - self.live_scroll.min(last),
+        };
+        let (range, _) = chart::candle_layout(count, 0, width.saturating_sub(2) as usize, self.live_scroll);
+        if i >= range.end {
+            self.live_scroll = last - i;
+        } else if i < range.start {
+            self.live_scroll += range.start - i;
         }
+        self.live_cursor = Some(i);
+    }
+
+    /// Jump the live crosshair to the oldest or newest candle in view.
+    pub fn jump_live_cursor(&mut self, to_end: bool, width: u16) {
+        let count = self.live_candles.len() + usize::from(self.current_candle.is_some());
+        let (range, _) = chart::candle_layout(count, 0, width.saturating_sub(2) as usize, self.live_scroll);
+        let Some(last) = range.end.checked_sub(1) else { return };
+        self.live_cursor = Some(if to_end { last } else { range.start });
     }
 
     pub fn clear_live_data(&mut self) {
         self.live_trades.clear();
         self.live_candles.clear();
+        self.live_scroll = 0;
+        self.live_cursor = None;
         self.current_candle = None;
         self.total_live_volume = 0;
         self.total_trade_count = 0;
+        self.coalesced_trades = 0;
+        self.live_pause = None;
+        self.cumulative_delta = 0;
+        self.candle_delta = 0;
+        self.session_high = None;
+        self.session_low = None;
+        self.session_notional = 0.0;
+        self.session_quantity = 0.0;
+        self.candle_volume_carry = 0.0;
+        self.session_extreme_flash = None;
+        self.bid_ask = None;
+        self.last_tick_side = 0;
+        self.recent_trade_times.clear();
+        self.trade_activity = [0; ACTIVITY_SECS];
+        self.activity_second = None;
+        self.live_batches.clear();
+        self.candles_pending = false;
+        self.live_session_start = None;
         self.last_live_price = None;
         if let Some(ref mut data) = self.stock_data {
             data.live_ticks.clear();
@@ -584,10 +2702,10 @@ impl App {
     }
 
     pub fn select_watchlist(&mut self) {
-        if let Some(i) = self.watchlist_state.selected() {
-            if let Some(symbol) = self.watchlist.get(i) {
-                self.symbol = symbol.clone();
-            }
+        if let Some(i) = self.watchlist_state.selected()
+            && let Some(symbol) = self.watchlist.get(i)
+        {
+            self.symbol = symbol.clone();
         }
     }
 
@@ -697,11 +2815,31 @@ impl App {
         }
     }
 
-    pub fn apply_historical_candles(&mut self, candles: Vec<Candlestick>) {
-        self.live_candles.clear();
-        for candle in candles {
-            self.live_candles.push_back(candle);
+    /// Put fetched candles in front of the ones built from live trades. Where
+    /// both cover a bucket the live one wins, as it's the more current.
+    pub fn apply_historical_candles(&mut self, interval: CandleInterval, candles: Vec<Candlestick>) {
+        if interval != self.candle_interval {
+            return; // fetched for an interval since left
         }
+        self.candles_pending = false;
+        let secs = interval.to_secs() as i64;
+        let first_live = self.live_candles.front()
+            .or(self.current_candle.as_ref())
+            .map(|c| c.timestamp.timestamp() / secs * secs);
+        let mut merged: VecDeque<Candlestick> = candles.into_iter()
+            .filter(|c| first_live.is_none_or(|bucket| c.timestamp.timestamp() < bucket))
+            .collect();
+        let added = merged.len();
+        merged.extend(self.live_candles.drain(..));
+        let excess = merged.len().saturating_sub(self.candle_buffer);
+        merged.drain(..excess);
+        self.live_candles = merged;
+        self.live_cursor = self.live_cursor.map(|i| (i + added).saturating_sub(excess));
+    }
+
+    pub fn apply_candles_error(&mut self, error: String) {
+        self.candles_pending = false;
+        self.add_error_to_log(format!("Error fetching candles: {}", error));
     }
 
     pub fn convert_to_candlesticks(&self) -> Vec<Candlestick> {
@@ -711,8 +2849,10 @@ impl App {
             let mut candles = Vec::new();
             let mut current_bucket: Vec<(DateTime<Utc>, f64)> = Vec::new();
             let mut current_bucket_start = 0i64;
+            let mut bucket_volume = 0.0;
 
-            for (ts, price) in data.timestamps.iter().zip(data.prices.iter()) {
+            for (i, (ts, price)) in data.timestamps.iter().zip(data.prices.iter()).enumerate() {
+                let volume = data.volumes.get(i).copied().unwrap_or(0.0);
                 let bucket_start = ts.timestamp() / interval_secs * interval_secs;
 
                 if current_bucket.is_empty() {
@@ -721,6 +2861,7 @@ impl App {
 
                 if bucket_start == current_bucket_start {
                     current_bucket.push((*ts, *price));
+                    bucket_volume += volume;
                 } else {
                     // Finalize current bucket
                     if !current_bucket.is_empty() {
@@ -734,7 +2875,7 @@ impl App {
                             high,
                             low,
                             close,
-                            volume: 0, // Not available from price data
+                            volume: bucket_volume as u64,
                             timestamp: current_bucket.first().unwrap().0,
                             trade_count: current_bucket.len() as u32,
                         });
@@ -744,6 +2885,7 @@ impl App {
                     current_bucket.clear();
                     current_bucket.push((*ts, *price));
                     current_bucket_start = bucket_start;
+                    bucket_volume = volume;
                 }
             }
 
@@ -759,7 +2901,7 @@ impl App {
                     high,
                     low,
                     close,
-                    volume: 0,
+                    volume: bucket_volume as u64,
                     timestamp: current_bucket.first().unwrap().0,
                     trade_count: current_bucket.len() as u32,
                 });
@@ -772,36 +2914,84 @@ impl App {
     }
 }
 
-pub fn render_help(f: &mut Frame, _app: &App){
+/// Compact "2d 3h" / "6h 12m" / "4m" duration for countdown labels.
+pub fn format_countdown(secs: i64) -> String {
+    let secs = secs.max(0);
+    let (d, h, m) = (secs / 86_400, secs % 86_400 / 3600, secs % 3600 / 60);
+    if d > 0 {
+        format!("{}d {}h", d, h)
+    } else if h > 0 {
+        format!("{}h {}m", h, m)
+    } else {
+        format!("{}m", m.max(1))
+    }
+}
+
+pub fn render_help(f: &mut Frame, app: &App){
     let area = f.area();
 
-    let popup_width = area.width.min(60);
-    let popup_height = area.height.min(15);
+    // Wide enough for the longest row: the key column plus its description
+    let popup_width = area.width.min(88);
     let popup_x = (area.width.saturating_sub(popup_width)) / 2;
-    let popup_y = (area.height.saturating_sub(popup_height)) / 2;
-
-    let popup_area = ratatui::layout::Rect {
-        x: popup_x,
-        y: popup_y,
-        width: popup_width,
-        height: popup_height,
-    };
 
+    let periods: Vec<String> = app.ema_periods.iter().map(|p| p.to_string()).collect();
+    let ema_help = format!("Toggle EMA {} (chart, live candles)", periods.join("/"));
     let help_items = vec![
         ("↑/↓", "Navigate list"),
         ("Enter", "Select stock"),
         ("s", "Search for stock"),
         ("←/→", "Change timeframe / candle interval"),
         ("l", "Enter live mode"),
-        ("a", "Set / clear price alert (any view)"),
+        ("a", "Add a price alert (any view)"),
+        ("A", "List / delete price alerts"),
         ("w", "Add to watchlist"),
         ("b", "Back to chart / landing"),
         ("e", "Show error log"),
+        ("E", "Earnings history (chart)"),
+        ("p", "Company profile (chart)"),
+        ("i", "Indicators popup (chart)"),
+        ("t", "Cycle chart interval (chart) / tape's minimum trade size (live ticker)"),
+        ("/", "Crosshair; Shift+←/→ move it, Home/End jump (chart, live candles)"),
+        ("+ / -", "Zoom the line chart in / out (chart)"),
+        ("[ / ]", "Pan the zoomed chart earlier / later (chart)"),
+        ("g", "Toggle log / linear price scale (chart)"),
+        ("d", "Appearance popup: candlesticks, area fill, baseline, log scale (chart)"),
+        ("n", "Statistics popup (chart, live)"),
+        ("c", "Compare with another symbol / clear (chart)"),
+        ("k", "Recent candle patterns (chart, live candles)"),
+        ("f", "Full-screen chart (chart) / previous session's pivot levels (live)"),
+        ("Enter", "Details of the candle under the crosshair"),
+        ("PgUp/PgDn", "Scroll back through live candles; any key returns (live candles)"),
+        ("m", ema_help.as_str()),
+        ("v", "Toggle volume pane (chart, live candles)"),
+        ("u", "Volume and trade count under each candle (live candles)"),
+        ("Space", "Pause / resume the live display; trades keep arriving (live)"),
+        ("r", "Reconnect once the feed has given up, keeping its data (live)"),
+        ("R", "Toggle RSI pane (chart) / record the feed to a file (live)"),
+        ("M", "Toggle MACD pane (chart)"),
+        ("O", "Toggle stochastic pane (chart, live candles)"),
+        ("x", "Export chart CSV / live session JSON"),
+        ("y", "Save the screen as a text snapshot"),
+        ("P", "Save the chart as a PNG image (chart)"),
+        ("T", "Recent trades beside the streaming chart"),
+        ("z", "Show times in local / UTC / exchange time"),
+        ("$", "Toggle native / display currency (chart)"),
         ("h", "Toggle this help screen"),
         ("Esc", "Cancel/close popup"),
         ("q", "Quit application"),
     ];
 
+    // One row per entry plus the border
+    let popup_height = area.height.min(help_items.len() as u16 + 2);
+    let popup_y = (area.height.saturating_sub(popup_height)) / 2;
+
+    let popup_area = ratatui::layout::Rect {
+        x: popup_x,
+        y: popup_y,
+        width: popup_width,
+        height: popup_height,
+    };
+
     let list_items: Vec<ListItem> = help_items
         .iter()
         .map(|(key, desc)| {
@@ -833,7 +3023,33 @@ pub fn render_help(f: &mut Frame, _app: &App){
 
 }
 
+/// The screen `ui` draws at `width` x `height` as plain text: colours and
+/// styles dropped, trailing blanks trimmed from each row.
+pub(crate) fn render_to_text(app: &App, width: u16, height: u16) -> Result<String, Box<dyn std::error::Error>> {
+    let mut terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(width, height))?;
+    terminal.draw(|f| ui(f, app))?;
+    let buf = terminal.backend().buffer();
+    let mut out = String::new();
+    for y in 0..height {
+        let row: String = (0..width).map(|x| buf[(x, y)].symbol()).collect();
+        out.push_str(row.trim_end());
+        out.push('\n');
+    }
+    Ok(out)
+}
+
 pub fn ui(f: &mut Frame, app: &App) {
+    let area = f.area();
+    if area.width < MIN_WIDTH || area.height < MIN_HEIGHT {
+        let notice = Paragraph::new(vec![
+            Line::from(Span::styled("Terminal too small", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))),
+            Line::from(format!("{}×{}, need at least {}×{}", area.width, area.height, MIN_WIDTH, MIN_HEIGHT)),
+        ])
+        .alignment(Alignment::Center);
+        f.render_widget(notice, Rect { y: area.y + area.height.saturating_sub(2) / 2, height: area.height.min(2), ..area });
+        return;
+    }
+
     match app.state {
         AppState::Landing => render_landing(f, app),
         AppState::Chart => render_chart_view(f, app),
@@ -844,15 +3060,449 @@ pub fn ui(f: &mut Frame, app: &App) {
 
     // Render popups on top
     if app.show_live_mode_select {
-        render_live_mode_select(f);
+        render_live_mode_select(f, app);
     }
     if app.show_error_log {
         render_error_log(f, app);
     }
+    if app.show_earnings {
+        render_earnings_popup(f, app);
+    }
+    if app.show_profile {
+        render_profile_popup(f, app);
+    }
+    if app.show_indicators {
+        render_indicators_popup(f, app);
+    }
+    if app.show_params {
+        render_params_popup(f, app);
+    }
+    if app.show_appearance {
+        render_appearance_popup(f, app);
+    }
+    if app.show_pattern_list {
+        render_patterns_popup(f, app);
+    }
+    if app.show_pivots {
+        render_pivots_popup(f, app);
+    }
+    if app.show_candle_detail {
+        render_candle_detail_popup(f, app);
+    }
+    if app.show_stats {
+        match app.state {
+            AppState::Chart => render_stats_popup(f, app),
+            AppState::LiveTicker | AppState::LiveCandles => render_session_stats_popup(f, app),
+            _ => {}
+        }
+    }
     if app.show_help {
         render_help(f, app);
     }
+    if app.show_alert_list {
+        render_alert_list(f, app);
+    }
     if app.show_alert_input {
         render_alert_input(f, app);
     }
+    if app.show_compare_input {
+        render_compare_input(f, app);
+    }
+    if app.display_mode == crate::config::DisplayMode::Monochrome {
+        strip_colors(f);
+    }
+}
+
+/// Drop every colour from the frame. Cells that were picked out by a
+/// background (selections, the crosshair) are shown reversed instead.
+fn strip_colors(f: &mut Frame) {
+    let area = f.area();
+    let buf = f.buffer_mut();
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            let cell = &mut buf[(x, y)];
+            let highlighted = cell.bg != Color::Reset;
+            cell.set_fg(Color::Reset).set_bg(Color::Reset);
+            if highlighted {
+                cell.modifier.insert(Modifier::REVERSED);
+            }
+        }
+    }
+}
+
+/// A price with its currency prefix: thousands separators from 10,000 up,
+/// and four significant digits (up to 6 decimals) below 1, so neither
+/// BRK-A nor a sub-dollar coin loses its readability.
+pub(super) fn format_price(value: f64, cur: &str) -> String {
+    let sign = if value < 0.0 { "-" } else { "" };
+    let v = value.abs();
+    let digits = if v >= 10_000.0 {
+        let text = format!("{:.2}", v);
+        let (whole, frac) = text.split_once('.').unwrap_or((&text, "00"));
+        let mut grouped = String::new();
+        for (i, ch) in whole.chars().enumerate() {
+            if i > 0 && (whole.len() - i) % 3 == 0 {
+                grouped.push(',');
+            }
+            grouped.push(ch);
+        }
+        format!("{}.{}", grouped, frac)
+    } else if v > 0.0 && v < 1.0 {
+        let decimals = (3 - v.log10().floor() as i32).clamp(4, 6) as usize;
+        format!("{:.*}", decimals, v)
+    } else {
+        format!("{:.2}", v)
+    };
+    format!("{}{}{}", sign, cur, digits)
+}
+
+/// "+" or "−" for a change, so direction reads without colour.
+pub(super) fn sign_prefix(value: f64) -> &'static str {
+    if value >= 0.0 { "+" } else { "−" }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An app charting `AAPL` with live updates on.
+    fn live_app() -> App {
+        let mut app = App::new();
+        app.symbol = "AAPL".to_string();
+        app.live_updates_enabled = true;
+        app
+    }
+
+    #[test]
+    fn live_volume_reaches_the_totals_and_candle() {
+        let mut app = live_app();
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let now = Utc::now().timestamp();
+        tx.send(LivePrice::trade("AAPL".to_string(), 190.0, now, Some(250))).unwrap();
+        // Another symbol's trade has no home on this chart
+        tx.send(LivePrice::trade("MSFT".to_string(), 410.0, now, Some(99))).unwrap();
+
+        assert!(app.drain_live_prices(&mut rx));
+        assert_eq!(app.total_live_volume, 250);
+        assert_eq!(app.current_candle.as_ref().map(|c| c.volume), Some(250));
+        assert_eq!(app.live_trades.front().and_then(|t| t.volume), Some(250));
+    }
+
+    #[test]
+    fn drained_backlog_keeps_every_trade() {
+        let mut app = live_app();
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let now = Utc::now().timestamp();
+        let (mut trades, mut volume, mut coalesced) = (0, 0, 0);
+        for i in 0..1_000u32 {
+            let mut batch = LivePrice::trade("AAPL".to_string(), 100.0 + (i % 7) as f64, now, Some(u64::from(i) + 1));
+            // Most messages stand for several trades coalesced into one
+            batch.trades = 1 + i % 3;
+            trades += batch.trades;
+            coalesced += batch.trades - 1;
+            volume += u64::from(i) + 1;
+            tx.send(batch).unwrap();
+        }
+
+        assert!(app.drain_live_prices(&mut rx));
+        assert!(rx.try_recv().is_err());
+        assert_eq!(app.total_trade_count, trades);
+        assert_eq!(app.coalesced_trades, coalesced);
+        assert_eq!(app.total_live_volume, volume);
+        let candles = || app.live_candles.iter().chain(app.current_candle.as_ref());
+        assert_eq!(candles().map(|c| c.volume).sum::<u64>(), volume);
+        assert_eq!(candles().map(|c| c.trade_count).sum::<u32>(), trades);
+    }
+
+    #[test]
+    fn trades_are_timed_by_the_exchange() {
+        let mut app = live_app();
+        app.candle_interval = CandleInterval::OneMinute;
+        let minute = 1_700_000_000 / 60 * 60;
+        let at = |secs: i64| DateTime::from_timestamp(minute + secs, 0).unwrap();
+        // Traded in the last second of one minute, arrived in the next
+        app.update_live_batch_at(&LivePrice::trade("AAPL".to_string(), 100.0, minute + 59, Some(10)), at(62));
+
+        assert_eq!(app.live_trades[0].timestamp, at(59));
+        assert_eq!(app.live_batches.back().map(|b| b.timestamp), Some(at(59)));
+        assert_eq!(app.current_candle.as_ref().map(|c| c.timestamp), Some(at(59)));
+        assert_eq!(app.ws_last_update, Some(at(62)));
+
+        // A print stamped before the last one is held at the last one's time
+        app.update_live_batch_at(&LivePrice::trade("AAPL".to_string(), 101.0, minute + 30, Some(5)), at(63));
+        assert_eq!(app.live_trades[0].timestamp, at(59));
+        assert!(app.live_candles.is_empty());
+        assert_eq!(app.current_candle.as_ref().map(|c| (c.close, c.volume)), Some((101.0, 15)));
+
+        // A clock this far out is ignored for the arrival time
+        app.update_live_batch_at(&LivePrice::trade("AAPL".to_string(), 102.0, minute - 3_600, Some(1)), at(70));
+        assert_eq!(app.live_trades[0].timestamp, at(70));
+        assert_eq!(app.live_candles.len(), 1);
+        assert_eq!(app.current_candle.as_ref().map(|c| c.timestamp), Some(at(70)));
+    }
+
+    #[test]
+    fn switching_interval_rebuckets_the_kept_batches() {
+        let mut app = live_app();
+        app.candle_interval = CandleInterval::OneMinute;
+        let start = 1_700_000_000 / 300 * 300;
+        let prices = [100.0, 101.0, 100.5, 102.0, 103.0, 102.5, 102.5, 104.0, 103.0, 101.0, 101.5, 100.0];
+        for (i, &price) in prices.iter().enumerate() {
+            let ts = start + i as i64 * 60 + 5;
+            let volume = 10 * (i as u64 + 1);
+            app.update_live_batch_at(&LivePrice::trade("AAPL".to_string(), price, ts, Some(volume)), DateTime::from_timestamp(ts, 0).unwrap());
+        }
+        assert_eq!(app.live_candles.len(), 11);
+        let cumulative = app.cumulative_delta;
+
+        app.set_candle_interval(CandleInterval::FiveMinutes);
+        let ohlcv = |c: &Candlestick| (c.open, c.high, c.low, c.close, c.volume, c.trade_count);
+        let closed: Vec<_> = app.live_candles.iter().map(ohlcv).collect();
+        assert_eq!(closed, [
+            (100.0, 103.0, 100.0, 103.0, 150, 5),
+            (102.5, 104.0, 101.0, 101.0, 400, 5),
+        ]);
+        assert_eq!(app.live_candles[1].timestamp.timestamp(), start + 5 * 60 + 5);
+        // The last bucket has only two of its five minutes so far
+        let forming = app.current_candle.as_ref().unwrap();
+        assert_eq!(ohlcv(forming), (101.5, 101.5, 100.0, 100.0, 230, 2));
+        // An uptick of 110 then a downtick of 120
+        assert_eq!(app.candle_delta, -10);
+        assert_eq!(app.cumulative_delta, cumulative);
+
+        // And back, from the same batches
+        app.set_candle_interval(CandleInterval::OneMinute);
+        assert_eq!(app.live_candles.len(), 11);
+        assert_eq!(app.current_candle.as_ref().map(|c| (c.close, c.volume)), Some((100.0, 120)));
+        assert_eq!(app.candle_delta, -120);
+    }
+
+    /// The row of `screen` that `needle` first appears on.
+    fn row_of(screen: &str, needle: &str) -> Option<usize> {
+        screen.lines().position(|line| line.contains(needle))
+    }
+
+    #[test]
+    fn tiny_terminals_get_a_notice() {
+        let app = App::new();
+        for (width, height) in [(20, 5), (MIN_WIDTH - 1, MIN_HEIGHT), (MIN_WIDTH, MIN_HEIGHT - 1)] {
+            let screen = render_to_text(&app, width, height).unwrap();
+            assert!(screen.contains("Terminal too small"), "{}×{}:\n{}", width, height, screen);
+        }
+        let screen = render_to_text(&app, MIN_WIDTH, MIN_HEIGHT).unwrap();
+        assert!(!screen.contains("Terminal too small"), "{}", screen);
+    }
+
+    #[test]
+    fn landing_panes_stack_when_narrow() {
+        let app = App::new();
+        let wide = render_to_text(&app, 120, 40).unwrap();
+        assert!(row_of(&wide, "Popular Stocks").is_some());
+        assert_eq!(row_of(&wide, "Popular Stocks"), row_of(&wide, "Watchlist"), "{}", wide);
+
+        let narrow = render_to_text(&app, NARROW_WIDTH - 1, 40).unwrap();
+        assert!(row_of(&narrow, "Popular Stocks") < row_of(&narrow, "Watchlist"), "{}", narrow);
+    }
+
+    #[test]
+    fn short_terminals_get_a_one_line_footer() {
+        let app = App::new();
+        let last_row = |height| render_to_text(&app, 100, height).unwrap().lines().last().unwrap().to_string();
+
+        assert!(last_row(SHORT_HEIGHT).trim_start().starts_with('└'));
+        let short = last_row(SHORT_HEIGHT - 1);
+        assert!(!short.trim().is_empty() && !short.contains('─'), "{}", short);
+    }
+
+    /// A live app with 19 closed candles and a forming one.
+    fn live_candles_app() -> App {
+        let mut app = live_app();
+        let from = DateTime::from_timestamp(1_700_000_000 / 60 * 60, 0).unwrap();
+        let mut candles = crate::provider::fixture::fixture_candles(from, 60, 20);
+        app.current_candle = candles.pop();
+        app.live_candles = candles.into();
+        app
+    }
+
+    #[test]
+    fn live_cursor_scrolls_the_view_with_it() {
+        // 30 cells inside the borders fit 10 candles
+        let mut app = live_candles_app();
+        app.toggle_live_cursor();
+        assert_eq!(app.live_cursor, Some(19));
+        app.move_live_cursor(-1, 32);
+        assert_eq!((app.live_cursor, app.live_scroll), (Some(18), 0));
+        app.move_live_cursor(-10, 32);
+        assert_eq!((app.live_cursor, app.live_scroll), (Some(8), 2));
+        app.jump_live_cursor(true, 32);
+        assert_eq!((app.live_cursor, app.live_scroll), (Some(17), 2));
+        app.jump_live_cursor(false, 32);
+        assert_eq!(app.live_cursor, Some(8));
+        app.move_live_cursor(100, 32);
+        assert_eq!((app.live_cursor, app.live_scroll), (Some(19), 0));
+        app.toggle_live_cursor();
+        assert_eq!(app.live_cursor, None);
+
+        let mut empty = live_app();
+        empty.move_live_cursor(-1, 32);
+        empty.toggle_live_cursor();
+        assert_eq!(empty.live_cursor, None);
+    }
+
+    #[test]
+    fn candle_detail_shows_the_selected_candle() {
+        let mut app = live_candles_app();
+        app.state = AppState::LiveCandles;
+        app.stock_data = Some(crate::provider::fixture::fixture_data("AAPL", "1m", 20));
+        app.time_zone = TimeZoneMode::Utc;
+        app.show_candle_detail = true;
+
+        app.live_cursor = Some(19);
+        let screen = render_to_text(&app, 120, 40).unwrap();
+        assert!(screen.contains("Candle (forming)"), "{}", screen);
+
+        app.live_cursor = Some(5);
+        let screen = render_to_text(&app, 120, 40).unwrap();
+        let (candle, prev) = (&app.live_candles[5], app.live_candles[4].close);
+        let time = candle.timestamp.format("%Y-%m-%d %H:%M").to_string();
+        let change = candle.close - prev;
+        for (label, value) in [
+            ("Time", time),
+            ("Open", format_price(candle.open, "$")),
+            ("Close", format_price(candle.close, "$")),
+            ("Trades", "0".to_string()),
+            ("Change", format!("{}{} ({:+.2}%)", sign_prefix(change), format_price(change.abs(), "$"), change / prev * 100.0)),
+        ] {
+            assert!(screen.contains(&format!("  {:<9}{}", label, value)), "{} {}:\n{}", label, value, screen);
+        }
+        assert!(!screen.contains("forming"), "{}", screen);
+    }
+
+    #[test]
+    fn alerts_compare_against_the_native_price() {
+        let mut app = App::new();
+        let mut data = crate::provider::fixture::fixture_data("7203.T", "1d", 10);
+        let native = data.current_price;
+        data.currency = Some("JPY".to_string());
+        data.fx = Some(crate::stock::FxRate {
+            native_currency: "JPY".to_string(),
+            display_currency: "USD".to_string(),
+            rate: 0.0065,
+        });
+        data.set_converted(true);
+        app.stock_data = Some(data);
+
+        let (price, currency) = app.current_price_for("7203.T").unwrap();
+        assert!((price - native).abs() < 1e-9);
+        assert_eq!(currency.as_deref(), Some("JPY"));
+        // A bare target under the yen price is a fall, though it's above the dollar one
+        let target = native / 2.0;
+        assert_eq!(crate::alerts::parse_condition(&target.to_string(), Some(price)), Some((target, false)));
+
+        app.landing_quotes.insert("NESN.SW".to_string(), crate::stock::QuoteSnapshot {
+            price: 98.0,
+            change_percent: 0.5,
+            market_state: crate::stock::MarketState::Regular,
+            currency: Some("CHF".to_string()),
+        });
+        assert_eq!(app.current_price_for("NESN.SW"), Some((98.0, Some("CHF".to_string()))));
+        assert_eq!(app.current_price_for("AAPL"), None);
+    }
+
+    #[test]
+    fn missing_fx_rate_reaches_the_error_log() {
+        let mut app = App::new();
+        app.symbol = "7203.T".to_string();
+        app.convert_currency = true;
+        let mut data = crate::provider::fixture::fixture_data("7203.T", "1d", 10);
+        data.currency = Some("JPY".to_string());
+        data.fx_error = Some("No JPY → USD rate: timed out".to_string());
+        app.apply_stock_data("7203.T", Ok(data));
+
+        assert!(app.ws_error_log.iter().any(|e| e.contains("7203.T: No JPY → USD rate: timed out")));
+        assert!(app.flash.as_ref().is_some_and(|(m, _)| m.contains("no exchange rate")));
+        app.toggle_currency();
+        assert!(app.flash.as_ref().is_some_and(|(m, _)| m.starts_with("No exchange rate")));
+    }
+
+    #[test]
+    fn help_lists_each_key_once_with_the_configured_emas() {
+        let mut app = App::new();
+        app.ema_periods = vec![12, 26, 50];
+        app.show_help = true;
+        let screen = render_to_text(&app, 100, 60).unwrap();
+
+        assert!(screen.contains("Toggle EMA 12/26/50 (chart, live candles)"), "{}", screen);
+        for key in ["f", "t", "m"] {
+            let rows = screen.lines().filter(|l| l.trim_start_matches([' ', '│']).starts_with(&format!("{:12}", key))).count();
+            assert_eq!(rows, 1, "'{}' rows:\n{}", key, screen);
+        }
+        assert!(screen.contains("Full-screen chart (chart) / previous session's pivot levels (live)"), "{}", screen);
+        assert!(screen.contains("Cycle chart interval (chart) / tape's minimum trade size (live ticker)"), "{}", screen);
+    }
+
+    #[test]
+    fn indicator_param_parse_table() {
+        use IndicatorParam::*;
+        let ok = |v: &[usize]| Ok(v.to_vec());
+        let err = |m: &str| Err(m.to_string());
+        let table = [
+            (Sma, "20, 50", ok(&[20, 50])),
+            (Sma, "9,21,50,200", ok(&[9, 21, 50, 200])),
+            (Ema, " 12 ,,26 ", ok(&[12, 26])),
+            (Sma, "", err("Enter 1 to 4 periods")),
+            (Ema, "5,10,20,50,100", err("Enter 1 to 4 periods")),
+            (Sma, "20,abc", err("\"abc\" is not a whole number")),
+            (Sma, "-5", err("\"-5\" is not a whole number")),
+            (Sma, "2.5", err("\"2.5\" is not a whole number")),
+            (Rsi, "14", ok(&[14])),
+            (Rsi, "14,28", err("Enter one period")),
+            (Volatility, "", err("Enter one period")),
+            (Rsi, "1", err("Periods must be 2–500")),
+            (Rsi, "500", ok(&[500])),
+            (Volatility, "501", err("Periods must be 2–500")),
+            (Ema, "0", err("Periods must be 2–500")),
+            (Macd, "12,26,9", ok(&[12, 26, 9])),
+            (Macd, "12,26", err("Enter 3 periods")),
+            (Macd, "12,26,9,3", err("Enter 3 periods")),
+            (Macd, "2,3,1", ok(&[2, 3, 1])),
+            (Macd, "1,26,9", err("Periods must be 2–500")),
+            (Macd, "12,26,0", err("Periods must be 1–500")),
+            (Macd, "12,501,9", err("Periods must be 1–500")),
+            (Macd, "26,12,9", err("The fast period must be shorter than the slow one")),
+            (Macd, "12,12,9", err("The fast period must be shorter than the slow one")),
+            (Stochastic, "14,3,3", ok(&[14, 3, 3])),
+            (Stochastic, "14,1,1", ok(&[14, 1, 1])),
+            // Only MACD orders its periods
+            (Stochastic, "14,20,3", ok(&[14, 20, 3])),
+            (Stochastic, "1,3,3", err("Periods must be 2–500")),
+            (Stochastic, "14,3", err("Enter 3 periods")),
+        ];
+        for (param, input, expected) in table {
+            assert_eq!(param.parse(input), expected, "{:?} {:?}", param, input);
+        }
+    }
+
+    #[test]
+    fn format_price_table() {
+        let table = [
+            (189.5, "$", "$189.50"),
+            (1.0, "$", "$1.00"),
+            (0.0, "$", "$0.00"),
+            (9_999.99, "$", "$9999.99"),
+            (10_000.0, "$", "$10,000.00"),
+            (611_234.5, "$", "$611,234.50"),
+            (1_234_567.891, "", "1,234,567.89"),
+            // Rounding up into the next group still gets its separator
+            (999_999.996, "$", "$1,000,000.00"),
+            (0.5, "$", "$0.5000"),
+            (0.08123, "$", "$0.08123"),
+            (0.00001234, "$", "$0.000012"),
+            (-0.25, "$", "-$0.2500"),
+            (-12_345.6, "€", "-€12,345.60"),
+        ];
+        for (value, cur, expected) in table {
+            assert_eq!(format_price(value, cur), expected, "{}", value);
+        }
+    }
 }
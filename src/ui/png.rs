@@ -0,0 +1,171 @@
+use chrono::{DateTime, Utc};
+use plotters::prelude::*;
+use std::path::{Path, PathBuf};
+
+use super::{format_price, Candlestick};
+use crate::stock::{StockData, TimeFrame};
+
+pub const PNG_WIDTH: u32 = 1200;
+pub const PNG_HEIGHT: u32 = 600;
+
+/// A moving average drawn over the price, in bar indices of whatever the
+/// image plots (candles or the raw series), as `IndicatorValues` holds them.
+pub struct Overlay<'a> {
+    pub label: String,
+    pub points: &'a [(f64, f64)],
+}
+
+const OVERLAY_COLORS: [RGBColor; 4] = [
+    RGBColor(0, 150, 200),
+    RGBColor(220, 160, 0),
+    RGBColor(180, 0, 180),
+    RGBColor(90, 120, 255),
+];
+
+/// Draw `data` (or `candles` of it) over `timeframe` with `overlays` to
+/// `charty_SYMBOL_TIMEFRAME_YYYYMMDD_HHMMSS.png` in `dir`.
+pub fn export_png(
+    data: &StockData,
+    candles: Option<&[Candlestick]>,
+    timeframe: TimeFrame,
+    interval: &str,
+    overlays: &[Overlay],
+    dir: &Path,
+) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let stem = format!(
+        "charty_{}_{}_{}",
+        crate::stock::file_safe_symbol(&data.symbol),
+        timeframe.short_label(),
+        chrono::Local::now().format("%Y%m%d_%H%M%S"),
+    );
+    let mut path = dir.join(format!("{}.png", stem));
+    let mut counter = 2;
+    while path.exists() {
+        path = dir.join(format!("{}_{}.png", stem, counter));
+        counter += 1;
+    }
+    draw(&path, data, candles, timeframe, interval, overlays)?;
+    Ok(path)
+}
+
+fn draw(
+    path: &Path,
+    data: &StockData,
+    candles: Option<&[Candlestick]>,
+    timeframe: TimeFrame,
+    interval: &str,
+    overlays: &[Overlay],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let timestamps: Vec<DateTime<Utc>> = match candles {
+        Some(candles) => candles.iter().map(|c| c.timestamp).collect(),
+        None => data.timestamps.clone(),
+    };
+    if timestamps.is_empty() {
+        return Err("Nothing to draw".into());
+    }
+    let (lows, highs): (Vec<f64>, Vec<f64>) = match candles {
+        Some(candles) => candles.iter().map(|c| (c.low, c.high)).unzip(),
+        None => data.prices.iter().map(|&p| (p, p)).unzip(),
+    };
+    let overlay_values = overlays.iter().flat_map(|o| o.points.iter().map(|&(_, v)| v));
+    let lo = lows.iter().copied().chain(overlay_values.clone()).fold(f64::INFINITY, f64::min);
+    let hi = highs.iter().copied().chain(overlay_values).fold(f64::NEG_INFINITY, f64::max);
+    let pad = ((hi - lo) * 0.05).max(hi.abs() * 0.001).max(1e-6);
+    let (lo, hi) = (lo - pad, hi + pad);
+    let last = timestamps.len() - 1;
+    let x_range = -0.5..last as f64 + 0.5;
+
+    let cur = data.currency_prefix();
+    let time_format = match timeframe {
+        TimeFrame::OneDay => "%H:%M",
+        TimeFrame::OneWeek => "%m/%d %H:%M",
+        _ => "%Y-%m-%d",
+    };
+
+    let root = BitMapBackend::new(path, (PNG_WIDTH, PNG_HEIGHT)).into_drawing_area();
+    root.fill(&WHITE)?;
+    let title = format!("{} - {} ({})", data.symbol, timeframe.display(), interval);
+    let mut chart = ChartBuilder::on(&root)
+        .caption(title, ("sans-serif", 28))
+        .margin(16)
+        .x_label_area_size(40)
+        .y_label_area_size(90)
+        .build_cartesian_2d(x_range, lo..hi)?;
+    chart
+        .configure_mesh()
+        .x_labels(8)
+        .y_labels(8)
+        .x_label_formatter(&|x| {
+            let i = x.round().clamp(0.0, last as f64) as usize;
+            timestamps[i].with_timezone(&chrono::Local).format(time_format).to_string()
+        })
+        .y_label_formatter(&|y| format_price(*y, &cur))
+        .light_line_style(RGBColor(235, 235, 235))
+        .draw()?;
+
+    match candles {
+        Some(candles) => {
+            // Bodies take up to 60% of the space each bar gets
+            let body = ((PNG_WIDTH - 140) as f64 / candles.len() as f64 * 0.6).clamp(1.0, 15.0) as u32;
+            chart.draw_series(candles.iter().enumerate().map(|(i, c)| {
+                CandleStick::new(i as f64, c.open, c.high, c.low, c.close, GREEN.filled(), RED.filled(), body)
+            }))?;
+        }
+        None => {
+            let color = match (data.prices.first(), data.prices.last()) {
+                (Some(first), Some(last)) if last < first => RED,
+                _ => RGBColor(0, 160, 0),
+            };
+            let points = data.prices.iter().enumerate().map(|(i, &p)| (i as f64, p));
+            chart.draw_series(LineSeries::new(points, color.stroke_width(2)))?;
+        }
+    }
+
+    for (overlay, &color) in overlays.iter().zip(OVERLAY_COLORS.iter().cycle()) {
+        chart
+            .draw_series(LineSeries::new(overlay.points.iter().copied(), color.stroke_width(2)))?
+            .label(overlay.label.clone())
+            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color.stroke_width(2)));
+    }
+    if !overlays.is_empty() {
+        chart
+            .configure_series_labels()
+            .position(SeriesLabelPosition::UpperLeft)
+            .background_style(WHITE.mix(0.8))
+            .border_style(BLACK)
+            .draw()?;
+    }
+    root.present()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Width and height from a PNG's IHDR chunk.
+    fn png_size(path: &Path) -> (u32, u32) {
+        let bytes = std::fs::read(path).unwrap();
+        assert_eq!(&bytes[..8], b"\x89PNG\r\n\x1a\n");
+        let be = |at: usize| u32::from_be_bytes(bytes[at..at + 4].try_into().unwrap());
+        (be(16), be(20))
+    }
+
+    #[test]
+    fn line_and_candle_charts_are_written_at_full_size() {
+        let dir = std::env::temp_dir().join(format!("charty-png-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let data = crate::provider::fixture::fixture_data("FIXT", "5m", 60);
+        let sma = crate::indicators::sma(&data.prices, 10);
+        let overlays = [Overlay { label: "SMA 10".to_string(), points: &sma }];
+
+        let line = export_png(&data, None, TimeFrame::OneDay, "5m", &overlays, &dir).unwrap();
+        assert!(line.file_name().unwrap().to_string_lossy().starts_with("charty_FIXT_1D_"));
+        assert_eq!(png_size(&line), (PNG_WIDTH, PNG_HEIGHT));
+
+        let candles = crate::provider::fixture::fixture_candles(data.timestamps[0], 300, 30);
+        let candle = export_png(&data, Some(&candles), TimeFrame::OneDay, "5m", &[], &dir).unwrap();
+        assert_ne!(candle, line);
+        assert_eq!(png_size(&candle), (PNG_WIDTH, PNG_HEIGHT));
+    }
+}
@@ -1,11 +1,17 @@
 use tokio::sync::mpsc;
 use std::sync::Arc;
 use tokio::sync::Mutex;
-use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio_tungstenite::{client_async_tls, connect_async, tungstenite::Message, MaybeTlsStream, WebSocketStream};
 use futures_util::{SinkExt, StreamExt};
 use serde_json::Value;
 use std::time::Duration;
 use chrono::Utc;
+use std::collections::{BTreeMap, BTreeSet};
+use serde::{Deserialize, Serialize};
+use crate::session::Record;
+use crate::stock::MarketHours;
 use crate::ui::WebSocketStatus;
 
 // Reconnection configuration constants
@@ -13,18 +19,80 @@ const MAX_RECONNECT_ATTEMPTS: u32 = 5;
 const BASE_DELAY_SECS: u64 = 2;
 const MAX_DELAY_SECS: u64 = 32;
 
+/// How long the websocket task gathers trades before sending them on.
+const BATCH_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How far ahead of the market's open a feed that dropped while it was
+/// closed connects again.
+const REOPEN_LEAD_SECS: i64 = 60;
+
+/// One or more trades of a symbol. A busy tape is coalesced into one of
+/// these per symbol per `BATCH_INTERVAL`, so the UI gets bounded traffic
+/// however fast trades arrive: `price` is the last trade, `open` the first,
+/// `high`/`low` the range between, `volume` the sum and `notional` the sum
+/// of price × volume, from which a VWAP follows.
 #[allow(dead_code)]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LivePrice {
     pub symbol: String,
     pub price: f64,
     pub timestamp: i64,
     pub volume: Option<u64>,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub trades: u32,
+    /// Missing from recordings made before it was added
+    #[serde(default)]
+    pub notional: f64,
+    /// Unrounded size where the feed trades fractions (Binance coins);
+    /// `volume` is it rounded. Also missing from older recordings
+    #[serde(default)]
+    pub quantity: f64,
+}
+
+impl LivePrice {
+    pub fn trade(symbol: String, price: f64, timestamp: i64, volume: Option<u64>) -> Self {
+        let quantity = volume.unwrap_or(0) as f64;
+        let notional = price * quantity;
+        Self { symbol, price, timestamp, volume, open: price, high: price, low: price, trades: 1, notional, quantity }
+    }
+
+    /// A trade of `quantity` units that needn't be whole, such as 0.002 BTC.
+    pub fn fractional_trade(symbol: String, price: f64, timestamp: i64, quantity: f64) -> Self {
+        let mut trade = Self::trade(symbol, price, timestamp, Some(quantity.round() as u64));
+        trade.quantity = quantity;
+        trade.notional = price * quantity;
+        trade
+    }
+
+    /// The batch's size, unrounded when the feed sent fractions.
+    pub fn size(&self) -> f64 {
+        if self.quantity > 0.0 { self.quantity } else { self.volume.unwrap_or(0) as f64 }
+    }
+
+    /// Fold a later trade of the same symbol into this batch.
+    fn merge(&mut self, later: LivePrice) {
+        self.price = later.price;
+        self.timestamp = later.timestamp;
+        self.high = self.high.max(later.high);
+        self.low = self.low.min(later.low);
+        self.trades += later.trades;
+        self.notional += later.notional;
+        // Fractions add up before rounding, so many small trades still count
+        let quantity = self.size() + later.size();
+        self.volume = match (self.volume, later.volume) {
+            (None, None) => None,
+            _ => Some(quantity.round() as u64),
+        };
+        self.quantity = quantity;
+    }
 }
 
 #[derive(Debug)]
 struct ReconnectionPolicy {
-    max_attempts: u32,
+    /// `None` keeps retrying for as long as it takes
+    max_attempts: Option<u32>,
     base_delay: Duration,
     max_delay: Duration,
     current_attempt: u32,
@@ -33,7 +101,7 @@ struct ReconnectionPolicy {
 impl ReconnectionPolicy {
     fn new() -> Self {
         Self {
-            max_attempts: MAX_RECONNECT_ATTEMPTS,
+            max_attempts: (!crate::config::keep_reconnecting()).then_some(MAX_RECONNECT_ATTEMPTS),
             base_delay: Duration::from_secs(BASE_DELAY_SECS),
             max_delay: Duration::from_secs(MAX_DELAY_SECS),
             current_attempt: 0,
@@ -41,12 +109,12 @@ impl ReconnectionPolicy {
     }
 
     fn calculate_delay(&self) -> Duration {
-        let delay = self.base_delay * 2_u32.pow(self.current_attempt);
+        let delay = self.base_delay.saturating_mul(2_u32.saturating_pow(self.current_attempt));
         delay.min(self.max_delay)
     }
 
     fn should_retry(&self) -> bool {
-        self.current_attempt < self.max_attempts
+        self.max_attempts.is_none_or(|max| self.current_attempt < max)
     }
 
     fn increment(&mut self) {
@@ -78,55 +146,389 @@ pub enum ConnectionStatus {
     Error(String),
 }
 
-pub struct WebSocketManager {
-    pub status: Arc<Mutex<ConnectionStatus>>,
+/// Where a websocket's trades come from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Provider {
+    Finnhub,
+    /// Binance's public trade stream: crypto only, but no key needed
+    Binance,
+}
+
+impl Provider {
+    /// Binance for the crypto pairs it lists, Finnhub for everything else.
+    pub fn for_symbol(symbol: &str) -> Self {
+        match yahoo_to_finnhub_symbol(symbol) {
+            Ok(feed) if feed.starts_with("BINANCE:") => Provider::Binance,
+            _ => Provider::Finnhub,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Provider::Finnhub => "Finnhub",
+            Provider::Binance => "Binance",
+        }
+    }
+}
+
+/// What a provider's connection produced next.
+pub enum FeedEvent {
+    /// The trades in one message, tagged with their feed symbol
+    Trades(Vec<LivePrice>),
+    /// Any other traffic (acks, pongs, heartbeats), which still shows the
+    /// connection is alive
+    Alive,
+    /// The provider answered with an error of its own, such as Finnhub's
+    /// `{"type":"error","msg":"Invalid symbol"}`
+    Rejected(String),
+    /// JSON of a type the provider isn't known to send, named by that type
+    Unexpected(String),
+    /// A text frame that isn't JSON at all
+    Unparseable(String),
+    /// The connection has gone, with the error that ended it if there was one
+    Closed(Option<String>),
+}
+
+/// The wire side of a live feed: how to reach a provider, what it calls a
+/// symbol and how its messages read. `WebSocketManager` does the rest
+/// (reconnecting, batching, stale detection and status) for any of them.
+pub trait LiveProvider: Send + 'static {
+    const KIND: Provider;
+
+    /// What the feed calls `symbol` (a Yahoo symbol), or a user-facing
+    /// reason it has no feed for it.
+    fn feed_symbol(symbol: &str) -> Result<String, String>;
+
+    /// Open a fresh connection, replacing any earlier one.
+    fn connect(&mut self) -> impl Future<Output = Result<(), ConnectFailure>> + Send;
+
+    fn subscribe(&mut self, feed: &str) -> impl Future<Output = Result<(), String>> + Send;
+
+    fn unsubscribe(&mut self, feed: &str) -> impl Future<Output = Result<(), String>> + Send;
+
+    /// Prod a quiet connection into answering.
+    fn ping(&mut self) -> impl Future<Output = Result<(), String>> + Send;
+
+    /// Wait for the next message. Must be cancel-safe: the manager drops
+    /// the future whenever a command or batch flush comes first.
+    fn next_event(&mut self) -> impl Future<Output = FeedEvent> + Send;
+
+    /// Close the connection.
+    fn shutdown(&mut self) -> impl Future<Output = ()> + Send;
+}
+
+/// Finnhub's trade websocket: US stocks, forex and crypto, with an API key.
+pub struct FinnhubProvider {
     api_key: Option<String>,
+    endpoint: String,
+    stream: Option<WsStream>,
 }
 
-impl WebSocketManager {
+impl FinnhubProvider {
     pub fn new(api_key: Option<String>) -> Self {
+        Self { api_key, endpoint: "wss://ws.finnhub.io".to_string(), stream: None }
+    }
+
+    /// The same provider against a local server, for tests.
+    #[cfg(test)]
+    pub fn at(endpoint: &str, api_key: &str) -> Self {
+        Self { api_key: Some(api_key.to_string()), endpoint: endpoint.to_string(), stream: None }
+    }
+
+    /// `{"type": "subscribe", "symbol": "AAPL"}` and its unsubscribe twin.
+    fn subscription_message(kind: &str, feed: &str) -> String {
+        serde_json::json!({ "type": kind, "symbol": feed }).to_string()
+    }
+
+    /// Trades come as `{"type": "trade", "data": [...]}`, errors as
+    /// `{"type": "error", "msg": ...}`, and idle connections get `ping`s.
+    fn parse(json: &Value) -> FeedEvent {
+        match json["type"].as_str() {
+            Some("trade") => FeedEvent::Trades(
+                json["data"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|trade| {
+                        let (price, ts, symbol) = (trade["p"].as_f64()?, trade["t"].as_i64()?, trade["s"].as_str()?);
+                        Some(LivePrice::trade(symbol.to_string(), price, ts / 1000, trade["v"].as_u64()))
+                    })
+                    .collect(),
+            ),
+            Some("ping") => FeedEvent::Alive,
+            Some("error") => FeedEvent::Rejected(json["msg"].as_str().unwrap_or("unknown error").to_string()),
+            Some(other) => FeedEvent::Unexpected(other.to_string()),
+            None => FeedEvent::Unexpected("untyped".to_string()),
+        }
+    }
+}
+
+impl LiveProvider for FinnhubProvider {
+    const KIND: Provider = Provider::Finnhub;
+
+    fn feed_symbol(symbol: &str) -> Result<String, String> {
+        yahoo_to_finnhub_symbol(symbol)
+    }
+
+    async fn connect(&mut self) -> Result<(), ConnectFailure> {
+        let Some(ref api_key) = self.api_key else {
+            return Err(ConnectFailure::new(
+                "No API key configured. Set FINNHUB_API_KEY environment variable.".to_string(),
+                "No API key configured",
+                true,
+            ));
+        };
+        let url = format!("{}/?token={}", self.endpoint, api_key.trim());
+        self.stream = Some(connect(&url, ("ws.finnhub.io", 443)).await?);
+        Ok(())
+    }
+
+    async fn subscribe(&mut self, feed: &str) -> Result<(), String> {
+        send_text(&mut self.stream, Self::subscription_message("subscribe", feed)).await
+    }
+
+    async fn unsubscribe(&mut self, feed: &str) -> Result<(), String> {
+        send_text(&mut self.stream, Self::subscription_message("unsubscribe", feed)).await
+    }
+
+    async fn ping(&mut self) -> Result<(), String> {
+        send_ping(&mut self.stream).await
+    }
+
+    async fn next_event(&mut self) -> FeedEvent {
+        next_ws_event(&mut self.stream, Self::parse).await
+    }
+
+    async fn shutdown(&mut self) {
+        close(&mut self.stream).await;
+    }
+}
+
+/// Binance's public trade stream. Crypto only, but no key needed.
+pub struct BinanceProvider {
+    stream: Option<WsStream>,
+}
+
+impl BinanceProvider {
+    pub fn new() -> Self {
+        Self { stream: None }
+    }
+
+    /// `{"method": "SUBSCRIBE", "params": ["btcusdt@trade"], "id": 1}`
+    fn subscription_message(method: &str, feed: &str) -> String {
+        serde_json::json!({
+            "method": method,
+            "params": [format!("{}@trade", feed.to_ascii_lowercase())],
+            "id": 1,
+        })
+        .to_string()
+    }
+
+    /// One trade per message, with price and quantity as strings. The
+    /// quantity is in coins and kept as sent; `volume` is it rounded. Requests
+    /// are answered with `{"result": null, "id": 1}`, or an `error` object.
+    fn parse(json: &Value) -> FeedEvent {
+        if json["e"] != "trade" {
+            if let Some(msg) = json["error"]["msg"].as_str().or_else(|| json["msg"].as_str()) {
+                return FeedEvent::Rejected(msg.to_string());
+            }
+            if json.get("result").is_some() {
+                return FeedEvent::Alive;
+            }
+            return FeedEvent::Unexpected(json["e"].as_str().unwrap_or("untyped").to_string());
+        }
+        let number = |key: &str| json[key].as_str().and_then(|v| v.parse::<f64>().ok());
+        match (number("p"), json["T"].as_i64(), json["s"].as_str()) {
+            (Some(price), Some(ts), Some(symbol)) => {
+                let trade = match number("q") {
+                    Some(quantity) => LivePrice::fractional_trade(symbol.to_string(), price, ts / 1000, quantity),
+                    None => LivePrice::trade(symbol.to_string(), price, ts / 1000, None),
+                };
+                FeedEvent::Trades(vec![trade])
+            }
+            _ => FeedEvent::Trades(Vec::new()),
+        }
+    }
+}
+
+impl LiveProvider for BinanceProvider {
+    const KIND: Provider = Provider::Binance;
+
+    /// The bare `BTCUSDT`, which is how Binance names the pair in its trades.
+    fn feed_symbol(symbol: &str) -> Result<String, String> {
+        yahoo_to_finnhub_symbol(symbol)?
+            .strip_prefix("BINANCE:")
+            .map(str::to_string)
+            .ok_or_else(|| format!("{} is not traded on Binance", symbol))
+    }
+
+    async fn connect(&mut self) -> Result<(), ConnectFailure> {
+        self.stream = Some(connect("wss://stream.binance.com:9443/ws", ("stream.binance.com", 9443)).await?);
+        Ok(())
+    }
+
+    async fn subscribe(&mut self, feed: &str) -> Result<(), String> {
+        send_text(&mut self.stream, Self::subscription_message("SUBSCRIBE", feed)).await
+    }
+
+    async fn unsubscribe(&mut self, feed: &str) -> Result<(), String> {
+        send_text(&mut self.stream, Self::subscription_message("UNSUBSCRIBE", feed)).await
+    }
+
+    async fn ping(&mut self) -> Result<(), String> {
+        send_ping(&mut self.stream).await
+    }
+
+    async fn next_event(&mut self) -> FeedEvent {
+        next_ws_event(&mut self.stream, Self::parse).await
+    }
+
+    async fn shutdown(&mut self) {
+        close(&mut self.stream).await;
+    }
+}
+
+async fn send_text(stream: &mut Option<WsStream>, text: String) -> Result<(), String> {
+    let ws = stream.as_mut().ok_or("Not connected")?;
+    ws.send(Message::Text(text)).await.map_err(|e| format!("WebSocket error: {}", e))
+}
+
+async fn send_ping(stream: &mut Option<WsStream>) -> Result<(), String> {
+    let ws = stream.as_mut().ok_or("Not connected")?;
+    ws.send(Message::Ping(Vec::new())).await.map_err(|e| format!("WebSocket error: {}", e))
+}
+
+/// Read the next message off `stream`, answering pings on the way. JSON text
+/// goes through `parse`; anything else only shows the connection is alive.
+async fn next_ws_event(stream: &mut Option<WsStream>, parse: fn(&Value) -> FeedEvent) -> FeedEvent {
+    let Some(ws) = stream.as_mut() else {
+        return FeedEvent::Closed(None);
+    };
+    match ws.next().await {
+        Some(Ok(Message::Text(text))) => match serde_json::from_str::<Value>(&text) {
+            Ok(json) => parse(&json),
+            Err(_) => FeedEvent::Unparseable(text),
+        },
+        Some(Ok(Message::Ping(data))) => {
+            let _ = ws.send(Message::Pong(data)).await;
+            FeedEvent::Alive
+        }
+        Some(Ok(_)) => FeedEvent::Alive,
+        Some(Err(e)) => FeedEvent::Closed(Some(format!("WebSocket error: {}", e))),
+        None => FeedEvent::Closed(None),
+    }
+}
+
+async fn close(stream: &mut Option<WsStream>) {
+    if let Some(mut ws) = stream.take() {
+        let _ = ws.close(None).await;
+    }
+}
+
+/// Change to the subscriptions of a running connection.
+#[derive(Debug)]
+enum Command {
+    Subscribe { feed: String, symbol: String },
+    Unsubscribe { feed: String },
+}
+
+/// Feed symbol → the Yahoo symbol its trades are tagged with.
+type Subscriptions = BTreeMap<String, String>;
+
+/// A running manager as the app sees it, whichever provider it drives.
+pub trait LiveFeed: Send + Sync {
+    fn provider(&self) -> Provider;
+
+    /// Add `symbol` (a Yahoo symbol) to the connection. Takes effect right
+    /// away when connected, otherwise on the next (re)connect.
+    fn subscribe(&self, symbol: &str) -> Result<(), String>;
+
+    /// Drop `symbol` from the connection; trades for it stop arriving.
+    fn unsubscribe(&self, symbol: &str) -> Result<(), String>;
+}
+
+/// One provider connection carrying any number of symbol subscriptions.
+/// Trades come out tagged with the Yahoo symbol they were subscribed as.
+pub struct WebSocketManager<P: LiveProvider> {
+    pub status: Arc<Mutex<ConnectionStatus>>,
+    commands: mpsc::UnboundedSender<Command>,
+    /// Handed to the connection task when the manager starts
+    pending: Mutex<Option<(P, mpsc::UnboundedReceiver<Command>)>>,
+    /// `config::stale_after` and `config::no_trade_after`, read once
+    stale_after: Duration,
+    no_trade_after: Duration,
+}
+
+impl<P: LiveProvider> LiveFeed for WebSocketManager<P> {
+    fn provider(&self) -> Provider {
+        P::KIND
+    }
+
+    fn subscribe(&self, symbol: &str) -> Result<(), String> {
+        let feed = P::feed_symbol(symbol)?;
+        let _ = self.commands.send(Command::Subscribe { feed, symbol: symbol.to_string() });
+        Ok(())
+    }
+
+    fn unsubscribe(&self, symbol: &str) -> Result<(), String> {
+        let feed = P::feed_symbol(symbol)?;
+        let _ = self.commands.send(Command::Unsubscribe { feed });
+        Ok(())
+    }
+}
+
+impl<P: LiveProvider> WebSocketManager<P> {
+    pub fn new(provider: P) -> Self {
+        let (commands, command_rx) = mpsc::unbounded_channel();
         Self {
             status: Arc::new(Mutex::new(ConnectionStatus::Disconnected)),
-            api_key,
+            commands,
+            pending: Mutex::new(Some((provider, command_rx))),
+            stale_after: crate::config::stale_after(),
+            no_trade_after: crate::config::no_trade_after(),
         }
     }
 
+    /// Run the connection for `symbols`, given as (feed, Yahoo) pairs,
+    /// until `should_stop` is set. A manager runs once. With `market_hours`,
+    /// a feed that drops while that market is closed waits for it to reopen
+    /// instead of retrying.
     pub async fn start(
         &self,
-        symbol: String,
+        symbols: Vec<(String, String)>,
         _base_price: f64,
+        market_hours: Option<MarketHours>,
         tx: mpsc::UnboundedSender<LivePrice>,
         status_tx: mpsc::UnboundedSender<WebSocketStatus>,
         should_stop: Arc<Mutex<bool>>,
     ) {
-        if let Some(ref api_key) = self.api_key {
-            self.start_finnhub_websocket(symbol, api_key.clone(), tx, status_tx, should_stop).await;
-        } else {
-            *self.status.lock().await = ConnectionStatus::Error(
-                "No API key configured. Set FINNHUB_API_KEY environment variable.".to_string()
-            );
-            let _ = status_tx.send(WebSocketStatus::Error {
-                message: "No API key configured".to_string(),
-                recoverable: false,
-            });
-        }
+        let Some((provider, commands)) = self.pending.lock().await.take() else {
+            log_to_file("WebSocket manager already started");
+            return;
+        };
+        let subscriptions = symbols.into_iter().collect();
+        self.run_connection(provider, subscriptions, commands, market_hours, tx, status_tx, should_stop).await;
     }
 
-    async fn start_finnhub_websocket(
+    #[allow(clippy::too_many_arguments)]
+    async fn run_connection(
         &self,
-        symbol: String,
-        api_key: String,
+        mut provider: P,
+        mut subscriptions: Subscriptions,
+        mut commands: mpsc::UnboundedReceiver<Command>,
+        market_hours: Option<MarketHours>,
         tx: mpsc::UnboundedSender<LivePrice>,
         status_tx: mpsc::UnboundedSender<WebSocketStatus>,
         should_stop: Arc<Mutex<bool>>,
     ) {
         let mut reconnection_policy = ReconnectionPolicy::new();
+        let name = P::KIND.name();
 
         // Reconnection loop
         loop {
-            // Check if we should stop before attempting connection
+            // Check if we should stop before attempting connection. Whoever
+            // stopped the feed sets the status it goes back to.
             if *should_stop.lock().await {
-                let _ = status_tx.send(WebSocketStatus::Disconnected);
                 *self.status.lock().await = ConnectionStatus::Disconnected;
                 log_to_file("WebSocket stopped by user");
                 return;
@@ -135,28 +537,32 @@ impl WebSocketManager {
             // Send connecting status
             *self.status.lock().await = ConnectionStatus::Connecting;
             let _ = status_tx.send(WebSocketStatus::Connecting);
-            let trimmed_key = api_key.trim();
-            let url = format!("wss://ws.finnhub.io/?token={}", trimmed_key);
-            log_to_file(&format!("WebSocket connecting to Finnhub for {}", symbol));
+            // Changes made while disconnected apply to this connection
+            while let Ok(command) = commands.try_recv() {
+                apply_command(&mut subscriptions, command);
+            }
+            let names = subscriptions.keys().cloned().collect::<Vec<_>>().join(", ");
+            log_to_file(&format!("WebSocket connecting to {} for {}", name, names));
 
-            match connect_async(&url).await {
-                Ok((ws_stream, _)) => {
+            match provider.connect().await {
+                Ok(()) => {
                     // Connection successful - reset reconnection counter
                     reconnection_policy.reset();
                     *self.status.lock().await = ConnectionStatus::Connected;
                     let connected_since = Utc::now();
                     let _ = status_tx.send(WebSocketStatus::Connected { since: connected_since });
-                    log_to_file(&format!("WebSocket connected successfully for {}", symbol));
-
-                    let (mut write, mut read) = ws_stream.split();
+                    log_to_file(&format!("WebSocket connected successfully for {}", names));
 
-                    // Subscribe to symbol
-                    let subscribe_msg = serde_json::json!({
-                        "type": "subscribe",
-                        "symbol": symbol
-                    });
+                    // One subscribe message per symbol
+                    let mut subscribed = Ok(());
+                    for feed in subscriptions.keys() {
+                        subscribed = provider.subscribe(feed).await;
+                        if subscribed.is_err() {
+                            break;
+                        }
+                    }
 
-                    if let Err(e) = write.send(Message::Text(subscribe_msg.to_string())).await {
+                    if let Err(e) = subscribed {
                         let error_msg = format!("Failed to subscribe: {}", e);
                         *self.status.lock().await = ConnectionStatus::Error(error_msg.clone());
                         let _ = status_tx.send(WebSocketStatus::Error {
@@ -168,39 +574,70 @@ impl WebSocketManager {
                         continue;
                     }
 
-                    log_to_file(&format!("WebSocket subscribed to {}", symbol));
+                    log_to_file(&format!("WebSocket subscribed to {}", names));
 
                     // Listen for updates
                     let connection_result = self.handle_websocket_messages(
-                        symbol.clone(),
-                        &mut write,
-                        &mut read,
+                        &mut provider,
+                        &mut subscriptions,
+                        &mut commands,
                         &tx,
+                        &status_tx,
+                        connected_since,
                         &should_stop,
                     ).await;
 
                     // Connection ended - check why
                     if *should_stop.lock().await {
                         // User requested stop
-                        let unsubscribe_msg = serde_json::json!({
-                            "type": "unsubscribe",
-                            "symbol": symbol
-                        });
-                        let _ = write.send(Message::Text(unsubscribe_msg.to_string())).await;
-                        let _ = status_tx.send(WebSocketStatus::Disconnected);
+                        for feed in subscriptions.keys() {
+                            let _ = provider.unsubscribe(feed).await;
+                        }
+                        provider.shutdown().await;
                         *self.status.lock().await = ConnectionStatus::Disconnected;
-                        log_to_file("WebSocket disconnected by user");
+                        log_to_file("WebSocket unsubscribed and closed by user");
                         return;
                     }
 
                     // Connection error - should we reconnect?
                     match connection_result {
+                        ConnectionResult::NoData(dropped) => {
+                            // The socket is fine; reconnecting won't make trades appear
+                            // until the market opens, if it's closed (as when 'r'
+                            // forces a connection during the wait for it)
+                            provider.shutdown().await;
+                            if market_reopens_at(market_hours.as_ref(), P::KIND).is_some() {
+                                subscriptions.extend(dropped);
+                                log_to_file("WebSocket subscriptions never traded, market closed");
+                                // On to the wait for the open
+                            } else {
+                                let message = "No live data available for this symbol".to_string();
+                                let _ = status_tx.send(WebSocketStatus::Error {
+                                    message: message.clone(),
+                                    recoverable: false,
+                                });
+                                *self.status.lock().await = ConnectionStatus::Error(message);
+                                log_to_file("WebSocket subscriptions never traded, not reconnecting");
+                                return;
+                            }
+                        }
+                        ConnectionResult::Rejected(msg) => {
+                            log_to_file(&format!("WebSocket rejected: {}", msg));
+                            let recoverable = is_recoverable(&msg);
+                            // Shown either way, so the error log says why
+                            let _ = status_tx.send(WebSocketStatus::Error {
+                                message: msg.clone(),
+                                recoverable,
+                            });
+                            if !recoverable {
+                                *self.status.lock().await = ConnectionStatus::Error(msg);
+                                log_to_file("WebSocket request refused, not reconnecting");
+                                return;
+                            }
+                        }
                         ConnectionResult::Error(msg) => {
                             log_to_file(&format!("WebSocket error: {}", msg));
-                            // Determine if error is recoverable
-                            let recoverable = !msg.to_lowercase().contains("auth")
-                                && !msg.to_lowercase().contains("invalid")
-                                && !msg.to_lowercase().contains("api key");
+                            let recoverable = is_recoverable(&msg);
 
                             if !recoverable {
                                 let _ = status_tx.send(WebSocketStatus::Error {
@@ -219,44 +656,66 @@ impl WebSocketManager {
                         }
                     }
                 }
-                Err(e) => {
-                    let error_msg = format!("Failed to connect: {}", e);
-                    *self.status.lock().await = ConnectionStatus::Error(error_msg.clone());
-                    log_to_file(&format!("WebSocket connection error: {}", error_msg));
-
-                    // Check if this is an auth error (fatal)
-                    let error_str = e.to_string().to_lowercase();
-                    if error_str.contains("auth") || error_str.contains("401") || error_str.contains("403") {
+                Err(failure) => {
+                    *self.status.lock().await = ConnectionStatus::Error(failure.message.clone());
+                    log_to_file(&format!("WebSocket connection error: {}", failure.message));
+
+                    // Auth failures and proxy rejections won't fix themselves
+                    if failure.fatal {
                         let _ = status_tx.send(WebSocketStatus::Error {
-                            message: "Authentication failed".to_string(),
+                            message: failure.summary,
                             recoverable: false,
                         });
-                        log_to_file("WebSocket authentication failed, not reconnecting");
+                        log_to_file("WebSocket connection refused, not reconnecting");
                         return;
                     }
                 }
             }
 
+            // Retrying against a closed market only burns attempts; come back
+            // shortly before it opens. Right before the open, retry as usual.
+            let wake = market_reopens_at(market_hours.as_ref(), P::KIND)
+                .map(|reopens_at| (reopens_at, reopens_at - chrono::Duration::seconds(REOPEN_LEAD_SECS)))
+                .filter(|(_, wake)| *wake > Utc::now());
+            if let Some((reopens_at, wake)) = wake {
+                *self.status.lock().await = ConnectionStatus::Disconnected;
+                let _ = status_tx.send(WebSocketStatus::MarketClosed { reopens_at });
+                log_to_file(&format!("WebSocket market closed, reconnecting at {}", wake));
+                while Utc::now() < wake && !*should_stop.lock().await {
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                }
+                reconnection_policy.reset();
+                continue;
+            }
+
             // Attempt reconnection if policy allows
             if reconnection_policy.should_retry() {
                 reconnection_policy.increment();
                 let delay = reconnection_policy.calculate_delay();
-                let _ = status_tx.send(WebSocketStatus::Reconnecting {
-                    attempt: reconnection_policy.current_attempt,
-                    next_retry_in: delay,
-                });
+                let attempt = reconnection_policy.current_attempt;
+                let max_attempts = reconnection_policy.max_attempts;
                 log_to_file(&format!(
                     "WebSocket reconnecting (attempt {}/{}) in {:?}",
-                    reconnection_policy.current_attempt,
-                    reconnection_policy.max_attempts,
+                    attempt,
+                    max_attempts.map_or("∞".to_string(), |max| max.to_string()),
                     delay
                 ));
-                tokio::time::sleep(delay).await;
+                // Count down a second at a time so the header shows it, and a
+                // stop doesn't wait out the whole delay
+                let retry_at = tokio::time::Instant::now() + delay;
+                loop {
+                    let next_retry_in = retry_at.saturating_duration_since(tokio::time::Instant::now());
+                    if next_retry_in.is_zero() || *should_stop.lock().await {
+                        break;
+                    }
+                    let _ = status_tx.send(WebSocketStatus::Reconnecting { attempt, max_attempts, next_retry_in });
+                    tokio::time::sleep(next_retry_in.min(Duration::from_secs(1))).await;
+                }
             } else {
                 // Max retries reached
                 let error_msg = format!(
                     "Failed to connect after {} attempts",
-                    reconnection_policy.max_attempts
+                    reconnection_policy.current_attempt
                 );
                 let _ = status_tx.send(WebSocketStatus::Error {
                     message: error_msg.clone(),
@@ -269,98 +728,858 @@ impl WebSocketManager {
         }
     }
 
+    /// Forward trades until the connection ends. A connection silent for
+    /// half the stale window gets a ping; at the full window the status turns
+    /// `Stale`, and after twice the window with no pong it's given up on.
+    /// A subscription without a trade for `no_trade_after` is dropped, and
+    /// once that leaves nothing the connection ends with `NoData`, carrying
+    /// the dropped ones. An error
+    /// from the provider ends it with `Rejected`; frames that don't parse, or
+    /// parse as a type the provider isn't known to send, go to the error log
+    /// as a `Warning` (unparseable ones by count, unexpected types once each).
+    #[allow(clippy::too_many_arguments)]
     async fn handle_websocket_messages(
         &self,
-        symbol: String,
-        write: &mut futures_util::stream::SplitSink<
-            tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>,
-            Message
-        >,
-        read: &mut futures_util::stream::SplitStream<
-            tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>
-        >,
+        provider: &mut P,
+        subscriptions: &mut Subscriptions,
+        commands: &mut mpsc::UnboundedReceiver<Command>,
         tx: &mpsc::UnboundedSender<LivePrice>,
+        status_tx: &mpsc::UnboundedSender<WebSocketStatus>,
+        connected_since: chrono::DateTime<Utc>,
         should_stop: &Arc<Mutex<bool>>,
     ) -> ConnectionResult {
+        let stale_after = self.stale_after;
+        let mut batches: BTreeMap<String, LivePrice> = BTreeMap::new();
+        let mut flush = tokio::time::interval(BATCH_INTERVAL);
+        let mut last_message = Utc::now();
+        let mut pinged = false;
+        let mut stale = false;
+        let no_trade_after = self.no_trade_after;
+        // Subscriptions still waiting for their first trade, and since when
+        let mut untraded: BTreeMap<String, chrono::DateTime<Utc>> =
+            subscriptions.keys().map(|feed| (feed.clone(), connected_since)).collect();
+        let name = P::KIND.name();
+        let mut unparseable: u32 = 0;
+        let mut unexpected: BTreeSet<String> = BTreeSet::new();
+        let mut dropped = Subscriptions::new();
         loop {
             if *should_stop.lock().await {
                 return ConnectionResult::Disconnected;
             }
 
+            let now = Utc::now();
+            let dead: Vec<String> = untraded.iter()
+                .filter(|(_, since)| (now - **since).to_std().unwrap_or_default() >= no_trade_after)
+                .map(|(feed, _)| feed.clone())
+                .collect();
+            if !dead.is_empty() {
+                for feed in dead {
+                    log_to_file(&format!("WebSocket dropping {}: no trades in {}s", feed, no_trade_after.as_secs()));
+                    untraded.remove(&feed);
+                    if let Some(symbol) = subscriptions.remove(&feed) {
+                        dropped.insert(feed.clone(), symbol);
+                    }
+                    let _ = provider.unsubscribe(&feed).await;
+                }
+                if subscriptions.is_empty() {
+                    return ConnectionResult::NoData(dropped);
+                }
+            }
+
+            let silent = (Utc::now() - last_message).to_std().unwrap_or_default();
+            if silent >= stale_after * 2 {
+                return ConnectionResult::Error(format!("No data or pong for {}s", silent.as_secs()));
+            }
+            if silent >= stale_after && !stale {
+                stale = true;
+                let _ = status_tx.send(WebSocketStatus::Stale { last_message });
+                log_to_file(&format!("WebSocket quiet for {}s", silent.as_secs()));
+            }
+            if silent >= stale_after / 2 && !pinged {
+                pinged = true;
+                if let Err(e) = provider.ping().await {
+                    return ConnectionResult::Error(e);
+                }
+            }
+
             tokio::select! {
-                msg = read.next() => {
-                    match msg {
-                        Some(Ok(Message::Text(text))) => {
-                            if let Ok(json) = serde_json::from_str::<Value>(&text) {
-                                if json["type"] == "trade" {
-                                    if let Some(data) = json["data"].as_array() {
-                                        for trade in data {
-                                            if let (Some(price), Some(ts)) = (
-                                                trade["p"].as_f64(),
-                                                trade["t"].as_i64(),
-                                            ) {
-                                                let volume = trade["v"].as_u64();
-                                                let live_price = LivePrice {
-                                                    symbol: symbol.clone(),
-                                                    price,
-                                                    timestamp: ts / 1000,
-                                                    volume,
-                                                };
-
-                                                if tx.send(live_price).is_err() {
-                                                    return ConnectionResult::Disconnected;
-                                                }
-                                            }
-                                        }
-                                    }
-                                }
+                event = provider.next_event() => {
+                    let trades = match event {
+                        FeedEvent::Trades(trades) => trades,
+                        FeedEvent::Alive => Vec::new(),
+                        FeedEvent::Unparseable(text) => {
+                            unparseable += 1;
+                            let snippet: String = text.chars().take(80).collect();
+                            log_to_file(&format!("WebSocket unparseable frame #{}: {}", unparseable, snippet));
+                            // 1st, 10th, 100th...: a garbled feed shouldn't flood the log
+                            if 10u32.pow(unparseable.ilog10()) == unparseable {
+                                let _ = status_tx.send(WebSocketStatus::Warning(format!(
+                                    "{} unparseable frame(s) from {}, latest: {}", unparseable, name, snippet
+                                )));
                             }
+                            Vec::new()
                         }
-                        Some(Ok(Message::Ping(data))) => {
-                            let _ = write.send(Message::Pong(data)).await;
+                        FeedEvent::Unexpected(kind) => {
+                            if unexpected.insert(kind.clone()) {
+                                log_to_file(&format!("WebSocket unexpected message type: {}", kind));
+                                let _ = status_tx.send(WebSocketStatus::Warning(format!(
+                                    "Unexpected {} message type: {}", name, kind
+                                )));
+                            }
+                            Vec::new()
                         }
-                        Some(Err(e)) => {
-                            return ConnectionResult::Error(format!("WebSocket error: {}", e));
+                        ended @ (FeedEvent::Rejected(_) | FeedEvent::Closed(_)) => {
+                            // Trades gathered since the last flush still happened
+                            for (_, batch) in std::mem::take(&mut batches) {
+                                let _ = tx.send(batch);
+                            }
+                            return match ended {
+                                FeedEvent::Rejected(msg) => ConnectionResult::Rejected(format!("{}: {}", name, msg)),
+                                FeedEvent::Closed(Some(error)) => ConnectionResult::Error(error),
+                                _ => ConnectionResult::Disconnected,
+                            };
                         }
-                        None => {
-                            return ConnectionResult::Disconnected;
+                    };
+                    last_message = Utc::now();
+                    pinged = false;
+                    if stale {
+                        stale = false;
+                        let _ = status_tx.send(WebSocketStatus::Connected { since: connected_since });
+                    }
+                    for mut live_price in trades {
+                        // Trades for a symbol dropped a moment ago can still arrive
+                        let Some(symbol) = subscriptions.get(&live_price.symbol) else { continue };
+                        untraded.remove(&live_price.symbol);
+                        live_price.symbol = symbol.clone();
+                        match batches.get_mut(symbol) {
+                            Some(batch) => batch.merge(live_price),
+                            None => { batches.insert(symbol.clone(), live_price); }
                         }
-                        _ => {}
                     }
                 }
-                _ = tokio::time::sleep(tokio::time::Duration::from_millis(100)) => {
+                Some(command) = commands.recv() => {
+                    let (subscribe, feed) = match &command {
+                        Command::Subscribe { feed, .. } if !subscriptions.contains_key(feed) => (true, feed.clone()),
+                        Command::Unsubscribe { feed } if subscriptions.contains_key(feed) => (false, feed.clone()),
+                        _ => continue,
+                    };
+                    apply_command(subscriptions, command);
+                    if subscribe {
+                        untraded.insert(feed.clone(), Utc::now());
+                    } else {
+                        untraded.remove(&feed);
+                    }
+                    let sent = if subscribe { provider.subscribe(&feed).await } else { provider.unsubscribe(&feed).await };
+                    if let Err(e) = sent {
+                        return ConnectionResult::Error(e);
+                    }
+                    log_to_file(&format!("WebSocket {} {}", if subscribe { "subscribed" } else { "unsubscribed" }, feed));
+                }
+                _ = flush.tick() => {
+                    for (_, batch) in std::mem::take(&mut batches) {
+                        if tx.send(batch).is_err() {
+                            return ConnectionResult::Disconnected;
+                        }
+                    }
                 }
             }
         }
     }
 }
 
+fn apply_command(subscriptions: &mut Subscriptions, command: Command) {
+    match command {
+        Command::Subscribe { feed, symbol } => { subscriptions.insert(feed, symbol); }
+        Command::Unsubscribe { feed } => { subscriptions.remove(&feed); }
+    }
+}
+
+/// When the market behind a feed reopens, if it's closed now. Crypto never
+/// closes, and without known hours there's nothing to wait for.
+fn market_reopens_at(hours: Option<&MarketHours>, provider: Provider) -> Option<chrono::DateTime<Utc>> {
+    let hours = hours.filter(|_| provider != Provider::Binance)?;
+    let now = Utc::now().timestamp();
+    if hours.is_trading_at(now) {
+        return None;
+    }
+    chrono::DateTime::from_timestamp(hours.next_session_after(now)?, 0)
+}
+
+/// Auth failures and bad symbols won't fix themselves on reconnect.
+fn is_recoverable(msg: &str) -> bool {
+    let msg = msg.to_lowercase();
+    !msg.contains("auth") && !msg.contains("invalid") && !msg.contains("api key")
+}
+
 #[derive(Debug)]
 enum ConnectionResult {
     Error(String),
+    /// The provider sent an error message of its own
+    Rejected(String),
     Disconnected,
+    /// Connected fine, but none of the subscriptions ever traded; they were
+    /// dropped from the connection and are returned
+    NoData(Subscriptions),
 }
 
-pub async fn start_websocket(
-    symbol: String,
+/// The configured Finnhub key, with stray quotes/whitespace from `.env` removed.
+pub fn finnhub_api_key() -> Option<String> {
+    std::env::var("FINNHUB_API_KEY")
+        .ok()
+        .map(|k| k.trim().trim_matches('"').trim_matches('\'').to_string())
+        .filter(|k| !k.is_empty())
+}
+
+/// Run `manager` for `symbols` (Yahoo symbols). Ones its provider has no feed
+/// for are logged and left out; if that leaves nothing, the first one's reason
+/// is reported as the status.
+pub async fn start_websocket<P: LiveProvider>(
+    manager: Arc<WebSocketManager<P>>,
+    symbols: Vec<String>,
     base_price: f64,
+    market_hours: Option<MarketHours>,
     tx: mpsc::UnboundedSender<LivePrice>,
     status_tx: mpsc::UnboundedSender<WebSocketStatus>,
     should_stop: Arc<Mutex<bool>>,
 ) {
-    let api_key = std::env::var("FINNHUB_API_KEY")
-        .ok()
-        .map(|k| k.trim().trim_matches('"').trim_matches('\'').to_string());
+    let mut pairs = Vec::new();
+    let mut first_failure = None;
+    for symbol in symbols {
+        match P::feed_symbol(&symbol) {
+            Ok(feed_symbol) => {
+                if feed_symbol != symbol {
+                    log_to_file(&format!("WebSocket mapped {} to {} symbol {}", symbol, P::KIND.name(), feed_symbol));
+                }
+                pairs.push((feed_symbol, symbol));
+            }
+            Err(reason) => {
+                // Subscribing would succeed and then never deliver a trade
+                log_to_file(&format!("WebSocket not subscribing to {}: {}", symbol, reason));
+                first_failure.get_or_insert(reason);
+            }
+        }
+    }
+    if pairs.is_empty() {
+        let message = first_failure.unwrap_or_else(|| "No symbols to stream".to_string());
+        let _ = status_tx.send(WebSocketStatus::Error { message, recoverable: false });
+        return;
+    }
+
+    manager.start(pairs, base_price, market_hours, tx, status_tx, should_stop).await;
+}
+
+/// Exchange suffixes Yahoo appends to non-US listings. Finnhub's websocket only
+/// streams US equities (plus crypto and forex), so these can't be subscribed.
+const FOREIGN_EXCHANGES: &[(&str, &str)] = &[
+    ("TO", "Toronto"), ("V", "TSX Venture"), ("NE", "Cboe Canada"), ("CN", "CSE"),
+    ("L", "London"), ("IL", "London IOB"), ("DE", "XETRA"), ("F", "Frankfurt"),
+    ("PA", "Euronext Paris"), ("AS", "Euronext Amsterdam"), ("BR", "Euronext Brussels"),
+    ("LS", "Euronext Lisbon"), ("MI", "Milan"), ("MC", "Madrid"), ("SW", "SIX Swiss"),
+    ("ST", "Stockholm"), ("OL", "Oslo"), ("CO", "Copenhagen"), ("HE", "Helsinki"),
+    ("VI", "Vienna"), ("WA", "Warsaw"), ("IR", "Dublin"), ("T", "Tokyo"),
+    ("HK", "Hong Kong"), ("SS", "Shanghai"), ("SZ", "Shenzhen"), ("KS", "Korea"),
+    ("KQ", "KOSDAQ"), ("TW", "Taiwan"), ("NS", "NSE India"), ("BO", "BSE India"),
+    ("AX", "ASX"), ("NZ", "NZX"), ("SI", "Singapore"), ("JK", "Jakarta"),
+    ("SA", "B3 São Paulo"), ("MX", "Mexico"), ("JO", "Johannesburg"), ("TA", "Tel Aviv"),
+];
+
+/// Translate a Yahoo symbol to what Finnhub's websocket expects:
+/// `BTC-USD` → `BINANCE:BTCUSDT`, `EURUSD=X` → `OANDA:EUR_USD`, `BRK-B` → `BRK.B`.
+/// Returns a user-facing reason when Finnhub has no live feed for the symbol.
+pub fn yahoo_to_finnhub_symbol(symbol: &str) -> Result<String, String> {
+    let symbol = symbol.trim().to_ascii_uppercase();
 
-    if api_key.is_none() || api_key.as_ref().map(|k| k.is_empty()).unwrap_or(true) {
-        let _ = status_tx.send(WebSocketStatus::Error {
-            message: "No API key configured. Set FINNHUB_API_KEY environment variable.".to_string(),
-            recoverable: false,
+    if symbol.starts_with('^') {
+        return Err("Live data not available for indices on Finnhub".to_string());
+    }
+    if symbol.ends_with("=F") {
+        return Err("Live data not available for futures on Finnhub".to_string());
+    }
+    // Five-letter symbols ending in X are mutual funds, priced once a day
+    if symbol.len() == 5 && symbol.ends_with('X') && symbol.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err("Live data not available for mutual funds on Finnhub".to_string());
+    }
+    if let Some(pair) = symbol.strip_suffix("=X") {
+        // "JPY=X" is Yahoo shorthand for USDJPY
+        let pair = if pair.len() == 3 { format!("USD{}", pair) } else { pair.to_string() };
+        if pair.len() != 6 {
+            return Err(format!("Unrecognised currency pair {}", symbol));
+        }
+        return Ok(format!("OANDA:{}_{}", &pair[..3], &pair[3..]));
+    }
+    if let Some((base, quote)) = symbol.rsplit_once('-') {
+        match quote {
+            // Binance has no USD book; USDT tracks it closely
+            "USD" => return Ok(format!("BINANCE:{}USDT", base)),
+            "USDT" | "EUR" | "BTC" | "ETH" => return Ok(format!("BINANCE:{}{}", base, quote)),
+            // Share classes: Yahoo BRK-B, Finnhub BRK.B
+            _ => return Ok(format!("{}.{}", base, quote)),
+        }
+    }
+    if let Some((_, suffix)) = symbol.rsplit_once('.') {
+        return Err(match FOREIGN_EXCHANGES.iter().find(|(s, _)| *s == suffix) {
+            Some((_, exchange)) => format!("Live data not available for this exchange ({}) on Finnhub", exchange),
+            None => format!("Live data not available for this exchange (.{}) on Finnhub", suffix),
         });
-        log_to_file("WebSocket Error: No API key configured");
-        return;
     }
+    Ok(symbol)
+}
+
+/// Delayed fallback feed: re-requests the latest Yahoo price every `interval`
+/// and forwards it as a `LivePrice` whenever it changes. Volume is reported as
+/// the growth in cumulative day volume since the previous poll.
+pub async fn start_polling(
+    symbol: String,
+    interval: Duration,
+    tx: mpsc::UnboundedSender<LivePrice>,
+    status_tx: mpsc::UnboundedSender<WebSocketStatus>,
+    should_stop: Arc<Mutex<bool>>,
+) {
+    let _ = status_tx.send(WebSocketStatus::Polling { interval });
+    log_to_file(&format!("Polling Yahoo for {} every {:?}", symbol, interval));
+
+    let mut last_seen: Option<(f64, i64)> = None;
+    let mut last_day_volume: Option<u64> = None;
+    let mut healthy = true;
+
+    loop {
+        if *should_stop.lock().await {
+            log_to_file("Polling stopped by user");
+            return;
+        }
+
+        let sym = symbol.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            crate::stock::fetch_latest_price(&sym).map_err(|e| e.to_string())
+        }).await.unwrap_or_else(|e| Err(e.to_string()));
+
+        match result {
+            Ok(latest) => {
+                if !healthy {
+                    healthy = true;
+                    let _ = status_tx.send(WebSocketStatus::Polling { interval });
+                }
+                let volume = match (last_day_volume, latest.day_volume) {
+                    (Some(prev), Some(cur)) if cur > prev => Some(cur - prev),
+                    _ => None,
+                };
+                if latest.day_volume.is_some() {
+                    last_day_volume = latest.day_volume;
+                }
+                if last_seen != Some((latest.price, latest.timestamp)) {
+                    last_seen = Some((latest.price, latest.timestamp));
+                    let live_price = LivePrice::trade(symbol.clone(), latest.price, latest.timestamp, volume);
+                    if tx.send(live_price).is_err() {
+                        return;
+                    }
+                }
+            }
+            Err(e) => {
+                log_to_file(&format!("Poll for {} failed: {}", symbol, e));
+                if healthy {
+                    healthy = false;
+                    let _ = status_tx.send(WebSocketStatus::Error {
+                        message: format!("Price poll failed: {}", e),
+                        recoverable: true,
+                    });
+                }
+            }
+        }
+
+        sleep_unless_stopped(interval, &should_stop).await;
+    }
+}
+
+/// Play a recording back through the live channel. The gap between two
+/// records is slept through at `1 / speed` of its recorded length, so the
+/// app sees the same sequence of batches it did live.
+pub async fn start_replay(
+    records: Vec<Record>,
+    speed: f64,
+    tx: mpsc::UnboundedSender<LivePrice>,
+    status_tx: mpsc::UnboundedSender<WebSocketStatus>,
+    should_stop: Arc<Mutex<bool>>,
+) {
+    let _ = status_tx.send(WebSocketStatus::Replay { speed });
+    log_to_file(&format!("Replaying {} records at {}x", records.len(), speed));
+
+    let mut previous: Option<i64> = None;
+    for record in records {
+        if *should_stop.lock().await {
+            log_to_file("Replay stopped by user");
+            return;
+        }
+        if let Some(prev) = previous {
+            let gap_ms = (record.received_ms - prev).max(0) as f64 / speed;
+            sleep_unless_stopped(Duration::from_secs_f64(gap_ms / 1000.0), &should_stop).await;
+        }
+        previous = Some(record.received_ms);
+        if tx.send(record.price).is_err() {
+            return;
+        }
+    }
+    let _ = status_tx.send(WebSocketStatus::Disconnected);
+}
+
+/// Sleep for `duration`, waking within a second of `should_stop` being set.
+async fn sleep_unless_stopped(duration: Duration, should_stop: &Arc<Mutex<bool>>) {
+    let wake = tokio::time::Instant::now() + duration;
+    loop {
+        let left = wake.saturating_duration_since(tokio::time::Instant::now());
+        if left.is_zero() || *should_stop.lock().await {
+            return;
+        }
+        tokio::time::sleep(left.min(Duration::from_secs(1))).await;
+    }
+}
 
-    let manager = WebSocketManager::new(api_key);
-    manager.start(symbol, base_price, tx, status_tx, should_stop).await;
-}
\ No newline at end of file
+type WsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+pub struct ConnectFailure {
+    /// Full message for the log
+    message: String,
+    /// Short message for the status bar
+    summary: String,
+    /// Retrying would just repeat the same rejection
+    fatal: bool,
+}
+
+impl ConnectFailure {
+    fn new(message: String, summary: &str, fatal: bool) -> Self {
+        Self { message, summary: summary.to_string(), fatal }
+    }
+}
+
+/// Open the websocket, tunnelling through the configured proxy to `host`
+/// when there is one.
+async fn connect(url: &str, (host, port): (&str, u16)) -> Result<WsStream, ConnectFailure> {
+    let result = match crate::config::proxy_url() {
+        Some(proxy) => {
+            let stream = connect_via_proxy(&proxy, host, port).await?;
+            client_async_tls(url, stream).await
+        }
+        None => connect_async(url).await,
+    };
+
+    result.map(|(ws_stream, _)| ws_stream).map_err(|e| {
+        let message = format!("Failed to connect: {}", e);
+        let error_str = e.to_string().to_lowercase();
+        if error_str.contains("auth") || error_str.contains("401") || error_str.contains("403") {
+            ConnectFailure::new(message, "Authentication failed", true)
+        } else {
+            ConnectFailure::new(message.clone(), &message, false)
+        }
+    })
+}
+
+/// Establish a CONNECT tunnel to `host:port` through an HTTP proxy.
+async fn connect_via_proxy(proxy: &str, host: &str, port: u16) -> Result<TcpStream, ConnectFailure> {
+    let without_scheme = proxy.split_once("://").map_or(proxy, |(_, rest)| rest);
+    let without_scheme = without_scheme.trim_end_matches('/');
+    let (credentials, address) = match without_scheme.rsplit_once('@') {
+        Some((creds, addr)) => (Some(creds), addr),
+        None => (None, without_scheme),
+    };
+    let address = if address.contains(':') {
+        address.to_string()
+    } else {
+        format!("{}:80", address)
+    };
+
+    let mut stream = TcpStream::connect(&address).await.map_err(|e| {
+        ConnectFailure::new(
+            format!("Could not reach proxy {}: {}", address, e),
+            "Could not reach proxy",
+            false,
+        )
+    })?;
+
+    let mut request = format!("CONNECT {host}:{port} HTTP/1.1\r\nHost: {host}:{port}\r\n");
+    if let Some(creds) = credentials {
+        request.push_str(&format!("Proxy-Authorization: Basic {}\r\n", base64_encode(creds.as_bytes())));
+    }
+    request.push_str("\r\n");
+
+    let io_failure = |e: std::io::Error| {
+        ConnectFailure::new(format!("Proxy {} dropped the connection: {}", address, e), "Proxy dropped the connection", false)
+    };
+    stream.write_all(request.as_bytes()).await.map_err(io_failure)?;
+
+    // Read just the response head; anything after it belongs to the tunnel
+    let mut head = Vec::new();
+    let mut byte = [0u8; 1];
+    while !head.ends_with(b"\r\n\r\n") {
+        if head.len() > 8192 || stream.read(&mut byte).await.map_err(io_failure)? == 0 {
+            return Err(ConnectFailure::new(
+                format!("Proxy {} closed the connection during CONNECT", address),
+                "Proxy dropped the connection",
+                false,
+            ));
+        }
+        head.push(byte[0]);
+    }
+
+    let head = String::from_utf8_lossy(&head);
+    let status_line = head.lines().next().unwrap_or_default();
+    let status = status_line.split_whitespace().nth(1).unwrap_or_default();
+    if status != "200" {
+        let summary = match status {
+            "407" => "Proxy rejected the connection: authentication required".to_string(),
+            _ => format!("Proxy rejected the connection: HTTP {}", status),
+        };
+        return Err(ConnectFailure::new(
+            format!("Proxy {} rejected CONNECT {}:{}: {}", address, host, port, status_line),
+            &summary,
+            true,
+        ));
+    }
+
+    log_to_file(&format!("WebSocket tunnelling through proxy {}", address));
+    Ok(stream)
+}
+
+fn base64_encode(input: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(input.len().div_ceil(3) * 4);
+    for chunk in input.chunks(3) {
+        let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = ((b[0] as u32) << 16) | ((b[1] as u32) << 8) | b[2] as u32;
+        out.push(ALPHABET[(n >> 18) as usize & 63] as char);
+        out.push(ALPHABET[(n >> 12) as usize & 63] as char);
+        out.push(if chunk.len() > 1 { ALPHABET[(n >> 6) as usize & 63] as char } else { '=' });
+        out.push(if chunk.len() > 2 { ALPHABET[n as usize & 63] as char } else { '=' });
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+    use tokio::task::JoinHandle;
+
+    /// Longer than `BATCH_INTERVAL`, so each scripted event is flushed on its own
+    const EVENT_GAP: Duration = Duration::from_millis(150);
+
+    /// A provider that replays `script` one event per `EVENT_GAP` and then
+    /// goes quiet, recording every call the manager makes on it.
+    struct MockLiveProvider {
+        script: VecDeque<FeedEvent>,
+        next_at: Option<tokio::time::Instant>,
+        calls: Arc<std::sync::Mutex<Vec<String>>>,
+    }
+
+    impl MockLiveProvider {
+        fn new(script: Vec<FeedEvent>) -> Self {
+            Self { script: script.into(), next_at: None, calls: Arc::default() }
+        }
+
+        fn record(&self, call: String) {
+            self.calls.lock().unwrap().push(call);
+        }
+    }
+
+    impl LiveProvider for MockLiveProvider {
+        const KIND: Provider = Provider::Finnhub;
+
+        fn feed_symbol(symbol: &str) -> Result<String, String> {
+            Ok(symbol.to_string())
+        }
+
+        async fn connect(&mut self) -> Result<(), ConnectFailure> {
+            self.record("connect".to_string());
+            Ok(())
+        }
+
+        async fn subscribe(&mut self, feed: &str) -> Result<(), String> {
+            self.record(format!("subscribe {}", feed));
+            Ok(())
+        }
+
+        async fn unsubscribe(&mut self, feed: &str) -> Result<(), String> {
+            self.record(format!("unsubscribe {}", feed));
+            Ok(())
+        }
+
+        async fn ping(&mut self) -> Result<(), String> {
+            Ok(())
+        }
+
+        async fn next_event(&mut self) -> FeedEvent {
+            if self.script.is_empty() {
+                return std::future::pending().await;
+            }
+            // The deadline survives a cancelled call, so every event arrives
+            let at = *self.next_at.get_or_insert_with(|| tokio::time::Instant::now() + EVENT_GAP);
+            tokio::time::sleep_until(at).await;
+            self.next_at = None;
+            self.script.pop_front().unwrap()
+        }
+
+        async fn shutdown(&mut self) {
+            self.record("shutdown".to_string());
+        }
+    }
+
+    struct Running {
+        manager: Arc<WebSocketManager<MockLiveProvider>>,
+        calls: Arc<std::sync::Mutex<Vec<String>>>,
+        prices: mpsc::UnboundedReceiver<LivePrice>,
+        statuses: mpsc::UnboundedReceiver<WebSocketStatus>,
+        should_stop: Arc<Mutex<bool>>,
+        task: JoinHandle<()>,
+    }
+
+    impl Running {
+        /// The next status `matches` accepts, skipping the rest.
+        async fn wait_for(&mut self, matches: impl Fn(&WebSocketStatus) -> bool) -> WebSocketStatus {
+            let wait = async {
+                loop {
+                    let status = self.statuses.recv().await.expect("task ended without the status");
+                    if matches(&status) {
+                        return status;
+                    }
+                }
+            };
+            tokio::time::timeout(Duration::from_secs(5), wait).await.expect("timed out waiting for status")
+        }
+
+        /// Set the stop flag and wait for the task to wind down.
+        async fn stop(self) -> Vec<String> {
+            *self.should_stop.lock().await = true;
+            tokio::time::timeout(Duration::from_secs(3), self.task).await
+                .expect("task didn't stop")
+                .unwrap();
+            self.calls.lock().unwrap().clone()
+        }
+    }
+
+    fn run(mock: MockLiveProvider, symbols: &[&str], no_trade_after: Option<Duration>, market_hours: Option<MarketHours>) -> Running {
+        let calls = mock.calls.clone();
+        let mut manager = WebSocketManager::new(mock);
+        if let Some(after) = no_trade_after {
+            manager.no_trade_after = after;
+        }
+        let manager = Arc::new(manager);
+        let (tx, prices) = mpsc::unbounded_channel();
+        let (status_tx, statuses) = mpsc::unbounded_channel();
+        let should_stop = Arc::new(Mutex::new(false));
+        let symbols = symbols.iter().map(|s| s.to_string()).collect();
+        let task = tokio::spawn(start_websocket(
+            manager.clone(), symbols, 100.0, market_hours, tx, status_tx, should_stop.clone(),
+        ));
+        Running { manager, calls, prices, statuses, should_stop, task }
+    }
+
+    fn trade(symbol: &str, price: f64, timestamp: i64, volume: u64) -> FeedEvent {
+        FeedEvent::Trades(vec![LivePrice::trade(symbol.to_string(), price, timestamp, Some(volume))])
+    }
+
+    #[test]
+    fn finnhub_symbols() {
+        let cases: &[(&str, Result<&str, &str>)] = &[
+            ("AAPL", Ok("AAPL")),
+            (" msft ", Ok("MSFT")),
+            ("BRK-B", Ok("BRK.B")),
+            ("BTC-USD", Ok("BINANCE:BTCUSDT")),
+            ("ETH-BTC", Ok("BINANCE:ETHBTC")),
+            ("SOL-USDT", Ok("BINANCE:SOLUSDT")),
+            ("EURUSD=X", Ok("OANDA:EUR_USD")),
+            ("JPY=X", Ok("OANDA:USD_JPY")),
+            ("VOD.L", Err("Live data not available for this exchange (London) on Finnhub")),
+            ("SHOP.TO", Err("Live data not available for this exchange (Toronto) on Finnhub")),
+            ("SAP.DE", Err("Live data not available for this exchange (XETRA) on Finnhub")),
+            ("0700.HK", Err("Live data not available for this exchange (Hong Kong) on Finnhub")),
+            ("FOO.ZZ", Err("Live data not available for this exchange (.ZZ) on Finnhub")),
+            ("^GSPC", Err("Live data not available for indices on Finnhub")),
+            ("^ftse", Err("Live data not available for indices on Finnhub")),
+            ("ES=F", Err("Live data not available for futures on Finnhub")),
+            ("VFIAX", Err("Live data not available for mutual funds on Finnhub")),
+            ("EURUSDX=X", Err("Unrecognised currency pair EURUSDX=X")),
+        ];
+        for &(symbol, expected) in cases {
+            let expected = expected.map(str::to_string).map_err(str::to_string);
+            assert_eq!(yahoo_to_finnhub_symbol(symbol), expected, "{}", symbol);
+        }
+    }
+
+    #[test]
+    fn foreign_exchanges_are_never_streamed() {
+        for (suffix, exchange) in FOREIGN_EXCHANGES {
+            let err = yahoo_to_finnhub_symbol(&format!("ABC.{}", suffix)).unwrap_err();
+            assert!(err.contains(exchange), "{}: {}", suffix, err);
+        }
+    }
+
+    #[test]
+    fn crypto_pairs_go_to_binance() {
+        assert_eq!(Provider::for_symbol("BTC-USD"), Provider::Binance);
+        assert_eq!(Provider::for_symbol("BRK-B"), Provider::Finnhub);
+        assert_eq!(BinanceProvider::feed_symbol("ETH-USD"), Ok("ETHUSDT".to_string()));
+        assert!(BinanceProvider::feed_symbol("AAPL").is_err());
+    }
+
+    #[test]
+    fn binance_keeps_fractional_quantities() {
+        let message = serde_json::json!({
+            "e": "trade", "s": "BTCUSDT", "p": "64000.50", "q": "0.00420", "T": 1_700_000_000_123i64,
+        });
+        let FeedEvent::Trades(trades) = BinanceProvider::parse(&message) else { panic!("expected a trade") };
+        let trade = &trades[0];
+        assert_eq!((trade.symbol.as_str(), trade.timestamp), ("BTCUSDT", 1_700_000_000));
+        assert_eq!(trade.quantity, 0.0042);
+        assert_eq!(trade.volume, Some(0));
+        assert!((trade.notional - 64_000.5 * 0.0042).abs() < 1e-9);
+
+        // Coalesced fractions round once, as a whole
+        let mut batch = trade.clone();
+        for _ in 0..249 {
+            batch.merge(trade.clone());
+        }
+        assert!((batch.size() - 1.05).abs() < 1e-9);
+        assert_eq!((batch.volume, batch.trades), (Some(1), 250));
+    }
+
+    #[test]
+    fn fractional_trades_add_up_in_the_app() {
+        let mut app = crate::ui::App::new();
+        app.symbol = "BTC-USD".to_string();
+        let minute = 1_700_000_000 / 60 * 60;
+        for (s, price) in [(0, 100.0), (10, 102.0), (20, 101.0), (30, 99.0)] {
+            let at = chrono::DateTime::from_timestamp(minute + s, 0).unwrap();
+            app.update_live_batch_at(&LivePrice::fractional_trade("BTC-USD".to_string(), price, minute + s, 0.4), at);
+        }
+
+        // 1.6 coins, though every trade alone rounds to nothing
+        assert_eq!(app.total_live_volume, 2);
+        assert_eq!(app.current_candle.as_ref().map(|c| c.volume), Some(2));
+        let vwap = app.session_vwap().unwrap();
+        assert!((vwap - 100.5).abs() < 1e-9, "{}", vwap);
+        assert!((app.average_trade_size().unwrap() - 0.4).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn rejected_symbol_is_not_retried() {
+        let mut running = run(MockLiveProvider::new(vec![FeedEvent::Rejected("Invalid symbol".to_string())]), &["AAPL"], None, None);
+        let status = running.wait_for(|s| matches!(s, WebSocketStatus::Error { .. })).await;
+        let WebSocketStatus::Error { message, recoverable } = status else { unreachable!() };
+        assert_eq!(message, "Finnhub: Invalid symbol");
+        assert!(!recoverable);
+
+        // Gives up on its own, without a stop
+        tokio::time::timeout(Duration::from_secs(1), &mut running.task).await.unwrap().unwrap();
+        assert_eq!(*running.manager.status.lock().await, ConnectionStatus::Error("Finnhub: Invalid symbol".to_string()));
+        assert_eq!(running.calls.lock().unwrap().iter().filter(|c| *c == "connect").count(), 1);
+    }
+
+    #[tokio::test]
+    async fn dropped_connection_backs_off_and_reconnects() {
+        let mut running = run(
+            MockLiveProvider::new(vec![FeedEvent::Closed(Some("WebSocket error: connection reset".to_string()))]),
+            &["AAPL"],
+            None,
+            None,
+        );
+        let status = running.wait_for(|s| matches!(s, WebSocketStatus::Reconnecting { .. })).await;
+        let WebSocketStatus::Reconnecting { attempt, next_retry_in, .. } = status else { unreachable!() };
+        let mut policy = ReconnectionPolicy::new();
+        policy.increment();
+        assert_eq!(attempt, 1);
+        assert!(next_retry_in <= policy.calculate_delay());
+        assert!(next_retry_in > policy.calculate_delay() - Duration::from_secs(1));
+
+        let calls = running.stop().await;
+        assert_eq!(calls.iter().filter(|c| *c == "connect").count(), 1);
+    }
+
+    #[tokio::test]
+    async fn untraded_feed_gives_up() {
+        let mut running = run(MockLiveProvider::new(Vec::new()), &["AAPL"], Some(Duration::from_millis(300)), None);
+        let status = running.wait_for(|s| matches!(s, WebSocketStatus::Error { .. })).await;
+        let WebSocketStatus::Error { message, recoverable } = status else { unreachable!() };
+        assert_eq!(message, "No live data available for this symbol");
+        assert!(!recoverable);
+
+        tokio::time::timeout(Duration::from_secs(1), &mut running.task).await.unwrap().unwrap();
+        assert_eq!(*running.calls.lock().unwrap(), ["connect", "subscribe AAPL", "unsubscribe AAPL", "shutdown"]);
+    }
+
+    #[tokio::test]
+    async fn untraded_feed_waits_for_a_closed_market() {
+        // An hour-long session starting two hours from now, every weekday
+        let now = Utc::now().timestamp();
+        let session_start = now - 10 * 86_400 + 7_200;
+        let hours = MarketHours {
+            session_start,
+            session_end: session_start + 3_600,
+            regular_start: session_start,
+            regular_end: session_start + 3_600,
+            gmt_offset: 0,
+            always_open: false,
+        };
+        let mut running = run(MockLiveProvider::new(Vec::new()), &["AAPL"], Some(Duration::from_millis(300)), Some(hours));
+        let status = running.wait_for(|s| matches!(s, WebSocketStatus::MarketClosed { .. } | WebSocketStatus::Error { .. })).await;
+        let WebSocketStatus::MarketClosed { reopens_at } = status else { panic!("gave up: {:?}", status) };
+        assert!(reopens_at.timestamp() >= now + 7_200);
+
+        let calls = running.stop().await;
+        assert_eq!(calls, ["connect", "subscribe AAPL", "unsubscribe AAPL", "shutdown"]);
+    }
+
+    #[tokio::test]
+    async fn trades_bucket_into_candles() {
+        // The first two share a minute candle; the third starts the next
+        let minute = 1_700_000_000 / 60 * 60;
+        let script = vec![
+            trade("AAPL", 100.0, minute + 5, 10),
+            trade("AAPL", 102.0, minute + 40, 20),
+            trade("AAPL", 101.0, minute + 70, 5),
+        ];
+        let mut running = run(MockLiveProvider::new(script), &["AAPL"], None, None);
+
+        let mut app = crate::ui::App::new();
+        app.candle_interval = crate::ui::CandleInterval::OneMinute;
+        let mut trades = 0;
+        while trades < 3 {
+            let batch = tokio::time::timeout(Duration::from_secs(5), running.prices.recv()).await
+                .expect("timed out waiting for trades")
+                .unwrap();
+            assert_eq!(batch.symbol, "AAPL");
+            trades += batch.trades;
+            let at = chrono::DateTime::from_timestamp(batch.timestamp, 0).unwrap();
+            app.update_live_batch_at(&batch, at);
+        }
+        running.stop().await;
+
+        assert_eq!(app.live_candles.len(), 1);
+        let closed = &app.live_candles[0];
+        assert_eq!((closed.open, closed.high, closed.low, closed.close), (100.0, 102.0, 100.0, 102.0));
+        assert_eq!((closed.volume, closed.trade_count), (30, 2));
+        let forming = app.current_candle.as_ref().unwrap();
+        assert_eq!(forming.timestamp.timestamp(), minute + 70);
+        assert_eq!((forming.open, forming.close, forming.volume), (101.0, 101.0, 5));
+        assert_eq!(app.total_trade_count, 3);
+    }
+
+    #[tokio::test]
+    async fn stop_unsubscribes_and_closes_first() {
+        let mut running = run(MockLiveProvider::new(Vec::new()), &["AAPL", "MSFT"], None, None);
+        running.wait_for(|s| matches!(s, WebSocketStatus::Connected { .. })).await;
+        // Subscribed on the running connection, so it needs undoing too
+        running.manager.subscribe("TSLA").unwrap();
+        tokio::time::timeout(Duration::from_secs(2), async {
+            while !running.calls.lock().unwrap().contains(&"subscribe TSLA".to_string()) {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        }).await.expect("TSLA never subscribed");
+
+        let mut handle = Some(running.task);
+        crate::stop_websocket(&mut handle, &running.should_stop).await;
+        assert!(handle.is_none());
+        assert_eq!(*running.calls.lock().unwrap(), [
+            "connect", "subscribe AAPL", "subscribe MSFT", "subscribe TSLA",
+            "unsubscribe AAPL", "unsubscribe MSFT", "unsubscribe TSLA", "shutdown",
+        ]);
+        assert_eq!(*running.manager.status.lock().await, ConnectionStatus::Disconnected);
+    }
+}